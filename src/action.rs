@@ -0,0 +1,303 @@
+/// A named thing the user can do, bound to keys per screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    Back,
+    MoveUp,
+    MoveDown,
+    GoTop,
+    GoBottom,
+    Select,
+    Confirm,
+    NewList,
+    NewTask,
+    SetGroup,
+    ToggleGroup,
+    ToggleAllGroups,
+    EditTask,
+    OpenNotes,
+    ToggleComplete,
+    ToggleMyDay,
+    DeleteSelected,
+    MoveTask,
+    ReorderUp,
+    ReorderDown,
+    ToggleVisual,
+    ToggleMark,
+    SetDueDate,
+    AddTag,
+    OpenMyDay,
+    OpenHistory,
+    OpenTrash,
+    Restore,
+    Purge,
+    EmptyTrash,
+    Search,
+    CycleListFilter,
+    Undo,
+    Redo,
+    OpenCommandLine,
+    OpenPalette,
+    Complete,
+    HalfPageDown,
+    HalfPageUp,
+    RepeatLast,
+    SetMark,
+    JumpToMark,
+    PageDown,
+    PageUp,
+    CycleTheme,
+    OpenWorkspaces,
+    QuickAdd,
+    OpenCalendar,
+    MoveLeft,
+    MoveRight,
+    GoToday,
+    NextTask,
+    Reschedule,
+    OpenUpcoming,
+    ToggleBoard,
+    MoveTaskLeft,
+    MoveTaskRight,
+    OpenStats,
+    ToggleSuggestions,
+    ToggleTimer,
+    OpenTimesheet,
+}
+
+impl Action {
+    pub const ALL: &'static [Action] = &[
+        Action::Quit,
+        Action::Back,
+        Action::MoveUp,
+        Action::MoveDown,
+        Action::GoTop,
+        Action::GoBottom,
+        Action::Select,
+        Action::Confirm,
+        Action::NewList,
+        Action::NewTask,
+        Action::SetGroup,
+        Action::ToggleGroup,
+        Action::ToggleAllGroups,
+        Action::EditTask,
+        Action::OpenNotes,
+        Action::ToggleComplete,
+        Action::ToggleMyDay,
+        Action::DeleteSelected,
+        Action::MoveTask,
+        Action::ReorderUp,
+        Action::ReorderDown,
+        Action::ToggleVisual,
+        Action::ToggleMark,
+        Action::SetDueDate,
+        Action::AddTag,
+        Action::OpenMyDay,
+        Action::OpenHistory,
+        Action::OpenTrash,
+        Action::Restore,
+        Action::Purge,
+        Action::EmptyTrash,
+        Action::Search,
+        Action::CycleListFilter,
+        Action::Undo,
+        Action::Redo,
+        Action::OpenCommandLine,
+        Action::OpenPalette,
+        Action::Complete,
+        Action::HalfPageDown,
+        Action::HalfPageUp,
+        Action::RepeatLast,
+        Action::SetMark,
+        Action::JumpToMark,
+        Action::PageDown,
+        Action::PageUp,
+        Action::CycleTheme,
+        Action::OpenWorkspaces,
+        Action::QuickAdd,
+        Action::OpenCalendar,
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::GoToday,
+        Action::NextTask,
+        Action::Reschedule,
+        Action::OpenUpcoming,
+        Action::ToggleBoard,
+        Action::MoveTaskLeft,
+        Action::MoveTaskRight,
+        Action::OpenStats,
+        Action::ToggleSuggestions,
+        Action::ToggleTimer,
+        Action::OpenTimesheet,
+    ];
+
+    /// Name used for the action in the keymap file
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Back => "back",
+            Action::MoveUp => "move_up",
+            Action::MoveDown => "move_down",
+            Action::GoTop => "go_top",
+            Action::GoBottom => "go_bottom",
+            Action::Select => "select",
+            Action::Confirm => "confirm",
+            Action::NewList => "new_list",
+            Action::NewTask => "new_task",
+            Action::SetGroup => "set_group",
+            Action::ToggleGroup => "toggle_group",
+            Action::ToggleAllGroups => "toggle_all_groups",
+            Action::EditTask => "edit_task",
+            Action::OpenNotes => "open_notes",
+            Action::ToggleComplete => "toggle_complete",
+            Action::ToggleMyDay => "toggle_my_day",
+            Action::DeleteSelected => "delete_selected",
+            Action::MoveTask => "move_task",
+            Action::ReorderUp => "reorder_up",
+            Action::ReorderDown => "reorder_down",
+            Action::ToggleVisual => "toggle_visual",
+            Action::ToggleMark => "toggle_mark",
+            Action::SetDueDate => "set_due_date",
+            Action::AddTag => "add_tag",
+            Action::OpenMyDay => "open_my_day",
+            Action::OpenHistory => "open_history",
+            Action::OpenTrash => "open_trash",
+            Action::Restore => "restore",
+            Action::Purge => "purge",
+            Action::EmptyTrash => "empty_trash",
+            Action::Search => "search",
+            Action::CycleListFilter => "cycle_list_filter",
+            Action::Undo => "undo",
+            Action::Redo => "redo",
+            Action::OpenCommandLine => "command_line",
+            Action::OpenPalette => "command_palette",
+            Action::Complete => "complete",
+            Action::HalfPageDown => "half_page_down",
+            Action::HalfPageUp => "half_page_up",
+            Action::RepeatLast => "repeat",
+            Action::SetMark => "set_mark",
+            Action::JumpToMark => "jump_to_mark",
+            Action::PageDown => "page_down",
+            Action::PageUp => "page_up",
+            Action::CycleTheme => "cycle_theme",
+            Action::OpenWorkspaces => "open_workspaces",
+            Action::QuickAdd => "quick_add",
+            Action::OpenCalendar => "open_calendar",
+            Action::MoveLeft => "move_left",
+            Action::MoveRight => "move_right",
+            Action::GoToday => "go_today",
+            Action::NextTask => "next_task",
+            Action::Reschedule => "reschedule",
+            Action::OpenUpcoming => "open_upcoming",
+            Action::ToggleBoard => "toggle_board",
+            Action::MoveTaskLeft => "move_task_left",
+            Action::MoveTaskRight => "move_task_right",
+            Action::OpenStats => "open_stats",
+            Action::ToggleSuggestions => "toggle_suggestions",
+            Action::ToggleTimer => "toggle_timer",
+            Action::OpenTimesheet => "open_timesheet",
+        }
+    }
+
+    /// Short description shown in the footer help
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::Quit => "Quit",
+            Action::Back => "Back",
+            Action::MoveUp => "Up",
+            Action::MoveDown => "Down",
+            Action::GoTop => "Top",
+            Action::GoBottom => "Bottom",
+            Action::Select => "Select",
+            Action::Confirm => "Save",
+            Action::NewList => "New List",
+            Action::NewTask => "New",
+            Action::SetGroup => "Set Group",
+            Action::ToggleGroup => "Expand/Collapse",
+            Action::ToggleAllGroups => "Fold Groups",
+            Action::EditTask => "Edit",
+            Action::OpenNotes => "View Notes",
+            Action::ToggleComplete => "Toggle",
+            Action::ToggleMyDay => "My Day",
+            Action::DeleteSelected => "Delete",
+            Action::MoveTask => "Move",
+            Action::ReorderUp => "Reorder Up",
+            Action::ReorderDown => "Reorder Down",
+            Action::ToggleVisual => "Visual",
+            Action::ToggleMark => "Mark",
+            Action::SetDueDate => "Due Date",
+            Action::AddTag => "Tag",
+            Action::OpenMyDay => "My Day",
+            Action::OpenHistory => "History",
+            Action::OpenTrash => "Trash",
+            Action::Restore => "Restore",
+            Action::Purge => "Purge",
+            Action::EmptyTrash => "Empty Trash",
+            Action::Search => "Search",
+            Action::CycleListFilter => "Filter by List",
+            Action::Undo => "Undo",
+            Action::Redo => "Redo",
+            Action::OpenCommandLine => "Command",
+            Action::OpenPalette => "Palette",
+            Action::Complete => "Complete",
+            Action::HalfPageDown => "Half Page Down",
+            Action::HalfPageUp => "Half Page Up",
+            Action::RepeatLast => "Repeat",
+            Action::SetMark => "Set Mark",
+            Action::JumpToMark => "Go to Mark",
+            Action::PageDown => "Page Down",
+            Action::PageUp => "Page Up",
+            Action::CycleTheme => "Next Theme",
+            Action::OpenWorkspaces => "Workspaces",
+            Action::QuickAdd => "Quick Add",
+            Action::OpenCalendar => "Calendar",
+            Action::MoveLeft => "Left",
+            Action::MoveRight => "Right",
+            Action::GoToday => "Today",
+            Action::NextTask => "Next Task",
+            Action::Reschedule => "Reschedule",
+            Action::OpenUpcoming => "Upcoming",
+            Action::ToggleBoard => "Board/List",
+            Action::MoveTaskLeft => "Move Left",
+            Action::MoveTaskRight => "Move Right",
+            Action::OpenStats => "Statistics",
+            Action::ToggleSuggestions => "Suggestions",
+            Action::ToggleTimer => "Start/Stop Timer",
+            Action::OpenTimesheet => "Timesheet",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Action> {
+        Action::ALL.iter().copied().find(|action| action.name() == name)
+    }
+
+    /// Cursor movements, which a count repeats one step at a time
+    pub fn is_motion(&self) -> bool {
+        matches!(
+            self,
+            Action::MoveUp
+                | Action::MoveDown
+                | Action::MoveLeft
+                | Action::MoveRight
+                | Action::HalfPageDown
+                | Action::HalfPageUp
+                | Action::PageDown
+                | Action::PageUp
+        )
+    }
+
+    /// Changes to tasks that `.` can repeat
+    pub fn is_repeatable(&self) -> bool {
+        matches!(
+            self,
+            Action::ToggleComplete
+                | Action::ToggleMyDay
+                | Action::DeleteSelected
+                | Action::ReorderUp
+                | Action::ReorderDown
+                | Action::ToggleMark
+                | Action::MoveTaskLeft
+                | Action::MoveTaskRight
+        )
+    }
+}
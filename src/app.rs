@@ -1,5 +1,17 @@
-use crate::models::{List, Storage, Task};
-use anyhow::{Context, Result};
+use crate::config::{Config, DeferredTasks, StartScreen};
+use crate::action::Action;
+use crate::history::{Change, Command, History, Snapshot};
+use crate::keymap::{KeyChord, Keymap};
+use crate::theme::Theme;
+use crate::models::{List, Storage, Task, TimeEntry};
+use crate::ui::scroll::ViewState;
+use crate::utils::date_utils::DueDate;
+use crate::utils::timesheet::{self, TimesheetDay};
+use crate::workspace::WorkspaceSummary;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Local, NaiveDate};
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum AppState {
@@ -10,6 +22,17 @@ pub enum AppState {
     ListEditor,
     NoteEditor,
     MoveTask,
+    Trash,
+    CompletedHistory,
+    BulkEdit,
+    CommandLine,
+    CommandPalette,
+    Workspaces,
+    QuickAdd,
+    Calendar,
+    Upcoming,
+    Stats,
+    Timesheet,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -18,6 +41,211 @@ pub enum TaskEditorMode {
     Edit,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum ListEditorMode {
+    Create,
+    Group,
+}
+
+/// A row in a list sidebar: either a group header or a list
+#[derive(Debug, Clone, PartialEq)]
+pub enum SidebarItem {
+    Group { name: String, collapsed: bool },
+    List(u32),
+}
+
+/// A row on the trash screen. Tasks deleted together with their list are
+/// shown as part of the list rather than on their own.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrashItem {
+    List(u32),
+    Task(u32),
+}
+
+/// The field being set on every selected task from the bulk edit prompt
+#[derive(Debug, Clone, PartialEq)]
+pub enum BulkEditField {
+    DueDate,
+    Tag,
+}
+
+/// Order of the tasks in a list. Manual keeps the order they were added in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TaskSort {
+    Manual,
+    DueDate,
+    Title,
+    Created,
+}
+
+impl TaskSort {
+    pub const ALL: &'static [TaskSort] = &[TaskSort::Manual, TaskSort::DueDate, TaskSort::Title, TaskSort::Created];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TaskSort::Manual => "manual",
+            TaskSort::DueDate => "due",
+            TaskSort::Title => "title",
+            TaskSort::Created => "created",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<TaskSort> {
+        TaskSort::ALL.iter().copied().find(|sort| sort.name() == name)
+    }
+}
+
+/// Which tasks a list shows. Deferred tasks, whose start date hasn't come
+/// yet, are left out of the actionable ones unless `deferred_tasks` is set
+/// to dim them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ListFilter {
+    Actionable,
+    Deferred,
+    All,
+}
+
+impl ListFilter {
+    pub const ALL: &'static [ListFilter] = &[ListFilter::Actionable, ListFilter::Deferred, ListFilter::All];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ListFilter::Actionable => "actionable",
+            ListFilter::Deferred => "deferred",
+            ListFilter::All => "all",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<ListFilter> {
+        ListFilter::ALL.iter().copied().find(|filter| filter.name() == name)
+    }
+
+    pub fn shows(&self, task: &Task, deferred_tasks: DeferredTasks) -> bool {
+        match self {
+            ListFilter::Actionable => !task.is_deferred() || deferred_tasks == DeferredTasks::Dim,
+            ListFilter::Deferred => task.is_deferred(),
+            ListFilter::All => true,
+        }
+    }
+}
+
+/// The last change made from normal mode, which `.` repeats
+#[derive(Debug, Clone, PartialEq)]
+pub enum LastChange {
+    Action { action: Action, count: usize },
+    BulkEdit { field: BulkEditField, input: String },
+}
+
+/// The last click, to recognise double clicks, and the task being dragged
+#[derive(Debug, Clone, Default)]
+pub struct MouseState {
+    pub last_click: Option<(Instant, u16, u16)>,
+    pub drag_task_id: Option<u32>,
+}
+
+/// Days from today that get a header of their own on the Upcoming screen,
+/// and the days recurring tasks are projected over
+pub const UPCOMING_DAYS: i64 = 7;
+
+/// A row under a date header on the Upcoming screen: an open task, or a
+/// later occurrence of a recurring task that hasn't been created yet
+#[derive(Debug, Clone, Copy)]
+pub enum UpcomingEntry<'a> {
+    Task(&'a Task),
+    Projected(&'a Task, DateTime<Local>),
+}
+
+impl UpcomingEntry<'_> {
+    fn due_date(&self) -> Option<DateTime<Local>> {
+        match self {
+            UpcomingEntry::Task(task) => task.due_date,
+            UpcomingEntry::Projected(_, due_date) => Some(*due_date),
+        }
+    }
+}
+
+/// Tasks under one date header on the Upcoming screen
+#[derive(Debug)]
+pub struct UpcomingGroup<'a> {
+    pub title: String,
+    /// The day of a group for one of the coming days
+    pub date: Option<NaiveDate>,
+    pub entries: Vec<UpcomingEntry<'a>>,
+}
+
+/// Days from today that tasks due are suggested for My Day
+pub const SUGGESTION_DAYS: i64 = 3;
+/// Days a newly created task is suggested for My Day
+const RECENT_DAYS: i64 = 2;
+
+/// Why a task is suggested for My Day, in the order suggestions are listed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SuggestionReason {
+    Overdue,
+    /// Left unfinished in My Day on an earlier day
+    Unfinished(NaiveDate),
+    DueSoon,
+    Recent,
+}
+
+impl SuggestionReason {
+    pub fn label(&self, today: NaiveDate) -> String {
+        match self {
+            SuggestionReason::Overdue => "Overdue".to_string(),
+            SuggestionReason::Unfinished(date) if *date == today - Duration::days(1) => "Unfinished yesterday".to_string(),
+            SuggestionReason::Unfinished(date) => format!("Unfinished since {}", date.format("%b %-d")),
+            SuggestionReason::DueSoon => "Due soon".to_string(),
+            SuggestionReason::Recent => "Recently added".to_string(),
+        }
+    }
+}
+
+/// A task that could be added to My Day
+#[derive(Debug, Clone, Copy)]
+pub struct Suggestion<'a> {
+    pub task: &'a Task,
+    pub reason: SuggestionReason,
+}
+
+/// Which pane of My Day has the cursor. The other pane's row is kept for
+/// when the cursor comes back.
+#[derive(Debug, Clone, Default)]
+pub struct MyDayState {
+    pub suggestions_focused: bool,
+    pub inactive_index: usize,
+}
+
+/// The day picked on the calendar, and the task being moved to another day
+#[derive(Debug, Clone)]
+pub struct CalendarState {
+    pub day: NaiveDate,
+    pub moving_task_id: Option<u32>,
+}
+
+impl Default for CalendarState {
+    fn default() -> Self {
+        Self {
+            day: Local::now().date_naive(),
+            moving_task_id: None,
+        }
+    }
+}
+
+/// The focused column of a list shown as a board. The cursor moves through
+/// the tasks of that column.
+#[derive(Debug, Clone, Default)]
+pub struct BoardState {
+    pub column: usize,
+}
+
+/// Search and list filter for the completed history screen
+#[derive(Debug, Clone, Default)]
+pub struct HistoryFilter {
+    pub query: String,
+    pub list_id: Option<u32>,
+    pub searching: bool,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum TaskEditorState {
     Title,
@@ -30,32 +258,82 @@ pub struct App {
     pub state: AppState,
     pub tasks: Vec<Task>,
     pub lists: Vec<List>,
+    pub trash_tasks: Vec<Task>,
+    pub trash_lists: Vec<List>,
+    pub archived_tasks: Vec<Task>,
+    pub time_entries: Vec<TimeEntry>,
     pub storage: Storage,
+    pub config: Config,
+    pub keymap: Keymap,
+    pub theme: Theme,
+    pub pending_keys: Vec<KeyChord>, // Keys typed so far of a multi-key binding
+    pub count: Option<usize>,        // Count typed before a normal mode action
+    pub pending_mark: Option<Action>, // Set or jump, waiting for the mark letter
+    pub pending_confirmation: Option<(Action, Option<usize>)>, // Delete waiting for y/n, with its count
+    pub marks: HashMap<char, u32>,   // Mark letter to task id
+    pub last_change: Option<LastChange>,
+    pub view_state: ViewState,
+    pub mouse: MouseState,
     pub current_list_id: Option<u32>,
     pub selected_task_index: usize,
     pub task_editor_mode: TaskEditorMode,
     pub task_editor_state: TaskEditorState,
+    pub list_editor_mode: ListEditorMode,
     pub editing_task: Option<Task>,
+    pub editing_list_id: Option<u32>,
     pub input_buffer: String,
     pub should_quit: bool,
-    pub moving_task: Option<Task>,
+    pub moving_task_ids: Vec<u32>,
+    pub marked_task_ids: Vec<u32>,
+    pub visual_anchor: Option<usize>, // Display index where visual mode started
+    pub bulk_edit_field: BulkEditField,
+    pub previous_state: AppState,
     pub my_day_task_order: Vec<u32>, // Task IDs in display order for My Day
+    pub collapsed_groups: HashSet<String>,
+    pub history: History,
+    pub status_message: Option<String>,
+    pub history_filter: HistoryFilter,
+    pub calendar: CalendarState,
+    pub board: BoardState,
+    pub my_day: MyDayState,
+    pub task_sort: TaskSort,
+    pub list_filter: ListFilter,
+    pub command_history: Vec<String>,
+    pub command_history_index: Option<usize>, // Entry shown while browsing command history
+    pub palette_index: usize,
+    pub workspace_summaries: Vec<WorkspaceSummary>,
+    recording_batch: bool,
+    tidied_on: NaiveDate, // Day the daily housekeeping last ran
 }
 
 impl App {
-    pub fn new() -> Result<Self> {
-        // Get the home directory and create todo-data folder
-        let home_dir = dirs::home_dir()
-            .context("Could not find home directory")?;
-        let data_dir = home_dir.join("todo-data");
-        
+    pub fn new(config: Config) -> Result<Self> {
+        let keymap = Keymap::load()?;
+        let theme = Theme::load(&config.theme)?.for_color_mode(config.color_mode);
+        Self::open(config, keymap, theme)
+    }
+
+    /// Open the task data without reading the keymap and theme files, which
+    /// only the interface needs. Used by subcommands, so a broken keymap or
+    /// unknown theme doesn't stop scripts.
+    pub fn without_interface(config: Config) -> Result<Self> {
+        Self::open(config, Keymap::default_keymap(), Theme::default())
+    }
+
+    fn open(config: Config, keymap: Keymap, theme: Theme) -> Result<Self> {
+        let data_dir = config.data_dir()?;
+
         // Create the data directory if it doesn't exist
         std::fs::create_dir_all(&data_dir)
-            .context("Failed to create todo-data directory")?;
+            .with_context(|| format!("Failed to create data directory {}", data_dir.display()))?;
         
         let data_dir_str = data_dir.to_string_lossy().to_string();
         let mut storage = Storage::new(&data_dir_str);
         let (tasks, lists) = storage.load_all()?;
+        let (trash_tasks, trash_lists) = storage.load_trash()?;
+        let archived_tasks = storage.load_archive()?;
+        let time_entries = storage.load_time_entries()?;
+        let collapsed_groups = storage.load_collapsed_groups()?;
 
         // Ensure we have at least one default list
         let mut lists = lists;
@@ -68,43 +346,175 @@ impl App {
         // Initialize My Day task order
         let my_day_task_order: Vec<u32> = tasks
             .iter()
-            .filter(|task| task.is_in_my_day || task.is_due_today())
+            .filter(|task| config.shows_in_my_day(task))
             .map(|task| task.id)
             .collect();
 
-        Ok(Self {
+        let mut app = Self {
             state: AppState::ListOverview,
             tasks,
             lists,
+            trash_tasks,
+            trash_lists,
+            archived_tasks,
+            time_entries,
             storage,
+            config,
+            keymap,
+            theme,
+            pending_keys: Vec::new(),
+            count: None,
+            pending_mark: None,
+            pending_confirmation: None,
+            marks: HashMap::new(),
+            last_change: None,
+            view_state: ViewState::default(),
+            mouse: MouseState::default(),
             current_list_id: None,
             selected_task_index: 0,
             task_editor_mode: TaskEditorMode::Create,
             task_editor_state: TaskEditorState::Title,
+            list_editor_mode: ListEditorMode::Create,
             editing_task: None,
+            editing_list_id: None,
             input_buffer: String::new(),
             should_quit: false,
-            moving_task: None,
+            moving_task_ids: Vec::new(),
+            marked_task_ids: Vec::new(),
+            visual_anchor: None,
+            bulk_edit_field: BulkEditField::DueDate,
+            previous_state: AppState::ListOverview,
             my_day_task_order,
-        })
+            collapsed_groups,
+            history: History::default(),
+            status_message: None,
+            history_filter: HistoryFilter::default(),
+            calendar: CalendarState::default(),
+            board: BoardState::default(),
+            my_day: MyDayState::default(),
+            task_sort: TaskSort::Manual,
+            list_filter: ListFilter::Actionable,
+            command_history: Vec::new(),
+            command_history_index: None,
+            palette_index: 0,
+            workspace_summaries: Vec::new(),
+            recording_batch: false,
+            tidied_on: NaiveDate::MIN,
+        };
+        app.tidy_up()?;
+        app.open_start_screen();
+
+        Ok(app)
+    }
+
+
+    fn open_start_screen(&mut self) {
+        match self.config.start_screen {
+            StartScreen::Lists => {}
+            StartScreen::MyDay => self.state = AppState::MyDay,
+            StartScreen::DefaultList => {
+                let name = self.config.default_list.clone().unwrap_or_default();
+                match self.lists.iter().find(|l| l.name.eq_ignore_ascii_case(&name)) {
+                    Some(list) => self.set_current_list(list.id),
+                    None => self.status_message = Some(format!("No default list named '{}'", name)),
+                }
+            }
+        }
+    }
+
+    /// Close this workspace and open another. Undo history and marks belong to
+    /// the workspace they were made in, so they don't carry over.
+    pub fn switch_workspace(&mut self, name: &str) -> Result<()> {
+        let mut config = self.config.clone();
+        config.workspace = name.to_string();
+        let mut app = App::new(config)?;
+
+        // Session settings carry over
+        app.theme = self.theme.clone();
+        app.task_sort = self.task_sort;
+        app.list_filter = self.list_filter;
+        app.command_history = std::mem::take(&mut self.command_history);
+        *self = app;
+        Ok(())
     }
 
+    /// The screen under the command line or palette, or the current one
+    pub fn screen_state(&self) -> &AppState {
+        match self.state {
+            AppState::CommandLine | AppState::CommandPalette => &self.previous_state,
+            _ => &self.state,
+        }
+    }
 
     pub fn get_current_list_tasks(&self) -> Vec<&Task> {
-        if let Some(list_id) = self.current_list_id {
-            self.tasks
-                .iter()
-                .filter(|task| task.list_id == list_id)
-                .collect()
-        } else {
-            vec![]
+        let Some(list_id) = self.current_list_id else {
+            return vec![];
+        };
+
+        let mut tasks: Vec<&Task> = self.tasks
+            .iter()
+            .filter(|task| task.list_id == list_id)
+            .filter(|task| self.list_filter.shows(task, self.config.deferred_tasks))
+            .collect();
+        match self.task_sort {
+            TaskSort::Manual => {}
+            // Tasks without a due date go last. All-day dates are kept at the
+            // end of their day, so they follow that day's timed tasks.
+            TaskSort::DueDate => tasks.sort_by_key(|task| (task.due_date.is_none(), task.due_date)),
+            TaskSort::Title => tasks.sort_by_key(|task| task.title.to_lowercase()),
+            TaskSort::Created => tasks.sort_by_key(|task| task.created_at),
         }
+        tasks
+    }
+
+    /// The current list, when it's shown as a board
+    pub fn current_board(&self) -> Option<&List> {
+        self.get_current_list().filter(|list| list.board)
+    }
+
+    /// Tasks of the current list in one column of its board, in list order
+    pub fn get_board_column_tasks(&self, column: usize) -> Vec<&Task> {
+        let Some(list) = self.current_board() else {
+            return vec![];
+        };
+        self.get_current_list_tasks()
+            .into_iter()
+            .filter(|task| list.column_of(task) == column)
+            .collect()
+    }
+
+    /// Move tasks to a column of the current board. Moving them to the last
+    /// column completes them, and moving them out of it reopens them.
+    pub fn move_tasks_to_column(&mut self, task_ids: &[u32], column: usize) -> Result<()> {
+        let Some(statuses) = self.current_board().map(|list| list.statuses()) else {
+            return Ok(());
+        };
+        let done = statuses.len() - 1;
+        let column = column.min(done);
+        self.run_batch(&format!("Move to {}", statuses[column]), |app| {
+            for &task_id in task_ids {
+                let Some(is_completed) = app.tasks.iter().find(|t| t.id == task_id).map(|t| t.is_completed) else {
+                    continue;
+                };
+                // Completing goes through the usual path, so repeating tasks
+                // get their next instance
+                if is_completed != (column == done) {
+                    app.toggle_task_completion(task_id)?;
+                }
+                if column < done {
+                    if let Some(task) = app.tasks.iter_mut().find(|t| t.id == task_id) {
+                        task.set_status(Some(statuses[column].clone()));
+                    }
+                }
+            }
+            app.save_tasks()
+        })
     }
 
     pub fn get_my_day_tasks(&self) -> Vec<&Task> {
         let mut tasks: Vec<&Task> = self.tasks
             .iter()
-            .filter(|task| task.is_in_my_day || task.is_due_today())
+            .filter(|task| self.config.shows_in_my_day(task))
             .collect();
         
         // Sort by the order in my_day_task_order
@@ -116,6 +526,105 @@ impl App {
         tasks
     }
 
+    /// Open tasks that My Day doesn't show yet and might be worth doing
+    /// today, each with the first reason that applies
+    pub fn get_my_day_suggestions(&self) -> Vec<Suggestion<'_>> {
+        let today = Local::now().date_naive();
+        let mut suggestions: Vec<Suggestion> = self
+            .tasks
+            .iter()
+            .filter(|task| !task.is_completed && !task.is_deferred() && !self.config.shows_in_my_day(task))
+            .filter_map(|task| {
+                let reason = if task.is_overdue() {
+                    SuggestionReason::Overdue
+                } else if let Some(date) = task.my_day_date.filter(|date| *date < today) {
+                    SuggestionReason::Unfinished(date)
+                } else if task.due_date.is_some_and(|due| (due.date_naive() - today).num_days() < SUGGESTION_DAYS) {
+                    SuggestionReason::DueSoon
+                } else if (today - task.created_at.date_naive()).num_days() < RECENT_DAYS {
+                    SuggestionReason::Recent
+                } else {
+                    return None;
+                };
+                Some(Suggestion { task, reason })
+            })
+            .collect();
+        suggestions.sort_by_key(|suggestion| (suggestion.reason, suggestion.task.due_date.is_none(), suggestion.task.due_date));
+        suggestions
+    }
+
+    /// The tasks shown on the current task screen (a list or My Day)
+    pub fn get_screen_tasks(&self) -> Vec<&Task> {
+        self.get_screen_tasks_for(self.screen_state())
+    }
+
+    fn get_screen_tasks_for(&self, state: &AppState) -> Vec<&Task> {
+        match state {
+            AppState::MyDay if self.my_day.suggestions_focused => {
+                self.get_my_day_suggestions().into_iter().map(|suggestion| suggestion.task).collect()
+            }
+            AppState::MyDay => self.get_my_day_tasks(),
+            AppState::Upcoming => self.get_upcoming_tasks(),
+            // A board's cursor moves through the focused column only
+            _ if self.current_board().is_some() => self.get_board_column_tasks(self.board.column),
+            _ => self.get_current_list_tasks(),
+        }
+    }
+
+    /// Ids of the tasks a bulk action applies to: the marked tasks plus the
+    /// visual range, or just the task under the cursor when nothing is selected
+    pub fn get_target_task_ids(&self) -> Vec<u32> {
+        self.get_target_task_ids_for(self.screen_state())
+    }
+
+    /// Target task ids for a task screen other than the current one, used by
+    /// prompts that are opened on top of a list or My Day
+    pub fn get_target_task_ids_for(&self, state: &AppState) -> Vec<u32> {
+        let tasks = self.get_screen_tasks_for(state);
+        let mut ids: Vec<u32> = self
+            .marked_task_ids
+            .iter()
+            .copied()
+            .filter(|id| tasks.iter().any(|t| t.id == *id))
+            .collect();
+
+        if let Some(anchor) = self.visual_anchor {
+            let start = anchor.min(self.selected_task_index);
+            let end = anchor.max(self.selected_task_index);
+            for display_index in start..=end {
+                if let Some(task) = self.get_task_at_display_index(&tasks, display_index) {
+                    if !ids.contains(&task.id) {
+                        ids.push(task.id);
+                    }
+                }
+            }
+        }
+
+        if ids.is_empty() {
+            if let Some(task) = self.get_task_at_display_index(&tasks, self.selected_task_index) {
+                ids.push(task.id);
+            }
+        }
+        ids
+    }
+
+    pub fn has_selection(&self) -> bool {
+        self.visual_anchor.is_some() || !self.marked_task_ids.is_empty()
+    }
+
+    pub fn toggle_task_mark(&mut self, task_id: u32) {
+        if let Some(pos) = self.marked_task_ids.iter().position(|&id| id == task_id) {
+            self.marked_task_ids.remove(pos);
+        } else {
+            self.marked_task_ids.push(task_id);
+        }
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.marked_task_ids.clear();
+        self.visual_anchor = None;
+    }
+
     /// Get the displayable task count that matches the UI structure
     /// This includes active tasks + separator (if both active and completed exist) + completed tasks
     pub fn get_displayable_task_count(&self, tasks: &[&Task]) -> usize {
@@ -160,38 +669,653 @@ impl App {
         }
     }
 
+    /// Find a list by name, ignoring case. A unique prefix is enough.
+    pub fn find_list(&self, name: &str) -> Result<u32> {
+        let name = name.to_lowercase();
+        if let Some(list) = self.lists.iter().find(|l| l.name.to_lowercase() == name) {
+            return Ok(list.id);
+        }
+
+        let matches: Vec<&List> = self.lists.iter().filter(|l| l.name.to_lowercase().starts_with(&name)).collect();
+        match matches.as_slice() {
+            [list] => Ok(list.id),
+            [] => bail!("No list named '{}'", name),
+            _ => bail!("'{}' matches more than one list", name),
+        }
+    }
+
+    /// The configured default list, or else the first list. There is always
+    /// at least one list: the last one can't be deleted.
+    pub fn default_list_id(&self) -> u32 {
+        let configured = self.config.default_list.as_ref().and_then(|name| self.find_list(name).ok());
+        configured.unwrap_or_else(|| self.lists[0].id)
+    }
+
+    /// Group names in the order they first appear in the list data
+    pub fn get_groups(&self) -> Vec<String> {
+        let mut groups: Vec<String> = Vec::new();
+        for list in &self.lists {
+            if let Some(group) = &list.group {
+                if !groups.contains(group) {
+                    groups.push(group.clone());
+                }
+            }
+        }
+        groups
+    }
+
+    /// Build the sidebar rows: ungrouped lists first, then each group header
+    /// followed by its lists unless the group is collapsed
+    pub fn get_sidebar_items(&self) -> Vec<SidebarItem> {
+        let mut items: Vec<SidebarItem> = self
+            .lists
+            .iter()
+            .filter(|list| list.group.is_none())
+            .map(|list| SidebarItem::List(list.id))
+            .collect();
+
+        for group in self.get_groups() {
+            let collapsed = self.collapsed_groups.contains(&group);
+            items.push(SidebarItem::Group { name: group.clone(), collapsed });
+            if !collapsed {
+                items.extend(
+                    self.lists
+                        .iter()
+                        .filter(|list| list.group.as_deref() == Some(group.as_str()))
+                        .map(|list| SidebarItem::List(list.id)),
+                );
+            }
+        }
+
+        items
+    }
+
+    /// Completed and total task counts across every list in a group
+    pub fn get_group_task_counts(&self, group: &str) -> (usize, usize) {
+        let list_ids: Vec<u32> = self
+            .lists
+            .iter()
+            .filter(|list| list.group.as_deref() == Some(group))
+            .map(|list| list.id)
+            .collect();
+        let group_tasks: Vec<&Task> = self
+            .tasks
+            .iter()
+            .filter(|task| list_ids.contains(&task.list_id))
+            .collect();
+        let completed = group_tasks.iter().filter(|task| task.is_completed).count();
+        (completed, group_tasks.len())
+    }
+
+    pub fn toggle_group_collapsed(&mut self, group: &str) -> Result<()> {
+        self.change_collapsed_groups(|collapsed| {
+            if !collapsed.remove(group) {
+                collapsed.insert(group.to_string());
+            }
+        })
+    }
+
+    /// Collapse every group, or expand them all if they are already collapsed
+    pub fn toggle_all_groups_collapsed(&mut self) -> Result<()> {
+        let groups = self.get_groups();
+        self.change_collapsed_groups(|collapsed| {
+            if groups.iter().all(|group| collapsed.contains(group)) {
+                collapsed.clear();
+            } else {
+                collapsed.extend(groups);
+            }
+        })
+    }
+
+    /// Collapse or expand groups and save them. On a sidebar screen the
+    /// cursor stays on its row, or moves to the row's group if it's hidden.
+    fn change_collapsed_groups(&mut self, change: impl FnOnce(&mut HashSet<String>)) -> Result<()> {
+        let selected = self.get_sidebar_items().get(self.selected_task_index).cloned();
+        change(&mut self.collapsed_groups);
+
+        if matches!(self.state, AppState::ListOverview | AppState::MoveTask) {
+            let group = match &selected {
+                Some(SidebarItem::Group { name, .. }) => Some(name.clone()),
+                Some(SidebarItem::List(list_id)) => self
+                    .lists
+                    .iter()
+                    .find(|list| list.id == *list_id)
+                    .and_then(|list| list.group.clone())
+                    .filter(|group| self.collapsed_groups.contains(group)),
+                None => None,
+            };
+            let items = self.get_sidebar_items();
+            let index = items.iter().position(|item| match (item, &group) {
+                (SidebarItem::Group { name, .. }, Some(group)) => name == group,
+                (item, None) => Some(item) == selected.as_ref(),
+                _ => false,
+            });
+            self.selected_task_index = index.unwrap_or(0).min(items.len().saturating_sub(1));
+        }
+
+        self.storage.save_collapsed_groups(&self.collapsed_groups)
+    }
+
     pub fn add_task(&mut self, mut task: Task) -> Result<()> {
+        let before = self.snapshot();
         task.id = self.storage.get_next_task_id();
         self.tasks.push(task);
         self.save_tasks()?;
+        self.record("Add task", before);
         Ok(())
     }
 
     pub fn update_task(&mut self, updated_task: Task) -> Result<()> {
+        let before = self.snapshot();
         if let Some(task) = self.tasks.iter_mut().find(|t| t.id == updated_task.id) {
             *task = updated_task;
             self.save_tasks()?;
         }
+        self.record("Edit task", before);
         Ok(())
     }
 
+    /// Move a task to the trash
     pub fn delete_task(&mut self, task_id: u32) -> Result<()> {
-        self.tasks.retain(|task| task.id != task_id);
-        // Remove from My Day order if present
-        self.my_day_task_order.retain(|&id| id != task_id);
+        let before = self.snapshot();
+        if let Some(pos) = self.tasks.iter().position(|task| task.id == task_id) {
+            self.stop_timer_of(task_id)?;
+            let mut task = self.tasks.remove(pos);
+            task.deleted_at = Some(chrono::Local::now());
+            self.trash_tasks.push(task);
+            // Remove from My Day order if present
+            self.my_day_task_order.retain(|&id| id != task_id);
+            self.save_tasks()?;
+            self.save_trash()?;
+        }
+        self.record("Delete task", before);
+        Ok(())
+    }
+
+    /// Complete every task, or reopen them all if they are all complete already
+    pub fn complete_tasks(&mut self, task_ids: &[u32]) -> Result<()> {
+        let all_completed = task_ids
+            .iter()
+            .all(|id| self.tasks.iter().any(|t| t.id == *id && t.is_completed));
+        self.run_batch("Complete tasks", |app| {
+            for &task_id in task_ids {
+                let is_completed = app.tasks.iter().any(|t| t.id == task_id && t.is_completed);
+                if is_completed == all_completed {
+                    app.toggle_task_completion(task_id)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    pub fn delete_tasks(&mut self, task_ids: &[u32]) -> Result<()> {
+        self.run_batch("Delete tasks", |app| {
+            for &task_id in task_ids {
+                app.delete_task(task_id)?;
+            }
+            Ok(())
+        })
+    }
+
+    pub fn move_tasks_to_list(&mut self, task_ids: &[u32], new_list_id: u32) -> Result<()> {
+        self.run_batch("Move tasks", |app| {
+            for &task_id in task_ids {
+                app.move_task_to_list(task_id, new_list_id)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Add every task to My Day, or remove them all if they are all in it
+    pub fn toggle_tasks_in_my_day(&mut self, task_ids: &[u32]) -> Result<()> {
+        let all_in_my_day = task_ids
+            .iter()
+            .all(|id| self.tasks.iter().any(|t| t.id == *id && t.is_in_my_day));
+        self.run_batch("Toggle My Day", |app| {
+            for &task_id in task_ids {
+                if all_in_my_day {
+                    app.remove_task_from_my_day(task_id)?;
+                } else {
+                    app.add_task_to_my_day(task_id)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    pub fn add_tasks_to_my_day(&mut self, task_ids: &[u32]) -> Result<()> {
+        self.run_batch("Add to My Day", |app| {
+            for &task_id in task_ids {
+                app.add_task_to_my_day(task_id)?;
+            }
+            Ok(())
+        })
+    }
+
+    pub fn remove_tasks_from_my_day(&mut self, task_ids: &[u32]) -> Result<()> {
+        self.run_batch("Remove from My Day", |app| {
+            for &task_id in task_ids {
+                app.remove_task_from_my_day(task_id)?;
+            }
+            Ok(())
+        })
+    }
+
+    pub fn set_tasks_due_date(
+        &mut self,
+        task_ids: &[u32],
+        due_date: Option<DueDate>,
+    ) -> Result<()> {
+        let before = self.snapshot();
+        for task in self.tasks.iter_mut().filter(|t| task_ids.contains(&t.id)) {
+            task.set_due_date(due_date);
+        }
         self.save_tasks()?;
+        self.record("Set due date", before);
+        Ok(())
+    }
+
+    /// Open tasks of every list under the headers Overdue, Today, Tomorrow,
+    /// the names of the following days, Later and No date. Occurrences of
+    /// recurring tasks over the coming days are added where they fall.
+    pub fn get_upcoming_groups(&self) -> Vec<UpcomingGroup<'_>> {
+        let today = Local::now().date_naive();
+        let day_title = |days: i64| match days {
+            0 => "Today".to_string(),
+            1 => "Tomorrow".to_string(),
+            _ => (today + Duration::days(days)).format("%A").to_string(),
+        };
+        let mut groups: Vec<UpcomingGroup> = std::iter::once(("Overdue".to_string(), None))
+            .chain((0..UPCOMING_DAYS).map(|days| (day_title(days), Some(today + Duration::days(days)))))
+            .chain([("Later".to_string(), None), ("No date".to_string(), None)])
+            .map(|(title, date)| UpcomingGroup { title, date, entries: Vec::new() })
+            .collect();
+        let later = UPCOMING_DAYS as usize + 1;
+        // Overdue comes first, so a day's group is one after its offset
+        let day_group = |date: NaiveDate| match (date - today).num_days() {
+            days @ 0..UPCOMING_DAYS => days as usize + 1,
+            _ => later,
+        };
+
+        let open_tasks = self
+            .tasks
+            .iter()
+            .filter(|task| !task.is_completed)
+            .filter(|task| ListFilter::Actionable.shows(task, self.config.deferred_tasks));
+        for task in open_tasks {
+            let group = match task.due_date {
+                None => later + 1,
+                Some(_) if task.is_overdue() => 0,
+                Some(due_date) => day_group(due_date.date_naive()),
+            };
+            groups[group].entries.push(UpcomingEntry::Task(task));
+
+            // Instances created on completion, as far as the named days go.
+            // The next instance follows the old due date, so a task that is
+            // more than one repeat overdue gets an overdue one and nothing
+            // is projected.
+            if let (Some(frequency), Some(due_date)) = (&task.recurring_frequency, task.due_date) {
+                let mut next = frequency.next_due_date(due_date);
+                while (0..UPCOMING_DAYS).contains(&(next.date_naive() - today).num_days()) {
+                    groups[day_group(next.date_naive())].entries.push(UpcomingEntry::Projected(task, next));
+                    next = frequency.next_due_date(next);
+                }
+            }
+        }
+
+        for group in &mut groups {
+            group.entries.sort_by_key(|entry| entry.due_date());
+        }
+        groups.retain(|group| !group.entries.is_empty());
+        groups
+    }
+
+    /// The tasks of the Upcoming screen in the order they are shown
+    pub fn get_upcoming_tasks(&self) -> Vec<&Task> {
+        self.get_upcoming_groups()
+            .into_iter()
+            .flat_map(|group| group.entries)
+            .filter_map(|entry| match entry {
+                UpcomingEntry::Task(task) => Some(task),
+                UpcomingEntry::Projected(..) => None,
+            })
+            .collect()
+    }
+
+    /// Tasks due on a day, open ones first, in order of their due time
+    pub fn get_calendar_tasks(&self, day: NaiveDate) -> Vec<&Task> {
+        let mut tasks: Vec<&Task> = self
+            .tasks
+            .iter()
+            .filter(|task| task.due_date.is_some_and(|due| due.date_naive() == day))
+            .collect();
+        tasks.sort_by_key(|task| (task.is_completed, task.due_date));
+        tasks
+    }
+
+    /// Move a task's due date to another day. It keeps its time of day, or
+    /// stays all day.
+    pub fn reschedule_task(&mut self, task_id: u32, day: NaiveDate) -> Result<()> {
+        let before = self.snapshot();
+        if let Some(task) = self.tasks.iter_mut().find(|t| t.id == task_id) {
+            let time = task.due().filter(|due| due.has_time).map(|due| due.at.time());
+            task.set_due_date(Some(DueDate::on(day, time)?));
+        }
+        self.save_tasks()?;
+        self.record("Reschedule task", before);
+        Ok(())
+    }
+
+    pub fn set_tasks_start_date(&mut self, task_ids: &[u32], start_date: Option<NaiveDate>) -> Result<()> {
+        let before = self.snapshot();
+        for task in self.tasks.iter_mut().filter(|t| task_ids.contains(&t.id)) {
+            task.set_start_date(start_date);
+        }
+        self.save_tasks()?;
+        self.record("Set start date", before);
+        Ok(())
+    }
+
+    pub fn add_tag_to_tasks(&mut self, task_ids: &[u32], tag: &str) -> Result<()> {
+        let before = self.snapshot();
+        for task in self.tasks.iter_mut().filter(|t| task_ids.contains(&t.id)) {
+            task.add_tag(tag);
+        }
+        self.save_tasks()?;
+        self.record("Add tag", before);
+        Ok(())
+    }
+
+    /// Move a list and all of its tasks to the trash
+    pub fn delete_list(&mut self, list_id: u32) -> Result<()> {
+        if self.lists.len() == 1 {
+            bail!("Can't delete the only list");
+        }
+        let before = self.snapshot();
+        if let Some(pos) = self.lists.iter().position(|list| list.id == list_id) {
+            let now = chrono::Local::now();
+            let mut list = self.lists.remove(pos);
+            list.deleted_at = Some(now);
+            self.trash_lists.push(list);
+
+            let (trashed, kept): (Vec<Task>, Vec<Task>) =
+                std::mem::take(&mut self.tasks).into_iter().partition(|task| task.list_id == list_id);
+            self.tasks = kept;
+            for mut task in trashed {
+                self.stop_timer_of(task.id)?;
+                self.my_day_task_order.retain(|&id| id != task.id);
+                task.deleted_at = Some(now);
+                self.trash_tasks.push(task);
+            }
+
+            if self.current_list_id == Some(list_id) {
+                self.current_list_id = None;
+            }
+            self.save_tasks()?;
+            self.save_lists()?;
+            self.save_trash()?;
+        }
+        self.record("Delete list", before);
+        Ok(())
+    }
+
+    /// Trash rows, most recently deleted first
+    pub fn get_trash_items(&self) -> Vec<TrashItem> {
+        let mut items: Vec<(chrono::DateTime<chrono::Local>, TrashItem)> = self
+            .trash_lists
+            .iter()
+            .filter_map(|list| Some((list.deleted_at?, TrashItem::List(list.id))))
+            .collect();
+        items.extend(
+            self.trash_tasks
+                .iter()
+                .filter(|task| !self.was_deleted_with_list(task))
+                .filter_map(|task| Some((task.deleted_at?, TrashItem::Task(task.id)))),
+        );
+        items.sort_by_key(|(deleted_at, _)| std::cmp::Reverse(*deleted_at));
+        items.into_iter().map(|(_, item)| item).collect()
+    }
+
+    /// Whether a trashed task went to the trash as part of deleting its list
+    pub fn was_deleted_with_list(&self, task: &Task) -> bool {
+        self.trash_lists
+            .iter()
+            .any(|list| list.id == task.list_id && list.deleted_at == task.deleted_at)
+    }
+
+    /// Restore a trashed item to where it was deleted from. Restoring a task
+    /// whose list is also in the trash brings the list back too.
+    pub fn restore_trash_item(&mut self, item: &TrashItem) -> Result<()> {
+        let before = self.snapshot();
+        match item {
+            TrashItem::List(list_id) => self.restore_list(*list_id, true),
+            TrashItem::Task(task_id) => {
+                if let Some(pos) = self.trash_tasks.iter().position(|task| task.id == *task_id) {
+                    let mut task = self.trash_tasks.remove(pos);
+                    task.deleted_at = None;
+                    // Fall back to the first list if the original was purged
+                    let list_known = self.lists.iter().chain(&self.trash_lists).any(|l| l.id == task.list_id);
+                    if let (false, Some(first_list)) = (list_known, self.lists.first()) {
+                        task.list_id = first_list.id;
+                    }
+                    let list_id = task.list_id;
+                    if self.config.shows_in_my_day(&task) {
+                        self.my_day_task_order.push(task.id);
+                    }
+                    self.tasks.push(task);
+                    self.restore_list(list_id, false);
+                }
+            }
+        }
+        self.save_tasks()?;
+        self.save_lists()?;
+        self.save_trash()?;
+        self.record("Restore from trash", before);
+        Ok(())
+    }
+
+    fn restore_list(&mut self, list_id: u32, with_tasks: bool) {
+        let Some(pos) = self.trash_lists.iter().position(|list| list.id == list_id) else {
+            return;
+        };
+        let mut list = self.trash_lists.remove(pos);
+        if with_tasks {
+            let (restored, kept): (Vec<Task>, Vec<Task>) = std::mem::take(&mut self.trash_tasks)
+                .into_iter()
+                .partition(|task| task.list_id == list_id && task.deleted_at == list.deleted_at);
+            self.trash_tasks = kept;
+            for mut task in restored {
+                task.deleted_at = None;
+                if self.config.shows_in_my_day(&task) {
+                    self.my_day_task_order.push(task.id);
+                }
+                self.tasks.push(task);
+            }
+        }
+        list.deleted_at = None;
+        self.lists.push(list);
+    }
+
+    /// Permanently remove a trashed item (and, for a list, the tasks deleted with it)
+    pub fn purge_trash_item(&mut self, item: &TrashItem) -> Result<()> {
+        let before = self.snapshot();
+        match item {
+            TrashItem::List(list_id) => {
+                if let Some(pos) = self.trash_lists.iter().position(|list| list.id == *list_id) {
+                    let list = self.trash_lists.remove(pos);
+                    self.trash_tasks
+                        .retain(|task| !(task.list_id == list.id && task.deleted_at == list.deleted_at));
+                }
+            }
+            TrashItem::Task(task_id) => self.trash_tasks.retain(|task| task.id != *task_id),
+        }
+        self.save_trash()?;
+        self.record("Purge from trash", before);
+        Ok(())
+    }
+
+    pub fn empty_trash(&mut self) -> Result<()> {
+        let before = self.snapshot();
+        self.trash_tasks.clear();
+        self.trash_lists.clear();
+        self.save_trash()?;
+        self.record("Empty trash", before);
+        Ok(())
+    }
+
+    /// Daily housekeeping: purge expired trash, archive old completed tasks
+    /// and start a new My Day. Runs on startup and again when the date
+    /// changes during a session. Returns whether it ran.
+    pub fn tidy_up(&mut self) -> Result<bool> {
+        let today = Local::now().date_naive();
+        if self.tidied_on == today {
+            return Ok(false);
+        }
+        self.tidied_on = today;
+        self.purge_expired_trash()?;
+        self.archive_completed_tasks()?;
+        self.reset_my_day()?;
+        Ok(true)
+    }
+
+    /// Move tasks completed more than the configured number of days ago out
+    /// of the task file and into the archive
+    fn archive_completed_tasks(&mut self) -> Result<()> {
+        if self.config.archive_after_days == 0 {
+            return Ok(());
+        }
+
+        let cutoff = chrono::Local::now() - chrono::Duration::days(self.config.archive_after_days.into());
+        let (archived, kept): (Vec<Task>, Vec<Task>) = std::mem::take(&mut self.tasks)
+            .into_iter()
+            .partition(|task| task.is_completed && task.completed_at.is_some_and(|d| d < cutoff));
+        self.tasks = kept;
+
+        if !archived.is_empty() {
+            for task in &archived {
+                self.my_day_task_order.retain(|&id| id != task.id);
+            }
+            self.archived_tasks.extend(archived);
+            self.storage.save_archive(&self.archived_tasks)?;
+            self.save_tasks()?;
+        }
+        Ok(())
+    }
+
+    /// Mark a completed task as not done, bringing it back from the archive
+    /// first if it was archived. Leaving the archive isn't undone, just as
+    /// archiving isn't, but reopening is.
+    pub fn reopen_task(&mut self, task_id: u32) -> Result<()> {
+        if let Some(pos) = self.archived_tasks.iter().position(|task| task.id == task_id) {
+            let mut task = self.archived_tasks.remove(pos);
+            if !self.lists.iter().any(|list| list.id == task.list_id) {
+                task.list_id = self.default_list_id();
+            }
+            self.tasks.push(task);
+            self.storage.save_archive(&self.archived_tasks)?;
+            self.save_tasks()?;
+        }
+        if self.tasks.iter().any(|task| task.id == task_id && task.is_completed) {
+            self.toggle_task_completion(task_id)?;
+        }
+        Ok(())
+    }
+
+    /// Every completed task, live or archived, that matches the history
+    /// filter, most recently completed first
+    pub fn get_completed_history(&self) -> Vec<&Task> {
+        let query = self.history_filter.query.trim().to_lowercase();
+        let mut tasks: Vec<&Task> = self
+            .tasks
+            .iter()
+            .chain(&self.archived_tasks)
+            .filter(|task| task.is_completed && task.completed_at.is_some())
+            .filter(|task| self.history_filter.list_id.is_none_or(|id| task.list_id == id))
+            .filter(|task| {
+                query.is_empty()
+                    || task.title.to_lowercase().contains(&query)
+                    || task.notes.as_deref().is_some_and(|n| n.to_lowercase().contains(&query))
+            })
+            .collect();
+        tasks.sort_by_key(|task| std::cmp::Reverse(task.completed_at));
+        tasks
+    }
+
+    /// Step the history list filter through "all lists" and each list in turn
+    pub fn cycle_history_list_filter(&mut self) {
+        let next = match self.history_filter.list_id {
+            None => self.lists.first().map(|l| l.id),
+            Some(id) => self
+                .lists
+                .iter()
+                .position(|l| l.id == id)
+                .and_then(|pos| self.lists.get(pos + 1))
+                .map(|l| l.id),
+        };
+        self.history_filter.list_id = next;
+        self.selected_task_index = 0;
+    }
+
+    /// Take tasks added to My Day on an earlier day back out of it, when My
+    /// Day starts empty each day. Their My Day date is kept so they're
+    /// suggested again.
+    fn reset_my_day(&mut self) -> Result<()> {
+        if !self.config.my_day_resets {
+            return Ok(());
+        }
+
+        let today = Local::now().date_naive();
+        let mut changed = false;
+        for task in self.tasks.iter_mut().filter(|task| task.is_in_my_day) {
+            match task.my_day_date {
+                Some(date) if date < today => {
+                    task.is_in_my_day = false;
+                    self.my_day_task_order.retain(|&id| id != task.id);
+                }
+                // Added before My Day dates were kept, so counted from today
+                None => task.my_day_date = Some(today),
+                Some(_) => continue,
+            }
+            changed = true;
+        }
+        if changed {
+            self.save_tasks()?;
+        }
+        Ok(())
+    }
+
+    /// Drop trashed items older than the configured retention period
+    fn purge_expired_trash(&mut self) -> Result<()> {
+        if self.config.trash_retention_days == 0 {
+            return Ok(());
+        }
+
+        let cutoff = chrono::Local::now() - chrono::Duration::days(self.config.trash_retention_days.into());
+        let is_expired = |deleted_at: Option<chrono::DateTime<chrono::Local>>| {
+            deleted_at.is_some_and(|deleted_at| deleted_at < cutoff)
+        };
+        let task_count = self.trash_tasks.len();
+        let list_count = self.trash_lists.len();
+        self.trash_tasks.retain(|task| !is_expired(task.deleted_at));
+        self.trash_lists.retain(|list| !is_expired(list.deleted_at));
+
+        if self.trash_tasks.len() != task_count || self.trash_lists.len() != list_count {
+            self.save_trash()?;
+        }
         Ok(())
     }
 
     pub fn toggle_task_completion(&mut self, task_id: u32) -> Result<()> {
-        // First, find the task and collect the data we need
+        let before = self.snapshot();
+
+        // First, find the task and keep what the next instance is made from
         let (was_completed, has_recurring, task_data) = if let Some(task) = self.tasks.iter().find(|t| t.id == task_id) {
             let was_completed = task.is_completed;
             let has_recurring = task.recurring_frequency.is_some();
             let task_data = if has_recurring && !was_completed {
-                // Only collect data if task is recurring AND currently incomplete
+                // Only copy the task if it is recurring AND currently incomplete
                 // This prevents creating duplicates when uncompleting a recurring task
-                Some((task.title.clone(), task.list_id, task.due_date, task.recurring_frequency.clone(), task.notes.clone()))
+                Some(task.clone())
             } else {
                 None
             };
@@ -204,79 +1328,64 @@ impl App {
         if let Some(task) = self.tasks.iter_mut().find(|t| t.id == task_id) {
             task.toggle_completion();
         }
+        if !was_completed {
+            self.stop_timer_of(task_id)?;
+        }
         
         // If task was just completed and has recurring frequency, create a new instance
-        if !was_completed && has_recurring && task_data.is_some() {
-            let (title, list_id, due_date, frequency, notes) = task_data.unwrap();
-            self.create_recurring_task_instance(title, list_id, due_date, frequency, notes)?;
+        if !was_completed && has_recurring {
+            if let Some(task) = task_data {
+                self.create_recurring_task_instance(task)?;
+            }
         }
         
         self.save_tasks()?;
+        // Recorded as one command so undoing a completion also removes the
+        // recurring instance it spawned
+        self.record("Toggle completion", before);
         Ok(())
     }
 
-    fn create_recurring_task_instance(
-        &mut self, 
-        title: String, 
-        list_id: u32, 
-        current_due_date: Option<chrono::DateTime<chrono::Local>>, 
-        frequency: Option<crate::models::RecurringFrequency>, 
-        notes: Option<String>
-    ) -> Result<()> {
-        use chrono::{Datelike, Duration, Local, Weekday};
-        
-        let frequency = frequency.unwrap();
-        let current_due_date = current_due_date.unwrap_or_else(|| Local::now());
-        let mut next_due_date = current_due_date;
-        
-        // Calculate the next due date based on frequency
-        match frequency {
-            crate::models::RecurringFrequency::Daily => {
-                next_due_date = current_due_date + Duration::days(1);
-            }
-            crate::models::RecurringFrequency::Weekdays => {
-                // Find the next weekday (Monday-Friday)
-                loop {
-                    next_due_date = next_due_date + Duration::days(1);
-                    let weekday = next_due_date.weekday();
-                    if weekday != Weekday::Sat && weekday != Weekday::Sun {
-                        break;
-                    }
-                }
-            }
-            crate::models::RecurringFrequency::Weekly => {
-                next_due_date = current_due_date + Duration::weeks(1);
-            }
-            crate::models::RecurringFrequency::Monthly => {
-                // Add one month (approximate)
-                next_due_date = current_due_date + Duration::days(30);
-            }
-            crate::models::RecurringFrequency::Yearly => {
-                // Add one year (approximate)
-                next_due_date = current_due_date + Duration::days(365);
-            }
-        }
-        
-        // Create new task instance
-        let mut new_task = crate::models::Task::new(
-            self.get_next_task_id(),
-            title,
-            list_id,
-        );
-        
-        new_task.set_due_date(Some(next_due_date));
-        new_task.set_recurring_frequency(Some(frequency));
-        new_task.set_notes(notes);
+    /// Add the instance that follows a recurring task. It is a copy of the
+    /// task, with its tags, priority and notes, that is due one repeat later
+    /// and starts afresh: open, in the first board column and out of My Day.
+    fn create_recurring_task_instance(&mut self, task: Task) -> Result<()> {
+        let Some(frequency) = task.recurring_frequency.clone() else {
+            return Ok(());
+        };
+        // The next instance keeps the time of day, or stays all day
+        let has_time = task.has_due_time;
+        let current_due_date = task.due_date.unwrap_or_else(chrono::Local::now);
+        let next_due_date = frequency.next_due_date(current_due_date);
+        // A start date keeps its distance from the due date
+        let shift = next_due_date.date_naive() - current_due_date.date_naive();
+
+        let fresh = Task::new(self.get_next_task_id(), task.title.clone(), task.list_id);
+        let mut new_task = Task {
+            id: fresh.id,
+            start_date: task.start_date.map(|start| start + shift),
+            reminder_date: None,
+            is_completed: false,
+            completed_at: None,
+            is_in_my_day: false,
+            my_day_date: None,
+            status: None,
+            created_at: fresh.created_at,
+            updated_at: fresh.updated_at,
+            deleted_at: None,
+            ..task
+        };
+        new_task.set_due_date(Some(DueDate { at: next_due_date, has_time }));
         
         // Add the new task
         let new_task_id = new_task.id;
         self.tasks.push(new_task);
         
         // Add to My Day order if the new task is due today
-        if next_due_date.date_naive() == chrono::Local::now().date_naive() {
-            if !self.my_day_task_order.contains(&new_task_id) {
-                self.my_day_task_order.push(new_task_id);
-            }
+        if next_due_date.date_naive() == chrono::Local::now().date_naive()
+            && !self.my_day_task_order.contains(&new_task_id)
+        {
+            self.my_day_task_order.push(new_task_id);
         }
         
         Ok(())
@@ -288,6 +1397,7 @@ impl App {
 
 
     pub fn move_task_up_in_my_day(&mut self, task_id: u32) -> Result<()> {
+        let before = self.snapshot();
         // Find all positions of this task_id (in case of duplicates)
         let positions: Vec<usize> = self.my_day_task_order
             .iter()
@@ -311,10 +1421,12 @@ impl App {
                 self.my_day_task_order.swap(pos, pos - 1);
             }
         }
+        self.record("Reorder My Day", before);
         Ok(())
     }
 
     pub fn move_task_down_in_my_day(&mut self, task_id: u32) -> Result<()> {
+        let before = self.snapshot();
         // Find all positions of this task_id (in case of duplicates)
         let positions: Vec<usize> = self.my_day_task_order
             .iter()
@@ -338,10 +1450,32 @@ impl App {
                 self.my_day_task_order.swap(pos, pos + 1);
             }
         }
+        self.record("Reorder My Day", before);
+        Ok(())
+    }
+
+    /// Move a task to the position of another task in the manual order of
+    /// the current task screen: My Day's order, or the order of its list
+    pub fn reorder_task(&mut self, task_id: u32, target_id: u32) -> Result<()> {
+        let before = self.snapshot();
+        if *self.screen_state() == AppState::MyDay {
+            // Tasks due today can be shown without being in the order yet
+            for id in self.get_my_day_tasks().iter().map(|t| t.id).collect::<Vec<_>>() {
+                if !self.my_day_task_order.contains(&id) {
+                    self.my_day_task_order.push(id);
+                }
+            }
+            move_item(&mut self.my_day_task_order, task_id, target_id, |&id| id);
+        } else {
+            move_item(&mut self.tasks, task_id, target_id, |t| t.id);
+            self.save_tasks()?;
+        }
+        self.record("Reorder tasks", before);
         Ok(())
     }
 
     pub fn add_task_to_my_day(&mut self, task_id: u32) -> Result<()> {
+        let before = self.snapshot();
         if let Some(task) = self.tasks.iter_mut().find(|t| t.id == task_id) {
             task.add_to_my_day();
             // Add to My Day order if not already present
@@ -350,43 +1484,230 @@ impl App {
             }
             self.save_tasks()?;
         }
+        self.record("Add to My Day", before);
         Ok(())
     }
 
     pub fn remove_task_from_my_day(&mut self, task_id: u32) -> Result<()> {
+        let before = self.snapshot();
         if let Some(task) = self.tasks.iter_mut().find(|t| t.id == task_id) {
             task.remove_from_my_day();
             // Remove from My Day order
             self.my_day_task_order.retain(|&id| id != task_id);
             self.save_tasks()?;
         }
+        self.record("Remove from My Day", before);
         Ok(())
     }
 
     pub fn move_task_to_list(&mut self, task_id: u32, new_list_id: u32) -> Result<()> {
+        let before = self.snapshot();
         if let Some(task) = self.tasks.iter_mut().find(|t| t.id == task_id) {
             task.list_id = new_list_id;
             task.updated_at = chrono::Local::now();
             self.save_tasks()?;
         }
+        self.record("Move task", before);
         Ok(())
     }
 
     pub fn add_list(&mut self, mut list: List) -> Result<()> {
+        let before = self.snapshot();
         list.id = self.storage.get_next_list_id();
         self.lists.push(list);
         self.save_lists()?;
+        self.record("Add list", before);
+        Ok(())
+    }
+
+
+    pub fn set_list_group(&mut self, list_id: u32, group: Option<String>) -> Result<()> {
+        let before = self.snapshot();
+        if let Some(list) = self.lists.iter_mut().find(|l| l.id == list_id) {
+            list.set_group(group);
+            self.save_lists()?;
+        }
+        self.record("Set list group", before);
+        Ok(())
+    }
+
+    pub fn set_list_board(&mut self, list_id: u32, board: bool) -> Result<()> {
+        let before = self.snapshot();
+        if let Some(list) = self.lists.iter_mut().find(|l| l.id == list_id) {
+            list.set_board(board);
+            self.save_lists()?;
+        }
+        self.record(if board { "Show list as board" } else { "Show board as list" }, before);
         Ok(())
     }
 
+    /// Set the board columns of a list. An empty list of statuses restores
+    /// the default columns.
+    pub fn set_list_statuses(&mut self, list_id: u32, statuses: Vec<String>) -> Result<()> {
+        let before = self.snapshot();
+        if let Some(list) = self.lists.iter_mut().find(|l| l.id == list_id) {
+            list.set_statuses(statuses);
+            self.save_lists()?;
+        }
+        self.record("Set board columns", before);
+        Ok(())
+    }
 
     pub fn set_current_list(&mut self, list_id: u32) {
         self.current_list_id = Some(list_id);
         self.selected_task_index = 0;
+        self.board.column = 0;
         self.state = AppState::TaskList;
     }
 
 
+    /// The timer that is running, if any. Only one runs at a time.
+    pub fn running_timer(&self) -> Option<&TimeEntry> {
+        self.time_entries.iter().find(|entry| entry.is_running())
+    }
+
+    /// Start timing a task, stopping the timer of any other task
+    pub fn start_timer(&mut self, task_id: u32) -> Result<()> {
+        let now = Local::now();
+        for entry in self.time_entries.iter_mut().filter(|entry| entry.is_running()) {
+            entry.stopped_at = Some(now);
+        }
+        let id = self.time_entries.iter().map(|entry| entry.id).max().unwrap_or(0) + 1;
+        self.time_entries.push(TimeEntry::new(id, task_id, now));
+        self.save_time_entries()
+    }
+
+    /// Stop the running timer, returning its entry
+    pub fn stop_timer(&mut self) -> Result<Option<TimeEntry>> {
+        let Some(entry) = self.time_entries.iter_mut().find(|entry| entry.is_running()) else {
+            return Ok(None);
+        };
+        entry.stopped_at = Some(Local::now());
+        let entry = entry.clone();
+        self.save_time_entries()?;
+        Ok(Some(entry))
+    }
+
+    /// Stop the timer if it's running for this task, which is done or gone
+    fn stop_timer_of(&mut self, task_id: u32) -> Result<()> {
+        if self.running_timer().is_some_and(|entry| entry.task_id == task_id) {
+            self.stop_timer()?;
+        }
+        Ok(())
+    }
+
+    /// Time tracked on a task so far, or `None` if it was never timed
+    pub fn tracked_time(&self, task_id: u32) -> Option<Duration> {
+        self.time_entries
+            .iter()
+            .any(|entry| entry.task_id == task_id)
+            .then(|| timesheet::total_time(&self.time_entries, task_id, Local::now()))
+    }
+
+    /// A task by id, wherever it is: open, archived or in the trash
+    pub fn find_any_task(&self, task_id: u32) -> Option<&Task> {
+        self.tasks
+            .iter()
+            .chain(&self.archived_tasks)
+            .chain(&self.trash_tasks)
+            .find(|task| task.id == task_id)
+    }
+
+    /// Tracked time by day, newest first, and by list and task on each day
+    pub fn get_timesheet(&self) -> Vec<TimesheetDay> {
+        let list_of = |task_id| self.find_any_task(task_id).map(|task| task.list_id);
+        timesheet::by_day(&self.time_entries, list_of, Local::now())
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            tasks: self.tasks.clone(),
+            lists: self.lists.clone(),
+            trash_tasks: self.trash_tasks.clone(),
+            trash_lists: self.trash_lists.clone(),
+            my_day_task_order: self.my_day_task_order.clone(),
+        }
+    }
+
+    /// Run several mutations as one undoable command. Batches may nest, in
+    /// which case the outermost one is recorded.
+    pub fn run_batch(&mut self, description: &str, f: impl FnOnce(&mut Self) -> Result<()>) -> Result<()> {
+        let before = self.snapshot();
+        let outer_batch = std::mem::replace(&mut self.recording_batch, true);
+        let result = f(self);
+        self.recording_batch = outer_batch;
+        self.record(description, before);
+        result
+    }
+
+    fn record(&mut self, description: &str, before: Snapshot) {
+        if self.recording_batch {
+            return;
+        }
+        if let Some(command) = Command::from_snapshots(description, &before, &self.snapshot()) {
+            self.history.push(command);
+        }
+    }
+
+    pub fn undo(&mut self) -> Result<()> {
+        match self.history.take_undo() {
+            Some(command) => {
+                self.apply_changes(&command.changes, false)?;
+                self.status_message = Some(format!("Undid: {}", command.description));
+            }
+            None => self.status_message = Some("Nothing to undo".to_string()),
+        }
+        Ok(())
+    }
+
+    pub fn redo(&mut self) -> Result<()> {
+        match self.history.take_redo() {
+            Some(command) => {
+                self.apply_changes(&command.changes, true)?;
+                self.status_message = Some(format!("Redid: {}", command.description));
+            }
+            None => self.status_message = Some("Nothing to redo".to_string()),
+        }
+        Ok(())
+    }
+
+    /// Apply a command's changes forwards (redo) or backwards (undo)
+    fn apply_changes(&mut self, changes: &[Change], forward: bool) -> Result<()> {
+        // Undo walks the changes in reverse so positional inserts line up
+        let ordered: Vec<&Change> = if forward {
+            changes.iter().collect()
+        } else {
+            changes.iter().rev().collect()
+        };
+
+        for change in ordered {
+            match change {
+                Change::Task { trashed, index, before, after } => {
+                    let (from, to) = if forward { (before, after) } else { (after, before) };
+                    let tasks = if *trashed { &mut self.trash_tasks } else { &mut self.tasks };
+                    apply_item_change(tasks, *index, from, to, |t| t.id);
+                }
+                Change::List { trashed, index, before, after } => {
+                    let (from, to) = if forward { (before, after) } else { (after, before) };
+                    let lists = if *trashed { &mut self.trash_lists } else { &mut self.lists };
+                    apply_item_change(lists, *index, from, to, |l| l.id);
+                }
+                Change::MyDayOrder { before, after } => {
+                    self.my_day_task_order = if forward { after.clone() } else { before.clone() };
+                }
+                Change::TaskOrder { before, after } => {
+                    let order = if forward { after } else { before };
+                    self.tasks.sort_by_key(|task| order.iter().position(|&id| id == task.id));
+                }
+            }
+        }
+
+        self.save_tasks()?;
+        self.save_lists()?;
+        self.save_trash()?;
+        Ok(())
+    }
+
     fn save_tasks(&self) -> Result<()> {
         self.storage.save_tasks(&self.tasks)
     }
@@ -394,4 +1715,223 @@ impl App {
     fn save_lists(&self) -> Result<()> {
         self.storage.save_lists(&self.lists)
     }
+
+    fn save_trash(&self) -> Result<()> {
+        self.storage.save_trash(&self.trash_tasks, &self.trash_lists)
+    }
+
+    fn save_time_entries(&self) -> Result<()> {
+        self.storage.save_time_entries(&self.time_entries)
+    }
+}
+
+/// Move the item with id `item_id` to where the item with id `target_id` is
+fn move_item<T>(items: &mut Vec<T>, item_id: u32, target_id: u32, id: impl Fn(&T) -> u32) {
+    let from = items.iter().position(|item| id(item) == item_id);
+    let to = items.iter().position(|item| id(item) == target_id);
+    if let (Some(from), Some(to)) = (from, to) {
+        let item = items.remove(from);
+        items.insert(to, item);
+    }
+}
+
+/// Move `items` from the `from` side of a change to the `to` side
+fn apply_item_change<T: Clone>(
+    items: &mut Vec<T>,
+    index: usize,
+    from: &Option<T>,
+    to: &Option<T>,
+    id: impl Fn(&T) -> u32,
+) {
+    let Some(item_id) = from.as_ref().or(to.as_ref()).map(&id) else {
+        return;
+    };
+    let position = items.iter().position(|item| id(item) == item_id);
+    match (position, to) {
+        (Some(pos), Some(item)) => items[pos] = item.clone(),
+        (Some(pos), None) => {
+            items.remove(pos);
+        }
+        (None, Some(item)) => {
+            let index = index.min(items.len());
+            items.insert(index, item.clone());
+        }
+        (None, None) => {}
+    }
+}
+
+#[cfg(test)]
+impl App {
+    /// An app with its own empty data directory and a list of tasks with the
+    /// given titles, open on that list. It has the default keymap and theme,
+    /// whatever is in the config directory of the machine running the tests.
+    pub fn with_tasks(name: &str, titles: &[&str]) -> Self {
+        let data_dir = std::env::temp_dir().join(format!("todo-tui-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&data_dir);
+        let config = Config {
+            data_dir: Some(data_dir),
+            confirm_delete: false,
+            ..Config::default()
+        };
+        let mut app = App::without_interface(config).unwrap();
+        let list_id = app.lists[0].id;
+        for title in titles {
+            app.add_task(Task::new(0, title.to_string(), list_id)).unwrap();
+        }
+        app.set_current_list(list_id);
+        app
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add_grouped_lists(app: &mut App, group: &str, names: &[&str]) {
+        for name in names {
+            app.add_list(List::new(0, name.to_string())).unwrap();
+            let list_id = app.lists.last().unwrap().id;
+            app.set_list_group(list_id, Some(group.to_string())).unwrap();
+        }
+    }
+
+    #[test]
+    fn collapsing_moves_cursor_to_hidden_row_group() {
+        let mut app = App::with_tasks("collapse-cursor", &[]);
+        add_grouped_lists(&mut app, "Work", &["Reports", "Meetings"]);
+        app.state = AppState::ListOverview;
+        app.selected_task_index = 3;
+        assert_eq!(app.get_sidebar_items()[3], SidebarItem::List(app.lists[2].id));
+
+        app.toggle_all_groups_collapsed().unwrap();
+        assert_eq!(app.selected_task_index, 1);
+
+        app.toggle_all_groups_collapsed().unwrap();
+        assert_eq!(app.selected_task_index, 1);
+    }
+
+    fn titles(app: &App) -> Vec<&str> {
+        app.tasks.iter().map(|task| task.title.as_str()).collect()
+    }
+
+    #[test]
+    fn undo_and_redo_round_trip() {
+        let mut app = App::with_tasks("undo-round-trip", &["a", "b", "c"]);
+        let b = app.tasks[1].id;
+
+        app.delete_task(b).unwrap();
+        let mut edited = app.tasks[0].clone();
+        edited.title = "a2".to_string();
+        app.update_task(edited).unwrap();
+        assert_eq!(titles(&app), ["a2", "c"]);
+
+        app.undo().unwrap();
+        app.undo().unwrap();
+        assert_eq!(titles(&app), ["a", "b", "c"]);
+        assert!(app.trash_tasks.is_empty());
+
+        app.redo().unwrap();
+        app.redo().unwrap();
+        assert_eq!(titles(&app), ["a2", "c"]);
+        assert_eq!(app.trash_tasks.len(), 1);
+
+        // Undone changes are saved, not just kept in memory
+        let reopened = App::without_interface(app.config.clone()).unwrap();
+        assert_eq!(titles(&reopened), ["a2", "c"]);
+    }
+
+    #[test]
+    fn nested_batches_undo_in_one_step() {
+        let mut app = App::with_tasks("undo-nested", &["a", "b", "c"]);
+        let ids: Vec<u32> = app.tasks.iter().map(|task| task.id).collect();
+
+        app.run_batch("Outer", |app| {
+            app.complete_tasks(&ids[..2])?;
+            app.delete_tasks(&ids[2..])
+        })
+        .unwrap();
+        assert_eq!(app.tasks.iter().filter(|task| task.is_completed).count(), 2);
+        assert_eq!(app.tasks.len(), 2);
+
+        app.undo().unwrap();
+        assert_eq!(app.status_message.as_deref(), Some("Undid: Outer"));
+        assert_eq!(titles(&app), ["a", "b", "c"]);
+        assert!(app.tasks.iter().all(|task| !task.is_completed));
+
+        // The step before the batch is adding the last task
+        app.undo().unwrap();
+        assert_eq!(titles(&app), ["a", "b"]);
+    }
+
+    #[test]
+    fn purged_task_ids_with_time_entries_are_not_reused() {
+        let mut app = App::with_tasks("purged-ids", &["a", "b"]);
+        let timed = app.tasks[1].id;
+        app.start_timer(timed).unwrap();
+        app.stop_timer().unwrap();
+        app.delete_task(timed).unwrap();
+        app.empty_trash().unwrap();
+
+        let mut reopened = App::without_interface(app.config.clone()).unwrap();
+        let list_id = reopened.lists[0].id;
+        reopened.add_task(Task::new(0, "c".to_string(), list_id)).unwrap();
+        let added = reopened.tasks.last().unwrap().id;
+        assert_ne!(added, timed);
+        assert_eq!(reopened.tracked_time(added), None);
+    }
+
+    #[test]
+    fn deleting_a_list_stops_its_timer() {
+        let mut app = App::with_tasks("delete-list-timer", &["a"]);
+        app.add_list(List::new(0, "Other".to_string())).unwrap();
+        app.start_timer(app.tasks[0].id).unwrap();
+
+        app.delete_list(app.lists[0].id).unwrap();
+        assert!(app.running_timer().is_none());
+        assert_eq!(app.time_entries.len(), 1);
+    }
+
+    #[test]
+    fn recurring_instance_keeps_task_details() {
+        let mut app = App::with_tasks("recurring-copy", &[]);
+        let list_id = app.lists[0].id;
+        let parsed = crate::utils::quick_add::parse(
+            "Standup tomorrow 9am every weekday #infra !high",
+            app.config.week_start,
+            app.config.date_order,
+        );
+        let mut task = Task::new(0, parsed.title, list_id);
+        task.set_due_date(parsed.due_date);
+        task.recurring_frequency = parsed.frequency;
+        task.tags = parsed.tags;
+        task.priority = parsed.priority;
+        task.start_date = task.due_date.map(|due| due.date_naive() - Duration::days(1));
+        task.status = Some("Doing".to_string());
+        app.add_task(task).unwrap();
+        let completed = app.tasks[0].clone();
+
+        app.toggle_task_completion(completed.id).unwrap();
+        let next = app.tasks.iter().find(|task| task.id != completed.id).unwrap();
+        assert_eq!(next.title, "Standup");
+        assert_eq!(next.tags, vec!["infra"]);
+        assert_eq!(next.priority, Some(crate::models::Priority::High));
+        assert_eq!(next.recurring_frequency, completed.recurring_frequency);
+        assert!(next.has_due_time);
+        assert!(!next.is_completed && next.completed_at.is_none());
+        assert_eq!(next.status, None);
+
+        let next_due = next.due_date.unwrap().date_naive();
+        assert!(next_due > completed.due_date.unwrap().date_naive());
+        assert_eq!(next.start_date, Some(next_due - Duration::days(1)));
+    }
+
+    #[test]
+    fn collapsed_groups_are_saved() {
+        let mut app = App::with_tasks("collapse-saved", &[]);
+        add_grouped_lists(&mut app, "Home", &["Garden"]);
+        app.toggle_group_collapsed("Home").unwrap();
+
+        let reopened = App::without_interface(app.config.clone()).unwrap();
+        assert!(reopened.collapsed_groups.contains("Home"));
+    }
 }
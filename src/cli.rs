@@ -0,0 +1,147 @@
+use crate::config::{Config, StartScreen};
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+/// Command line options. Each one can also be set in an environment
+/// variable, and both take precedence over the config file. Without a
+/// subcommand the full-screen app is started.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Print the output of subcommands as JSON
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    /// Config file to use instead of config.toml in the config directory
+    #[arg(long, env = "TODO_TUI_CONFIG", value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Directory holding the task files of the default workspace
+    #[arg(long, env = "TODO_TUI_DATA_DIR", value_name = "DIR")]
+    pub data_dir: Option<PathBuf>,
+
+    /// Workspace to open
+    #[arg(long, short, env = "TODO_TUI_WORKSPACE", value_name = "NAME")]
+    pub workspace: Option<String>,
+
+    /// Screen to open on
+    #[arg(long, env = "TODO_TUI_START_SCREEN", value_name = "SCREEN")]
+    pub start_screen: Option<StartScreen>,
+
+    /// Theme to start with
+    #[arg(long, env = "TODO_TUI_THEME", value_name = "NAME")]
+    pub theme: Option<String>,
+}
+
+/// Subcommands that change or list tasks without starting the app
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Add a task
+    Add {
+        title: String,
+        /// List to add the task to, instead of the default list
+        #[arg(long, short)]
+        list: Option<String>,
+        /// Due date, e.g. "tomorrow" or 2024-05-01
+        #[arg(long, short)]
+        due: Option<String>,
+        /// Day the task can be started on, hiding it until then
+        #[arg(long, short)]
+        start: Option<String>,
+        /// Notes for the task
+        #[arg(long, short)]
+        note: Option<String>,
+        /// Add the task to My Day
+        #[arg(long, short)]
+        myday: bool,
+    },
+    /// List open tasks, optionally only those matching a filter
+    Ls {
+        /// Text in the title or notes, or a #tag
+        filter: Option<String>,
+        /// Only list tasks in this list
+        #[arg(long, short)]
+        list: Option<String>,
+        /// Include completed tasks
+        #[arg(long, short)]
+        all: bool,
+        /// Only list tasks whose start date hasn't come yet
+        #[arg(long)]
+        deferred: bool,
+    },
+    /// Complete tasks
+    Done {
+        #[arg(required = true)]
+        ids: Vec<u32>,
+    },
+    /// Change a task
+    Edit {
+        id: u32,
+        /// New title
+        #[arg(long, short)]
+        title: Option<String>,
+        /// New due date, or "none" to clear it
+        #[arg(long, short)]
+        due: Option<String>,
+        /// New start date, or "none" to clear it
+        #[arg(long, short)]
+        start: Option<String>,
+        /// New notes, or "" to clear them
+        #[arg(long, short)]
+        note: Option<String>,
+        /// Tag to add
+        #[arg(long)]
+        tag: Option<String>,
+    },
+    /// Move a task to another list
+    Mv {
+        id: u32,
+        list: String,
+    },
+    /// List My Day, or add tasks to it
+    Myday {
+        ids: Vec<u32>,
+        /// Remove the tasks from My Day instead
+        #[arg(long, short, requires = "ids")]
+        remove: bool,
+    },
+    /// Start the timer of a task, stopping any other
+    Start {
+        id: u32,
+    },
+    /// Stop the running timer
+    Stop,
+    /// Show the time tracked by day and list, or export it
+    Time {
+        /// Only count time from this day on, e.g. "monday" or 2024-05-01
+        #[arg(long, short)]
+        since: Option<String>,
+        /// Only count time on tasks in this list
+        #[arg(long, short)]
+        list: Option<String>,
+        /// Print every time entry as CSV instead
+        #[arg(long)]
+        csv: bool,
+    },
+}
+
+impl Cli {
+    /// Override config file settings with the ones given here
+    pub fn apply(&self, config: &mut Config) {
+        if let Some(data_dir) = &self.data_dir {
+            config.data_dir = Some(data_dir.clone());
+        }
+        if let Some(workspace) = &self.workspace {
+            config.workspace = workspace.clone();
+        }
+        if let Some(start_screen) = self.start_screen {
+            config.start_screen = start_screen;
+        }
+        if let Some(theme) = &self.theme {
+            config.theme = theme.clone();
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use crate::models::Task;
+use crate::theme::ColorMode;
+use crate::utils::date_utils::DateOrder;
+use anyhow::{bail, Context, Result};
+use chrono::format::{Item, StrftimeItems};
+use chrono::Weekday;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// User settings read from `config.toml` in the todo-tui config directory.
+/// Every field has a default, so the file and any key in it are optional.
+/// Some settings can also be given on the command line or in environment
+/// variables, see `cli.rs`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Where the task files of the default workspace are kept. Defaults to
+    /// `~/todo-data`.
+    pub data_dir: Option<PathBuf>,
+    /// Workspace to open
+    pub workspace: String,
+    /// Other workspaces by name, each with the directory of its task files
+    pub workspaces: BTreeMap<String, PathBuf>,
+    /// Screen shown when the app starts
+    pub start_screen: StartScreen,
+    /// Name of the list opened by the `default_list` start screen
+    pub default_list: Option<String>,
+    /// First day of the week, used when parsing dates like "next week"
+    pub week_start: Weekday,
+    /// Order of day, month and year when dates are typed as numbers
+    pub date_order: DateOrder,
+    /// chrono format for dates shown in lists, e.g. `%d/%m/%Y`
+    pub date_format: String,
+    /// Width of the list sidebar as a percentage of the screen
+    pub sidebar_width: u16,
+    /// Show tasks due today in My Day without adding them
+    pub my_day_due_today: bool,
+    /// Show overdue tasks in My Day without adding them
+    pub my_day_overdue: bool,
+    /// Show tasks whose start date is today in My Day without adding them
+    pub my_day_starting: bool,
+    /// Start each day with an empty My Day. Tasks left unfinished in it are
+    /// suggested the next day.
+    pub my_day_resets: bool,
+    /// Whether lists hide tasks that can't be started yet, or show them dimmed
+    pub deferred_tasks: DeferredTasks,
+    /// Ask before deleting tasks or lists and before purging the trash
+    pub confirm_delete: bool,
+    /// Days a deleted task or list stays in the trash before it is purged.
+    /// Zero keeps trashed items until they are purged by hand.
+    pub trash_retention_days: u32,
+    /// Days after completion before a task moves from the task file into
+    /// the archive. Zero turns archiving off.
+    pub archive_after_days: u32,
+    /// Theme to start with: a built-in theme or a file in the themes folder
+    pub theme: String,
+    /// Colors the terminal supports, or auto to detect them
+    pub color_mode: ColorMode,
+}
+
+/// The screen the app opens on
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+#[value(rename_all = "snake_case")]
+pub enum StartScreen {
+    #[default]
+    Lists,
+    MyDay,
+    DefaultList,
+}
+
+/// How lists show tasks with a start date that hasn't come yet
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeferredTasks {
+    #[default]
+    Hide,
+    Dim,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            data_dir: None,
+            workspace: DEFAULT_WORKSPACE.to_string(),
+            workspaces: BTreeMap::new(),
+            start_screen: StartScreen::Lists,
+            default_list: None,
+            week_start: Weekday::Mon,
+            date_order: DateOrder::MonthDayYear,
+            date_format: "%Y-%m-%d".to_string(),
+            sidebar_width: 30,
+            my_day_due_today: true,
+            my_day_overdue: false,
+            my_day_starting: true,
+            my_day_resets: false,
+            deferred_tasks: DeferredTasks::Hide,
+            confirm_delete: false,
+            trash_retention_days: 30,
+            archive_after_days: 14,
+            theme: "dark".to_string(),
+            color_mode: ColorMode::Auto,
+        }
+    }
+}
+
+impl Config {
+    /// Load the config file at `path`, or `config.toml` in the config
+    /// directory when no path is given. Only an explicit path has to exist.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => match config_file_path() {
+                Some(path) if path.exists() => path,
+                _ => return Ok(Self::default()),
+            },
+        };
+
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let config: Self = toml::from_str(&contents).with_context(|| format!("Invalid config file {}", path.display()))?;
+        config.validate().with_context(|| format!("Invalid config file {}", path.display()))?;
+        Ok(config)
+    }
+
+    /// Check settings that parse but can't be used
+    fn validate(&self) -> Result<()> {
+        if StrftimeItems::new(&self.date_format).any(|item| matches!(item, Item::Error)) {
+            bail!("Invalid date_format '{}'", self.date_format);
+        }
+        if !(10..=90).contains(&self.sidebar_width) {
+            bail!("sidebar_width must be between 10 and 90");
+        }
+        Ok(())
+    }
+
+    /// Directory holding the task files of the open workspace
+    pub fn data_dir(&self) -> Result<PathBuf> {
+        self.workspace_dir(&self.workspace)
+    }
+
+    /// Directory holding a workspace's task files, with a leading `~`
+    /// expanded
+    pub fn workspace_dir(&self, name: &str) -> Result<PathBuf> {
+        let dir = match (self.workspaces.get(name), &self.data_dir) {
+            (Some(dir), _) => dir.clone(),
+            (None, Some(dir)) if name == DEFAULT_WORKSPACE => dir.clone(),
+            (None, None) if name == DEFAULT_WORKSPACE => {
+                return Ok(dirs::home_dir().context("Could not find home directory")?.join("todo-data"));
+            }
+            (None, _) => bail!("Unknown workspace '{}'", name),
+        };
+        match (dir.strip_prefix("~"), dirs::home_dir()) {
+            (Ok(rest), Some(home_dir)) => Ok(home_dir.join(rest)),
+            _ => Ok(dir),
+        }
+    }
+
+    /// The default workspace followed by the configured ones
+    pub fn workspace_names(&self) -> Vec<String> {
+        let mut names = vec![DEFAULT_WORKSPACE.to_string()];
+        names.extend(self.workspaces.keys().filter(|name| *name != DEFAULT_WORKSPACE).cloned());
+        names
+    }
+
+    /// Whether My Day shows a task. Tasks added to My Day are always shown.
+    pub fn shows_in_my_day(&self, task: &Task) -> bool {
+        task.is_in_my_day
+            || (self.my_day_due_today && task.is_due_today())
+            || (self.my_day_overdue && task.is_overdue())
+            || (self.my_day_starting && task.starts_today())
+    }
+}
+
+/// Workspace kept in `data_dir`, which is the only one unless others are
+/// configured
+pub const DEFAULT_WORKSPACE: &str = "default";
+
+/// Directory holding todo-tui's config files, e.g. `~/.config/todo-tui`
+pub fn config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("todo-tui"))
+}
+
+fn config_file_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("config.toml"))
+}
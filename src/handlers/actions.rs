@@ -0,0 +1,1151 @@
+use super::commands;
+use crate::action::Action;
+use crate::app::{App, AppState, BulkEditField, LastChange, ListEditorMode, SidebarItem, TaskEditorMode, TaskEditorState, TaskSort};
+use crate::models::{List, RecurringFrequency, Task};
+use crate::utils::{date_utils, quick_add, timesheet};
+use crate::workspace;
+use anyhow::Result;
+use chrono::{Local, Months};
+
+/// Apply a normal mode action with an optional count, which repeats it.
+/// Repeated changes to tasks are undone together, and are remembered for
+/// `.`. Cursor movements, undo and redo repeat step by step, and other
+/// actions ignore the count.
+pub fn dispatch_counted(app: &mut App, action: Action, count: Option<usize>) -> Result<()> {
+    if action == Action::RepeatLast {
+        return repeat_last_change(app, count);
+    }
+
+    if app.config.confirm_delete {
+        if let Some(question) = confirmation_question(app, action, count) {
+            app.pending_confirmation = Some((action, count));
+            app.status_message = Some(format!("{} (y/n)", question));
+            return Ok(());
+        }
+    }
+    run_counted(app, action, count)
+}
+
+/// Go ahead with the action waiting for confirmation, or drop it
+pub fn answer_confirmation(app: &mut App, confirmed: bool) -> Result<()> {
+    match app.pending_confirmation.take() {
+        Some((action, count)) if confirmed => run_counted(app, action, count),
+        _ => Ok(()),
+    }
+}
+
+/// The question asked before an action that deletes something, or `None`
+/// if the action wouldn't delete anything on this screen
+fn confirmation_question(app: &App, action: Action, count: Option<usize>) -> Option<String> {
+    match action {
+        Action::DeleteSelected if app.state == AppState::ListOverview => match selected_sidebar_item(app)? {
+            SidebarItem::List(_) if app.lists.len() == 1 => None,
+            SidebarItem::List(list_id) => {
+                let list = app.lists.iter().find(|l| l.id == list_id)?;
+                Some(format!("Delete list '{}'?", list.name))
+            }
+            SidebarItem::Group { .. } => None,
+        },
+        Action::DeleteSelected if is_task_screen(app) => {
+            let task_count = tasks_to_delete(app, count).len();
+            (task_count > 0).then(|| format!("Delete {}?", task_count_label(task_count)))
+        }
+        Action::Purge if app.state == AppState::Trash && !app.get_trash_items().is_empty() => {
+            Some("Purge permanently?".to_string())
+        }
+        Action::EmptyTrash if app.state == AppState::Trash && !app.get_trash_items().is_empty() => {
+            Some("Empty the trash?".to_string())
+        }
+        _ => None,
+    }
+}
+
+fn run_counted(app: &mut App, action: Action, count: Option<usize>) -> Result<()> {
+    let times = action_times(app, count);
+    if action == Action::DeleteSelected && is_task_screen(app) {
+        // Deleted in one go, so the tasks are the ones that were confirmed
+        delete_tasks(app, &tasks_to_delete(app, count))?;
+    } else if times > 1 && action.is_repeatable() && is_task_screen(app) {
+        app.run_batch(&format!("{} x{}", action.label(), times), |app| {
+            for _ in 0..times {
+                dispatch(app, action)?;
+            }
+            Ok(())
+        })?;
+    } else if action.is_motion() || matches!(action, Action::Undo | Action::Redo) {
+        // Undo and redo walk the history, so they mustn't be recorded in it
+        for _ in 0..times {
+            dispatch(app, action)?;
+        }
+    } else {
+        dispatch(app, action)?;
+    }
+
+    if action.is_repeatable() && is_task_screen(app) {
+        app.last_change = Some(LastChange::Action { action, count: times });
+    }
+    Ok(())
+}
+
+/// How many times an action runs. A selection already says which tasks an
+/// action applies to, so the count is ignored while there is one.
+fn action_times(app: &App, count: Option<usize>) -> usize {
+    if app.has_selection() {
+        1
+    } else {
+        count.unwrap_or(1)
+    }
+}
+
+/// The tasks a delete with this count removes: the selected tasks, or the
+/// task under the cursor and the ones below it
+fn tasks_to_delete(app: &App, count: Option<usize>) -> Vec<u32> {
+    if app.has_selection() {
+        return app.get_target_task_ids();
+    }
+    let tasks = app.get_screen_tasks();
+    (app.selected_task_index..app.get_displayable_task_count(&tasks))
+        .filter_map(|index| app.get_task_at_display_index(&tasks, index))
+        .take(action_times(app, count))
+        .map(|task| task.id)
+        .collect()
+}
+
+fn delete_tasks(app: &mut App, task_ids: &[u32]) -> Result<()> {
+    if !task_ids.is_empty() {
+        app.delete_tasks(task_ids)?;
+        app.status_message = Some(format!("{} moved to trash", task_count_label(task_ids.len())));
+        app.clear_selection();
+        clamp_selection(app);
+    }
+    Ok(())
+}
+
+/// Repeat the last change on the task under the cursor. A count replaces
+/// the count the change was made with.
+fn repeat_last_change(app: &mut App, count: Option<usize>) -> Result<()> {
+    if !is_task_screen(app) {
+        return Ok(());
+    }
+    match app.last_change.clone() {
+        Some(LastChange::Action { action, count: last_count }) => {
+            dispatch_counted(app, action, Some(count.unwrap_or(last_count)))?;
+        }
+        Some(LastChange::BulkEdit { field, input }) => {
+            let task_ids = app.get_target_task_ids();
+            if !task_ids.is_empty() && apply_bulk_edit(app, &task_ids, &field, &input)? {
+                app.clear_selection();
+            }
+        }
+        None => {}
+    }
+    Ok(())
+}
+
+/// Remember the task under the cursor as mark `letter`
+pub fn set_mark(app: &mut App, letter: char) {
+    if let Some(task) = selected_task(app) {
+        app.marks.insert(letter, task.id);
+        app.status_message = Some(format!("Marked '{}' as {}", task.title, letter));
+    }
+}
+
+/// Open the list holding the task at mark `letter` and select the task
+pub fn jump_to_mark(app: &mut App, letter: char) {
+    let Some(task) = app.marks.get(&letter).and_then(|id| app.tasks.iter().find(|t| t.id == *id)) else {
+        app.status_message = Some(format!("Mark {} is not set", letter));
+        return;
+    };
+    let (task_id, list_id) = (task.id, task.list_id);
+
+    app.clear_selection();
+    app.set_current_list(list_id);
+    if let Some(index) = display_index_of(app, task_id) {
+        app.selected_task_index = index;
+    }
+}
+
+/// Apply an action to the app. Actions that don't apply to the current
+/// screen are ignored, so any input source can emit any action.
+pub fn dispatch(app: &mut App, action: Action) -> Result<()> {
+    match action {
+        Action::Quit => {
+            app.should_quit = true;
+        }
+        Action::Back => go_back(app),
+        Action::MoveUp if app.state == AppState::CommandLine => commands::history_previous(app),
+        Action::MoveDown if app.state == AppState::CommandLine => commands::history_next(app),
+        Action::Complete if app.state == AppState::CommandLine => commands::complete(app),
+        Action::MoveUp if app.state == AppState::CommandPalette => {
+            app.palette_index = app.palette_index.saturating_sub(1);
+        }
+        Action::MoveDown
+            if app.state == AppState::CommandPalette
+                && app.palette_index + 1 < commands::palette_actions(app).len() =>
+        {
+            app.palette_index += 1;
+        }
+        Action::MoveLeft if app.state == AppState::Calendar => move_calendar_day(app, -1),
+        Action::MoveRight if app.state == AppState::Calendar => move_calendar_day(app, 1),
+        Action::MoveUp if app.state == AppState::Calendar => move_calendar_day(app, -7),
+        Action::MoveDown if app.state == AppState::Calendar => move_calendar_day(app, 7),
+        Action::PageUp if app.state == AppState::Calendar => move_calendar_month(app, -1),
+        Action::PageDown if app.state == AppState::Calendar => move_calendar_month(app, 1),
+        Action::GoToday if app.state == AppState::Calendar => {
+            app.calendar.day = Local::now().date_naive();
+            app.selected_task_index = 0;
+        }
+        Action::NextTask if app.state == AppState::Calendar => {
+            // Wraps around to the first task of the day
+            let task_count = app.get_calendar_tasks(app.calendar.day).len();
+            app.selected_task_index = if app.selected_task_index + 1 < task_count { app.selected_task_index + 1 } else { 0 };
+        }
+        Action::Reschedule if app.state == AppState::Calendar => match app.calendar.moving_task_id {
+            Some(_) => drop_moving_task(app)?,
+            None => {
+                if let Some(task) = selected_task(app) {
+                    app.calendar.moving_task_id = Some(task.id);
+                    app.status_message = Some(format!("Moving '{}', pick a day and press Enter", task.title));
+                }
+            }
+        },
+        Action::ToggleComplete if app.state == AppState::Calendar => {
+            if let Some(task) = selected_task(app) {
+                app.complete_tasks(&[task.id])?;
+            }
+        }
+        Action::MoveLeft if is_board(app) => move_board_column(app, -1),
+        Action::MoveRight if is_board(app) => move_board_column(app, 1),
+        Action::MoveTaskLeft if is_board(app) => shift_tasks(app, -1)?,
+        Action::MoveTaskRight if is_board(app) => shift_tasks(app, 1)?,
+        Action::ToggleSuggestions if app.state == AppState::MyDay => {
+            focus_suggestions(app, !app.my_day.suggestions_focused);
+        }
+        Action::Select | Action::ToggleMyDay if is_suggestions(app) => add_suggestions(app)?,
+        Action::ToggleBoard if app.state == AppState::TaskList => {
+            if let Some(list) = app.get_current_list() {
+                let (list_id, board) = (list.id, !list.board);
+                app.set_list_board(list_id, board)?;
+                app.clear_selection();
+                app.board.column = 0;
+                app.selected_task_index = 0;
+            }
+        }
+        Action::MoveUp => {
+            app.selected_task_index = app.selected_task_index.saturating_sub(1);
+        }
+        Action::MoveDown if app.selected_task_index + 1 < row_count(app) => {
+            app.selected_task_index += 1;
+        }
+        Action::GoTop => {
+            app.selected_task_index = 0;
+        }
+        Action::HalfPageUp | Action::PageUp => {
+            let rows = page_rows(app, action);
+            app.selected_task_index = app.selected_task_index.saturating_sub(rows);
+        }
+        Action::HalfPageDown | Action::PageDown => {
+            let last_row = row_count(app).saturating_sub(1);
+            app.selected_task_index = (app.selected_task_index + page_rows(app, action)).min(last_row);
+        }
+        Action::GoBottom => {
+            app.selected_task_index = row_count(app).saturating_sub(1);
+        }
+        Action::Select => select(app)?,
+        Action::Confirm => confirm(app)?,
+        Action::NewList if app.state == AppState::ListOverview => {
+            app.input_buffer.clear();
+            app.list_editor_mode = ListEditorMode::Create;
+            app.state = AppState::ListEditor;
+        }
+        Action::NewTask if app.state == AppState::TaskList => {
+            if let Some(list_id) = app.current_list_id {
+                app.previous_state = app.state.clone();
+                app.editing_task = Some(Task::new(0, "".to_string(), list_id));
+                app.input_buffer.clear();
+                app.state = AppState::TaskEditor;
+                app.task_editor_mode = TaskEditorMode::Create;
+                app.task_editor_state = TaskEditorState::Title;
+            }
+        }
+        Action::QuickAdd if is_task_screen(app) => {
+            app.previous_state = app.state.clone();
+            app.input_buffer.clear();
+            app.state = AppState::QuickAdd;
+        }
+        Action::SetGroup if app.state == AppState::ListOverview => {
+            // Assign the selected list to a group
+            if let Some(SidebarItem::List(list_id)) = selected_sidebar_item(app) {
+                app.input_buffer = app
+                    .lists
+                    .iter()
+                    .find(|l| l.id == list_id)
+                    .and_then(|l| l.group.clone())
+                    .unwrap_or_default();
+                app.editing_list_id = Some(list_id);
+                app.list_editor_mode = ListEditorMode::Group;
+                app.state = AppState::ListEditor;
+            }
+        }
+        Action::ToggleGroup if is_sidebar_screen(app) => {
+            if let Some(SidebarItem::Group { name, .. }) = selected_sidebar_item(app) {
+                app.toggle_group_collapsed(&name)?;
+            }
+        }
+        Action::ToggleAllGroups if is_task_screen(app) => {
+            app.toggle_all_groups_collapsed()?;
+        }
+        Action::EditTask if is_task_screen(app) => edit_selected_task(app),
+        Action::OpenNotes if is_task_screen(app) => {
+            if let Some(task) = selected_task(app) {
+                app.previous_state = app.state.clone();
+                app.input_buffer = task.notes.clone().unwrap_or_default();
+                app.editing_task = Some(task);
+                app.state = AppState::NoteEditor;
+            }
+        }
+        Action::ToggleComplete if is_task_screen(app) => {
+            let task_ids = app.get_target_task_ids();
+            if !task_ids.is_empty() {
+                app.complete_tasks(&task_ids)?;
+                app.clear_selection();
+                clamp_selection(app);
+            }
+        }
+        Action::ToggleMyDay if is_task_screen(app) => {
+            let task_ids = app.get_target_task_ids();
+            if app.state == AppState::MyDay {
+                app.remove_tasks_from_my_day(&task_ids)?;
+            } else {
+                app.toggle_tasks_in_my_day(&task_ids)?;
+            }
+            app.clear_selection();
+            clamp_selection(app);
+        }
+        Action::DeleteSelected if app.state == AppState::ListOverview => {
+            if let Some(SidebarItem::List(list_id)) = selected_sidebar_item(app) {
+                if app.lists.len() > 1 {
+                    app.delete_list(list_id)?;
+                    app.status_message = Some("List moved to trash".to_string());
+                    clamp_selection(app);
+                } else {
+                    app.status_message = Some("Can't delete the only list".to_string());
+                }
+            }
+        }
+        Action::DeleteSelected if is_task_screen(app) => delete_tasks(app, &tasks_to_delete(app, None))?,
+        Action::MoveTask if is_task_screen(app) => {
+            // Move tasks to another list
+            app.moving_task_ids = app.get_target_task_ids();
+            if !app.moving_task_ids.is_empty() {
+                app.previous_state = app.state.clone();
+                app.selected_task_index = 0;
+                app.state = AppState::MoveTask;
+            }
+        }
+        Action::ReorderUp | Action::ReorderDown if app.state == AppState::MyDay && can_reorder(app) => {
+            if let Some(task) = selected_task(app) {
+                if action == Action::ReorderUp {
+                    app.move_task_up_in_my_day(task.id)?;
+                } else {
+                    app.move_task_down_in_my_day(task.id)?;
+                }
+                // Keep the cursor on the moved task
+                if let Some(index) = display_index_of(app, task.id) {
+                    app.selected_task_index = index;
+                }
+            }
+        }
+        Action::ReorderUp | Action::ReorderDown if can_reorder(app) => {
+            let tasks = app.get_screen_tasks();
+            let neighbour = if action == Action::ReorderUp {
+                app.selected_task_index.checked_sub(1)
+            } else {
+                Some(app.selected_task_index + 1)
+            };
+            let task = app.get_task_at_display_index(&tasks, app.selected_task_index);
+            let target = neighbour.and_then(|index| app.get_task_at_display_index(&tasks, index));
+            // Tasks only move within their own section, active or completed
+            if let (Some(task), Some(target)) = (task, target) {
+                if task.is_completed == target.is_completed {
+                    let (task_id, target_id) = (task.id, target.id);
+                    reorder_task(app, task_id, target_id)?;
+                }
+            }
+        }
+        Action::ToggleVisual if is_task_screen(app) => {
+            // Start or end a visual range at the cursor
+            app.visual_anchor = match app.visual_anchor {
+                Some(_) => None,
+                None => Some(app.selected_task_index),
+            };
+        }
+        Action::ToggleMark if is_task_screen(app) => {
+            if let Some(task) = selected_task(app) {
+                app.toggle_task_mark(task.id);
+            }
+        }
+        Action::SetDueDate if is_task_screen(app) => start_bulk_edit(app, BulkEditField::DueDate),
+        Action::AddTag if is_task_screen(app) => start_bulk_edit(app, BulkEditField::Tag),
+        Action::OpenMyDay if !is_editing(app) => {
+            open_screen(app, AppState::MyDay);
+            app.my_day = Default::default();
+        }
+        Action::OpenHistory if !is_editing(app) => open_screen(app, AppState::CompletedHistory),
+        Action::OpenTrash if !is_editing(app) => open_screen(app, AppState::Trash),
+        Action::OpenStats if !is_editing(app) => open_screen(app, AppState::Stats),
+        Action::OpenTimesheet if !is_editing(app) => open_screen(app, AppState::Timesheet),
+        Action::ToggleTimer if is_task_screen(app) || app.state == AppState::Timesheet => toggle_timer(app)?,
+        Action::OpenUpcoming if !is_editing(app) => open_screen(app, AppState::Upcoming),
+        Action::OpenCalendar if !is_editing(app) => {
+            open_screen(app, AppState::Calendar);
+            app.calendar = Default::default();
+        }
+        Action::OpenWorkspaces if !is_editing(app) => {
+            app.workspace_summaries = workspace::summaries(&app.config);
+            open_screen(app, AppState::Workspaces);
+            // Start on the open workspace
+            if let Some(index) = app.workspace_summaries.iter().position(|w| w.name == app.config.workspace) {
+                app.selected_task_index = index;
+            }
+        }
+        Action::Restore if app.state == AppState::Trash => {
+            if let Some(item) = app.get_trash_items().get(app.selected_task_index).cloned() {
+                app.restore_trash_item(&item)?;
+                app.status_message = Some("Restored from trash".to_string());
+                clamp_selection(app);
+            }
+        }
+        Action::Purge if app.state == AppState::Trash => {
+            if let Some(item) = app.get_trash_items().get(app.selected_task_index).cloned() {
+                app.purge_trash_item(&item)?;
+                app.status_message = Some("Purged permanently".to_string());
+                clamp_selection(app);
+            }
+        }
+        Action::EmptyTrash if app.state == AppState::Trash => {
+            app.empty_trash()?;
+            app.status_message = Some("Trash emptied".to_string());
+            app.selected_task_index = 0;
+        }
+        Action::ToggleComplete if app.state == AppState::CompletedHistory => {
+            if let Some(task) = app.get_completed_history().get(app.selected_task_index) {
+                let (task_id, title) = (task.id, task.title.clone());
+                app.reopen_task(task_id)?;
+                app.status_message = Some(format!("Reopened '{}'", title));
+                clamp_selection(app);
+            }
+        }
+        Action::Search if app.state == AppState::CompletedHistory => {
+            app.history_filter.searching = true;
+        }
+        Action::CycleListFilter if app.state == AppState::CompletedHistory => {
+            app.cycle_history_list_filter();
+        }
+        Action::SetMark if is_task_screen(app) => {
+            app.pending_mark = Some(action);
+        }
+        Action::JumpToMark if !is_editing(app) => {
+            app.pending_mark = Some(action);
+        }
+        Action::OpenCommandLine if !is_editing(app) => {
+            app.previous_state = app.state.clone();
+            app.input_buffer.clear();
+            app.command_history_index = None;
+            app.state = AppState::CommandLine;
+        }
+        Action::OpenPalette if !is_editing(app) => {
+            app.previous_state = app.state.clone();
+            app.input_buffer.clear();
+            app.palette_index = 0;
+            app.state = AppState::CommandPalette;
+        }
+        Action::CycleTheme if !is_editing(app) => commands::cycle_theme(app),
+        Action::Undo if !is_editing(app) => {
+            app.undo()?;
+            clamp_selection(app);
+        }
+        Action::Redo if !is_editing(app) => {
+            app.redo()?;
+            clamp_selection(app);
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Leave the current screen, or drop the selection first on task screens
+fn go_back(app: &mut App) {
+    match app.state {
+        AppState::ListOverview => {}
+        AppState::TaskList | AppState::MyDay | AppState::Upcoming if app.has_selection() => {
+            app.clear_selection();
+        }
+        AppState::MyDay if app.my_day.suggestions_focused => focus_suggestions(app, false),
+        AppState::TaskList => {
+            app.state = AppState::ListOverview;
+            app.current_list_id = None;
+            app.selected_task_index = 0;
+        }
+        AppState::Calendar if app.calendar.moving_task_id.is_some() => {
+            app.calendar.moving_task_id = None;
+            app.status_message = None;
+        }
+        AppState::MyDay
+        | AppState::Upcoming
+        | AppState::Trash
+        | AppState::Workspaces
+        | AppState::Calendar
+        | AppState::Stats
+        | AppState::Timesheet => {
+            app.state = AppState::ListOverview;
+            app.selected_task_index = 0;
+        }
+        AppState::CompletedHistory if app.history_filter.searching => {
+            app.history_filter.searching = false;
+        }
+        AppState::CompletedHistory => {
+            app.state = AppState::ListOverview;
+            app.history_filter = Default::default();
+            app.selected_task_index = 0;
+        }
+        AppState::TaskEditor => {
+            app.state = editor_return_state(app);
+            app.editing_task = None;
+            app.input_buffer.clear();
+            app.task_editor_state = TaskEditorState::Title;
+        }
+        AppState::NoteEditor => {
+            app.state = editor_return_state(app);
+            app.editing_task = None;
+            app.input_buffer.clear();
+        }
+        AppState::ListEditor => {
+            app.state = AppState::ListOverview;
+            app.editing_list_id = None;
+            app.input_buffer.clear();
+        }
+        AppState::MoveTask => {
+            app.moving_task_ids.clear();
+            app.state = app.previous_state.clone();
+            app.selected_task_index = 0;
+        }
+        AppState::BulkEdit | AppState::QuickAdd | AppState::CommandLine | AppState::CommandPalette => {
+            app.state = app.previous_state.clone();
+            app.input_buffer.clear();
+        }
+    }
+}
+
+/// Act on the row under the cursor
+fn select(app: &mut App) -> Result<()> {
+    match app.state {
+        AppState::ListOverview => match selected_sidebar_item(app) {
+            Some(SidebarItem::Group { name, .. }) => app.toggle_group_collapsed(&name)?,
+            Some(SidebarItem::List(list_id)) => app.set_current_list(list_id),
+            None => {}
+        },
+        AppState::TaskList | AppState::Upcoming => edit_selected_task(app),
+        AppState::Calendar if app.calendar.moving_task_id.is_some() => drop_moving_task(app)?,
+        AppState::Calendar => edit_selected_task(app),
+        AppState::Workspaces => {
+            if let Some(workspace) = app.workspace_summaries.get(app.selected_task_index) {
+                let name = workspace.name.clone();
+                commands::switch_workspace(app, &name);
+            }
+        }
+        AppState::MoveTask => match selected_sidebar_item(app) {
+            Some(SidebarItem::Group { name, .. }) => app.toggle_group_collapsed(&name)?,
+            Some(SidebarItem::List(list_id)) => {
+                let task_ids = std::mem::take(&mut app.moving_task_ids);
+                app.move_tasks_to_list(&task_ids, list_id)?;
+                app.clear_selection();
+                app.state = app.previous_state.clone();
+                app.selected_task_index = 0;
+            }
+            None => {}
+        },
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Submit the input on a text entry screen
+fn confirm(app: &mut App) -> Result<()> {
+    match app.state {
+        AppState::TaskEditor => confirm_task_editor(app)?,
+        AppState::NoteEditor => {
+            if let Some(mut task) = app.editing_task.take() {
+                task.set_notes(Some(app.input_buffer.clone()));
+                app.update_task(task)?;
+            }
+            app.state = editor_return_state(app);
+            app.input_buffer.clear();
+        }
+        AppState::ListEditor => match app.list_editor_mode {
+            ListEditorMode::Create => {
+                if !app.input_buffer.trim().is_empty() {
+                    let new_list = List::new(0, app.input_buffer.trim().to_string());
+                    app.add_list(new_list)?;
+                    app.state = AppState::ListOverview;
+                    app.input_buffer.clear();
+                }
+            }
+            ListEditorMode::Group => {
+                if let Some(list_id) = app.editing_list_id.take() {
+                    // An empty group name removes the list from its group
+                    let group = Some(app.input_buffer.trim().to_string()).filter(|g| !g.is_empty());
+                    app.set_list_group(list_id, group)?;
+                }
+                app.state = AppState::ListOverview;
+                app.input_buffer.clear();
+            }
+        },
+        AppState::BulkEdit => confirm_bulk_edit(app)?,
+        AppState::QuickAdd => confirm_quick_add(app)?,
+        AppState::CompletedHistory => {
+            app.history_filter.searching = false;
+        }
+        AppState::CommandLine => {
+            // Commands act on the screen the command line was opened from
+            let line = std::mem::take(&mut app.input_buffer);
+            app.state = app.previous_state.clone();
+            commands::run_command(app, &line)?;
+        }
+        AppState::CommandPalette => {
+            let action = commands::palette_actions(app).get(app.palette_index).copied();
+            app.input_buffer.clear();
+            app.state = app.previous_state.clone();
+            if let Some(action) = action {
+                dispatch_counted(app, action, None)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Advance the task editor one step, saving the task after the last one
+fn confirm_task_editor(app: &mut App) -> Result<()> {
+    let Some(task) = app.editing_task.as_mut() else {
+        return Ok(());
+    };
+
+    match app.task_editor_state {
+        TaskEditorState::Title => {
+            if !app.input_buffer.trim().is_empty() {
+                task.title = app.input_buffer.trim().to_string();
+                app.input_buffer.clear();
+                app.task_editor_state = TaskEditorState::DueDate;
+            } else if app.task_editor_mode == TaskEditorMode::Edit {
+                // New tasks need a title, existing ones keep theirs
+                app.input_buffer.clear();
+                app.task_editor_state = TaskEditorState::DueDate;
+            }
+        }
+        TaskEditorState::DueDate => {
+            if !app.input_buffer.trim().is_empty() {
+                // A date that doesn't parse keeps the editor on this step
+                match date_utils::parse_date_input(&app.input_buffer, app.config.week_start, app.config.date_order) {
+                    Ok(due_date) => task.set_due_date(Some(due_date)),
+                    Err(err) => {
+                        app.status_message = Some(format!("{:#}", err));
+                        return Ok(());
+                    }
+                }
+            }
+            app.input_buffer.clear();
+            app.task_editor_state = TaskEditorState::Frequency;
+        }
+        TaskEditorState::Frequency => {
+            if !app.input_buffer.trim().is_empty() {
+                let frequency = match app.input_buffer.trim().to_lowercase().as_str() {
+                    "daily" => Some(RecurringFrequency::Daily),
+                    "weekdays" => Some(RecurringFrequency::Weekdays),
+                    "weekly" => Some(RecurringFrequency::Weekly),
+                    "monthly" => Some(RecurringFrequency::Monthly),
+                    "yearly" => Some(RecurringFrequency::Yearly),
+                    _ => None,
+                };
+                task.set_recurring_frequency(frequency);
+            }
+            app.input_buffer.clear();
+            app.task_editor_state = TaskEditorState::Notes;
+        }
+        TaskEditorState::Notes => {
+            if !app.input_buffer.trim().is_empty() {
+                task.set_notes(Some(app.input_buffer.trim().to_string()));
+            }
+
+            let task_to_save = task.clone();
+            match app.task_editor_mode {
+                TaskEditorMode::Create => app.add_task(task_to_save)?,
+                TaskEditorMode::Edit => app.update_task(task_to_save)?,
+            }
+
+            app.state = editor_return_state(app);
+            app.editing_task = None;
+            app.input_buffer.clear();
+            app.task_editor_state = TaskEditorState::Title;
+        }
+    }
+    Ok(())
+}
+
+/// Apply the bulk edit prompt to the tasks it was opened for
+fn confirm_bulk_edit(app: &mut App) -> Result<()> {
+    let task_ids = app.get_target_task_ids_for(&app.previous_state);
+    let field = app.bulk_edit_field.clone();
+    let input = app.input_buffer.trim().to_string();
+    if !apply_bulk_edit(app, &task_ids, &field, &input)? {
+        return Ok(());
+    }
+
+    app.last_change = Some(LastChange::BulkEdit { field, input });
+    app.state = app.previous_state.clone();
+    app.clear_selection();
+    app.input_buffer.clear();
+    Ok(())
+}
+
+/// Set a due date or add a tag on every task. Returns false, leaving the
+/// tasks alone, if the input isn't usable.
+fn apply_bulk_edit(app: &mut App, task_ids: &[u32], field: &BulkEditField, input: &str) -> Result<bool> {
+    match field {
+        BulkEditField::DueDate => {
+            // An empty date clears the due date
+            let due_date = if input.is_empty() {
+                None
+            } else {
+                match date_utils::parse_date_input(input, app.config.week_start, app.config.date_order) {
+                    Ok(due_date) => Some(due_date),
+                    Err(err) => {
+                        app.status_message = Some(format!("{:#}", err));
+                        return Ok(false);
+                    }
+                }
+            };
+            app.set_tasks_due_date(task_ids, due_date)?;
+        }
+        BulkEditField::Tag => {
+            if input.is_empty() {
+                return Ok(false);
+            }
+            app.add_tag_to_tasks(task_ids, input)?;
+        }
+    }
+    Ok(true)
+}
+
+/// Add the task described on the quick-add line. The prompt stays open if
+/// the task has no title or names a list that doesn't exist.
+fn confirm_quick_add(app: &mut App) -> Result<()> {
+    let parsed = quick_add::parse(&app.input_buffer, app.config.week_start, app.config.date_order);
+    if parsed.title.is_empty() {
+        app.status_message = Some("A task needs a title".to_string());
+        return Ok(());
+    }
+    let list_id = match quick_add_list(app, &parsed) {
+        Ok(list_id) => list_id,
+        Err(err) => {
+            app.status_message = Some(format!("{:#}", err));
+            return Ok(());
+        }
+    };
+
+    let mut task = Task::new(0, parsed.title.clone(), list_id);
+    task.set_due_date(parsed.due_date);
+    task.recurring_frequency = parsed.frequency;
+    task.tags = parsed.tags;
+    task.priority = parsed.priority;
+    let add_to_my_day = app.previous_state == AppState::MyDay;
+    app.run_batch("Add task", |app| {
+        app.add_task(task)?;
+        if add_to_my_day {
+            let task_id = app.tasks.last().map(|t| t.id).unwrap_or_default();
+            app.add_task_to_my_day(task_id)?;
+        }
+        Ok(())
+    })?;
+
+    app.status_message = Some(format!("Added '{}'", parsed.title));
+    app.state = app.previous_state.clone();
+    app.input_buffer.clear();
+    Ok(())
+}
+
+/// The list a quick-add task goes to: the `@list` it names, or else the
+/// open list, or the default list when added from My Day
+pub fn quick_add_list(app: &App, parsed: &quick_add::QuickAdd) -> Result<u32> {
+    match (&parsed.list, app.current_list_id) {
+        (Some(name), _) => app.find_list(name),
+        (None, Some(list_id)) if app.previous_state == AppState::TaskList => Ok(list_id),
+        (None, _) => Ok(app.default_list_id()),
+    }
+}
+
+fn edit_selected_task(app: &mut App) {
+    if let Some(task) = selected_task(app) {
+        app.previous_state = app.state.clone();
+        app.input_buffer = task.title.clone();
+        app.editing_task = Some(task);
+        app.state = AppState::TaskEditor;
+        app.task_editor_mode = TaskEditorMode::Edit;
+        app.task_editor_state = TaskEditorState::Title;
+    }
+}
+
+/// Pick another day on the calendar. The cursor goes back to the first task
+/// of the day.
+fn move_calendar_day(app: &mut App, days: i64) {
+    app.calendar.day += chrono::Duration::days(days);
+    app.selected_task_index = 0;
+}
+
+/// Start the timer of the task under the cursor, or stop it if it's the one
+/// running. Without a task, such as on the timesheet, the running timer is
+/// stopped.
+fn toggle_timer(app: &mut App) -> Result<()> {
+    let task = if app.state == AppState::Timesheet { None } else { selected_task(app) };
+    let running_task_id = app.running_timer().map(|entry| entry.task_id);
+    match task {
+        Some(task) if running_task_id != Some(task.id) => {
+            app.start_timer(task.id)?;
+            app.status_message = Some(format!("Timing '{}'", task.title));
+        }
+        _ => {
+            if let Some(entry) = app.stop_timer()? {
+                let title = app.find_any_task(entry.task_id).map(|t| t.title.clone()).unwrap_or_default();
+                let duration = timesheet::duration_label(entry.duration(Local::now()));
+                app.status_message = Some(format!("Stopped '{}' after {}", title, duration));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Whether the cursor is in the suggestions pane of My Day
+pub fn is_suggestions(app: &App) -> bool {
+    app.state == AppState::MyDay && app.my_day.suggestions_focused
+}
+
+/// Move the cursor between My Day's tasks and its suggestions. Each pane
+/// keeps its row while the other one has the cursor.
+pub fn focus_suggestions(app: &mut App, focused: bool) {
+    if app.my_day.suggestions_focused == focused {
+        return;
+    }
+    app.clear_selection();
+    app.my_day.suggestions_focused = focused;
+    std::mem::swap(&mut app.selected_task_index, &mut app.my_day.inactive_index);
+    clamp_selection(app);
+}
+
+/// Add the selected suggestions to My Day
+fn add_suggestions(app: &mut App) -> Result<()> {
+    let task_ids = app.get_target_task_ids();
+    if task_ids.is_empty() {
+        return Ok(());
+    }
+    app.add_tasks_to_my_day(&task_ids)?;
+    app.status_message = Some(format!("Added {} to My Day", task_count_label(task_ids.len())));
+    app.clear_selection();
+    clamp_selection(app);
+    Ok(())
+}
+
+/// Whether the current list is shown as a board
+pub fn is_board(app: &App) -> bool {
+    app.state == AppState::TaskList && app.current_board().is_some()
+}
+
+/// Focus the board column to the left or right. The cursor keeps its row
+/// where the column has one.
+fn move_board_column(app: &mut App, offset: isize) {
+    let Some(columns) = app.current_board().map(|list| list.statuses().len()) else {
+        return;
+    };
+    let column = app.board.column.saturating_add_signed(offset).min(columns - 1);
+    if column != app.board.column {
+        app.clear_selection();
+        app.board.column = column;
+        clamp_selection(app);
+    }
+}
+
+/// Move the selected tasks one column over on the board, and follow them
+fn shift_tasks(app: &mut App, offset: isize) -> Result<()> {
+    let Some(columns) = app.current_board().map(|list| list.statuses().len()) else {
+        return Ok(());
+    };
+    let column = app.board.column.saturating_add_signed(offset).min(columns - 1);
+    if column == app.board.column {
+        return Ok(());
+    }
+    let task_ids = app.get_target_task_ids();
+    app.move_tasks_to_column(&task_ids, column)?;
+    app.clear_selection();
+    app.board.column = column;
+    match task_ids.first().and_then(|&task_id| display_index_of(app, task_id)) {
+        Some(index) => app.selected_task_index = index,
+        None => clamp_selection(app),
+    }
+    Ok(())
+}
+
+/// Pick the same day in another month, or the last day of a shorter month
+fn move_calendar_month(app: &mut App, months: i32) {
+    let day = app.calendar.day;
+    let moved = if months < 0 {
+        day.checked_sub_months(Months::new(months.unsigned_abs()))
+    } else {
+        day.checked_add_months(Months::new(months as u32))
+    };
+    app.calendar.day = moved.unwrap_or(day);
+    app.selected_task_index = 0;
+}
+
+/// Give the task being moved on the calendar the picked day as due date
+fn drop_moving_task(app: &mut App) -> Result<()> {
+    if let Some(task_id) = app.calendar.moving_task_id.take() {
+        app.reschedule_task(task_id, app.calendar.day)?;
+        app.status_message = Some(format!("Rescheduled to {}", app.calendar.day.format(&app.config.date_format)));
+        // Keep the cursor on the moved task
+        let tasks = app.get_calendar_tasks(app.calendar.day);
+        app.selected_task_index = tasks.iter().position(|t| t.id == task_id).unwrap_or(0);
+    }
+    Ok(())
+}
+
+/// Open the bulk edit prompt for the selected tasks
+fn start_bulk_edit(app: &mut App, field: BulkEditField) {
+    if app.get_target_task_ids().is_empty() {
+        return;
+    }
+    app.bulk_edit_field = field;
+    app.previous_state = app.state.clone();
+    app.input_buffer.clear();
+    app.state = AppState::BulkEdit;
+}
+
+fn open_screen(app: &mut App, state: AppState) {
+    app.clear_selection();
+    app.state = state;
+    app.selected_task_index = 0;
+}
+
+/// Where the task and note editors return to
+fn editor_return_state(app: &App) -> AppState {
+    if matches!(app.previous_state, AppState::Calendar | AppState::Upcoming) {
+        app.previous_state.clone()
+    } else if app.current_list_id.is_some() {
+        AppState::TaskList
+    } else {
+        AppState::ListOverview
+    }
+}
+
+fn is_task_screen(app: &App) -> bool {
+    matches!(app.state, AppState::TaskList | AppState::MyDay | AppState::Upcoming)
+}
+
+fn is_sidebar_screen(app: &App) -> bool {
+    matches!(app.state, AppState::ListOverview | AppState::MoveTask)
+}
+
+fn is_editing(app: &App) -> bool {
+    matches!(
+        app.state,
+        AppState::TaskEditor
+            | AppState::ListEditor
+            | AppState::NoteEditor
+            | AppState::BulkEdit
+            | AppState::QuickAdd
+            | AppState::CommandLine
+            | AppState::CommandPalette
+    )
+}
+
+fn selected_sidebar_item(app: &App) -> Option<SidebarItem> {
+    app.get_sidebar_items().get(app.selected_task_index).cloned()
+}
+
+fn selected_task(app: &App) -> Option<Task> {
+    if app.state == AppState::Calendar {
+        return app.get_calendar_tasks(app.calendar.day).get(app.selected_task_index).map(|task| (*task).clone());
+    }
+    let tasks = app.get_screen_tasks();
+    app.get_task_at_display_index(&tasks, app.selected_task_index).cloned()
+}
+
+pub fn display_index_of(app: &App, task_id: u32) -> Option<usize> {
+    let tasks = app.get_screen_tasks();
+    (0..app.get_displayable_task_count(&tasks))
+        .find(|&index| app.get_task_at_display_index(&tasks, index).map(|t| t.id) == Some(task_id))
+}
+
+/// Whether tasks on the current screen are in an order the user controls
+pub fn can_reorder(app: &App) -> bool {
+    match app.screen_state() {
+        AppState::MyDay => !app.my_day.suggestions_focused,
+        AppState::TaskList => app.task_sort == TaskSort::Manual,
+        _ => false,
+    }
+}
+
+/// Move a task to another task's place and keep the cursor on it
+pub fn reorder_task(app: &mut App, task_id: u32, target_id: u32) -> Result<()> {
+    app.reorder_task(task_id, target_id)?;
+    if let Some(index) = display_index_of(app, task_id) {
+        app.selected_task_index = index;
+    }
+    Ok(())
+}
+
+/// Rows moved by a page or half page scrolling action
+fn page_rows(app: &App, action: Action) -> usize {
+    let page_height = app.view_state.page_height.max(1);
+    match action {
+        Action::HalfPageUp | Action::HalfPageDown => (page_height / 2).max(1),
+        _ => page_height,
+    }
+}
+
+/// Number of selectable rows on the current screen
+pub fn row_count(app: &App) -> usize {
+    match app.state {
+        AppState::ListOverview | AppState::MoveTask => app.get_sidebar_items().len(),
+        AppState::TaskList | AppState::MyDay | AppState::Upcoming => {
+            let tasks = app.get_screen_tasks();
+            app.get_displayable_task_count(&tasks)
+        }
+        AppState::Trash => app.get_trash_items().len(),
+        AppState::CompletedHistory => app.get_completed_history().len(),
+        AppState::Workspaces => app.workspace_summaries.len(),
+        AppState::Calendar => app.get_calendar_tasks(app.calendar.day).len(),
+        AppState::Timesheet => app.get_timesheet().iter().map(|day| day.row_count()).sum(),
+        _ => 0,
+    }
+}
+
+/// Keep the selection on a valid row after the number of rows on screen
+/// changes
+pub fn clamp_selection(app: &mut App) {
+    let row_count = row_count(app);
+    if app.selected_task_index >= row_count {
+        app.selected_task_index = row_count.saturating_sub(1);
+    }
+}
+
+fn task_count_label(count: usize) -> String {
+    if count == 1 {
+        "1 task".to_string()
+    } else {
+        format!("{} tasks", count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn titles(app: &App) -> Vec<String> {
+        app.get_screen_tasks().iter().map(|t| t.title.clone()).collect()
+    }
+
+    #[test]
+    fn count_repeats_action_on_cursor_task() {
+        let mut app = App::with_tasks("count-cursor", &["a", "b", "c", "d"]);
+        dispatch_counted(&mut app, Action::DeleteSelected, Some(2)).unwrap();
+        assert_eq!(titles(&app), ["c", "d"]);
+
+        app.undo().unwrap();
+        assert_eq!(titles(&app).len(), 4);
+    }
+
+    #[test]
+    fn count_is_ignored_with_a_selection() {
+        let mut app = App::with_tasks("count-selection", &["a", "b", "c", "d", "e", "f", "g", "h"]);
+        let ids: Vec<u32> = app.get_screen_tasks().iter().take(5).map(|t| t.id).collect();
+        for id in ids {
+            app.toggle_task_mark(id);
+        }
+        dispatch_counted(&mut app, Action::DeleteSelected, Some(3)).unwrap();
+        assert_eq!(titles(&app), ["f", "g", "h"]);
+
+        let mut app = App::with_tasks("count-selection-toggle", &["a", "b", "c", "d"]);
+        let id = app.get_screen_tasks()[1].id;
+        app.toggle_task_mark(id);
+        dispatch_counted(&mut app, Action::ToggleComplete, Some(3)).unwrap();
+        let completed: Vec<&str> = app.tasks.iter().filter(|t| t.is_completed).map(|t| t.title.as_str()).collect();
+        assert_eq!(completed, ["b"]);
+    }
+
+    #[test]
+    fn delete_confirmation_counts_the_deleted_tasks() {
+        let mut app = App::with_tasks("confirm-count", &["a", "b", "c", "d"]);
+        app.config.confirm_delete = true;
+        app.selected_task_index = 2;
+        dispatch_counted(&mut app, Action::DeleteSelected, Some(5)).unwrap();
+        assert_eq!(app.status_message.as_deref(), Some("Delete 2 tasks? (y/n)"));
+
+        app.config.confirm_delete = false;
+        dispatch_counted(&mut app, Action::DeleteSelected, Some(5)).unwrap();
+        assert_eq!(titles(&app), ["a", "b"]);
+    }
+
+    #[test]
+    fn counted_undo_keeps_redo() {
+        let mut app = App::with_tasks("counted-undo", &["a", "b", "c"]);
+        dispatch_counted(&mut app, Action::DeleteSelected, None).unwrap();
+        dispatch_counted(&mut app, Action::DeleteSelected, None).unwrap();
+        assert_eq!(titles(&app), ["c"]);
+
+        dispatch_counted(&mut app, Action::Undo, Some(2)).unwrap();
+        assert_eq!(titles(&app), ["a", "b", "c"]);
+
+        dispatch_counted(&mut app, Action::Redo, Some(2)).unwrap();
+        assert_eq!(titles(&app), ["c"]);
+        dispatch_counted(&mut app, Action::Undo, None).unwrap();
+        assert_eq!(titles(&app), ["b", "c"]);
+        assert_eq!(app.status_message.as_deref(), Some("Undid: Delete tasks"));
+    }
+
+    #[test]
+    fn count_is_ignored_outside_task_changes() {
+        let mut app = App::with_tasks("count-ignored", &[]);
+        for name in ["Work", "Home"] {
+            app.add_list(List::new(0, name.to_string())).unwrap();
+        }
+        app.state = AppState::ListOverview;
+        app.selected_task_index = 0;
+        app.config.confirm_delete = true;
+
+        dispatch_counted(&mut app, Action::DeleteSelected, Some(3)).unwrap();
+        assert_eq!(app.status_message.as_deref(), Some("Delete list 'My Tasks'? (y/n)"));
+        answer_confirmation(&mut app, true).unwrap();
+        assert_eq!(app.lists.len(), 2);
+
+        dispatch_counted(&mut app, Action::Select, Some(3)).unwrap();
+        assert_eq!(app.state, AppState::TaskList);
+
+        app.add_task(Task::new(0, "a".to_string(), app.lists[0].id)).unwrap();
+        app.delete_task(app.tasks[0].id).unwrap();
+        app.state = AppState::Trash;
+        app.selected_task_index = 0;
+        dispatch_counted(&mut app, Action::Purge, Some(3)).unwrap();
+        assert_eq!(app.status_message.as_deref(), Some("Purge permanently? (y/n)"));
+        answer_confirmation(&mut app, true).unwrap();
+        assert_eq!(app.get_trash_items().len(), 1);
+    }
+
+    #[test]
+    fn only_list_is_not_deleted() {
+        let mut app = App::with_tasks("only-list", &["a"]);
+        app.state = AppState::ListOverview;
+        app.selected_task_index = 0;
+        dispatch_counted(&mut app, Action::DeleteSelected, None).unwrap();
+        assert_eq!(app.lists.len(), 1);
+        assert_eq!(app.status_message.as_deref(), Some("Can't delete the only list"));
+    }
+}
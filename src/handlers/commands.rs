@@ -0,0 +1,345 @@
+use super::actions;
+use crate::action::Action;
+use crate::app::{App, AppState, ListFilter, TaskSort};
+use crate::models::List;
+use crate::theme::Theme;
+use crate::utils::{date_utils, export, fuzzy};
+use anyhow::{anyhow, bail, Result};
+use std::path::PathBuf;
+
+/// A command that can be typed on the command line. Every action can also
+/// be run by its keymap name, e.g. `:undo`.
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub usage: &'static str,
+    pub description: &'static str,
+}
+
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec { name: "newlist", usage: "newlist <name>", description: "Create a list" },
+    CommandSpec { name: "move", usage: "move <list>", description: "Move the selected tasks to a list" },
+    CommandSpec { name: "due", usage: "due [date]", description: "Set or clear the due date of the selected tasks" },
+    CommandSpec { name: "start", usage: "start [date]", description: "Defer the selected tasks until a date, or clear their start date" },
+    CommandSpec { name: "tag", usage: "tag <tag>", description: "Tag the selected tasks" },
+    CommandSpec { name: "sort", usage: "sort <manual|due|title|created>", description: "Change the order of tasks in lists" },
+    CommandSpec { name: "filter", usage: "filter <actionable|deferred|all>", description: "Show tasks that can be started, deferred tasks or all tasks in lists" },
+    CommandSpec { name: "columns", usage: "columns [status, ...]", description: "Set the board columns of the current list, or restore To Do, Doing, Done" },
+    CommandSpec { name: "export", usage: "export md [path]", description: "Export the current list, or every list, as Markdown" },
+    CommandSpec { name: "theme", usage: "theme [name]", description: "Switch to a theme, or to the next one" },
+    CommandSpec { name: "workspace", usage: "workspace <name>", description: "Switch to another workspace" },
+    CommandSpec { name: "quit", usage: "quit", description: "Quit" },
+];
+
+const EXPORT_FORMATS: &[&str] = &["md"];
+
+/// Run a command line. Problems with the command are shown as a status
+/// message rather than returned.
+pub fn run_command(app: &mut App, line: &str) -> Result<()> {
+    let line = line.trim();
+    if line.is_empty() {
+        return Ok(());
+    }
+    if app.command_history.last().map(String::as_str) != Some(line) {
+        app.command_history.push(line.to_string());
+    }
+
+    let (name, arg) = match line.split_once(' ') {
+        Some((name, arg)) => (name, arg.trim()),
+        None => (line, ""),
+    };
+
+    if let Err(err) = execute(app, name, arg) {
+        app.status_message = Some(format!("{:#}", err));
+    }
+    Ok(())
+}
+
+fn execute(app: &mut App, name: &str, arg: &str) -> Result<()> {
+    match name {
+        "newlist" => {
+            if arg.is_empty() {
+                bail!("Usage: newlist <name>");
+            }
+            app.add_list(List::new(0, arg.to_string()))?;
+            app.status_message = Some(format!("Created list '{}'", arg));
+        }
+        "move" => {
+            let task_ids = target_task_ids(app)?;
+            let list_id = find_list(app, arg)?;
+            app.move_tasks_to_list(&task_ids, list_id)?;
+            app.clear_selection();
+            actions::clamp_selection(app);
+        }
+        "due" => {
+            let task_ids = target_task_ids(app)?;
+            // No date clears the due date
+            let due_date = if arg.is_empty() {
+                None
+            } else {
+                Some(date_utils::parse_date_input(arg, app.config.week_start, app.config.date_order)?)
+            };
+            app.set_tasks_due_date(&task_ids, due_date)?;
+            app.clear_selection();
+        }
+        "start" => {
+            let task_ids = target_task_ids(app)?;
+            // No date clears the start date. Only the day counts, not the time.
+            let start_date = if arg.is_empty() {
+                None
+            } else {
+                Some(date_utils::parse_date_input(arg, app.config.week_start, app.config.date_order)?.at.date_naive())
+            };
+            app.set_tasks_start_date(&task_ids, start_date)?;
+            app.clear_selection();
+            actions::clamp_selection(app);
+        }
+        "tag" => {
+            let task_ids = target_task_ids(app)?;
+            if arg.is_empty() {
+                bail!("Usage: tag <tag>");
+            }
+            app.add_tag_to_tasks(&task_ids, arg)?;
+            app.clear_selection();
+        }
+        "sort" => {
+            let sort = TaskSort::from_name(arg).ok_or_else(|| anyhow!("Usage: sort <manual|due|title|created>"))?;
+            app.task_sort = sort;
+            app.clear_selection();
+            app.status_message = Some(format!("Sorted by {}", sort.name()));
+        }
+        "filter" => {
+            let filter = ListFilter::from_name(arg).ok_or_else(|| anyhow!("Usage: filter <actionable|deferred|all>"))?;
+            app.list_filter = filter;
+            app.clear_selection();
+            actions::clamp_selection(app);
+            app.status_message = Some(format!("Showing {} tasks", filter.name()));
+        }
+        "columns" => set_columns(app, arg)?,
+        "export" => export_tasks(app, arg)?,
+        "theme" if arg.is_empty() => cycle_theme(app),
+        "theme" => set_theme(app, arg)?,
+        "workspace" if arg.is_empty() => bail!("Usage: workspace <name>"),
+        "workspace" => switch_workspace(app, arg),
+        "q" | "quit" => {
+            app.should_quit = true;
+        }
+        _ => {
+            let action = Action::from_name(name).ok_or_else(|| anyhow!("Unknown command '{}'", name))?;
+            actions::dispatch_counted(app, action, None)?;
+        }
+    }
+    Ok(())
+}
+
+fn target_task_ids(app: &App) -> Result<Vec<u32>> {
+    if !matches!(app.state, AppState::TaskList | AppState::MyDay | AppState::Upcoming) {
+        bail!("Open a list, My Day or Upcoming first");
+    }
+    let task_ids = app.get_target_task_ids();
+    if task_ids.is_empty() {
+        bail!("No task selected");
+    }
+    Ok(task_ids)
+}
+
+fn find_list(app: &App, name: &str) -> Result<u32> {
+    if name.is_empty() {
+        bail!("Usage: move <list>");
+    }
+    app.find_list(name)
+}
+
+/// Give the current list's board new columns, the last of which holds
+/// completed tasks
+fn set_columns(app: &mut App, arg: &str) -> Result<()> {
+    let list_id = match app.get_current_list() {
+        Some(list) if app.state == AppState::TaskList => list.id,
+        _ => bail!("Open a list first"),
+    };
+    let statuses: Vec<String> = arg
+        .split(',')
+        .map(|status| status.trim().to_string())
+        .filter(|status| !status.is_empty())
+        .collect();
+    if statuses.len() == 1 {
+        bail!("A board needs at least two columns");
+    }
+    app.set_list_statuses(list_id, statuses)?;
+    app.board.column = 0;
+    app.selected_task_index = 0;
+    if let Some(list) = app.get_current_list() {
+        app.status_message = Some(format!("Columns: {}", list.statuses().join(", ")));
+    }
+    Ok(())
+}
+
+fn export_tasks(app: &mut App, arg: &str) -> Result<()> {
+    let (format, path) = match arg.split_once(' ') {
+        Some((format, path)) => (format, Some(path.trim())),
+        None => (arg, None),
+    };
+    if format != "md" {
+        bail!("Usage: export md [path]");
+    }
+
+    let lists: Vec<&List> = match app.get_current_list() {
+        Some(list) if app.state == AppState::TaskList => vec![list],
+        _ => app.lists.iter().collect(),
+    };
+    let tasks = app.tasks.iter().collect::<Vec<_>>();
+    let markdown = export::to_markdown(&lists, &tasks);
+
+    let path = match path {
+        Some(path) => PathBuf::from(path),
+        None => PathBuf::from(app.storage.data_dir()).join("export.md"),
+    };
+    std::fs::write(&path, markdown).map_err(|err| anyhow!("Failed to write {}: {}", path.display(), err))?;
+
+    app.status_message = Some(format!("Exported to {}", path.display()));
+    Ok(())
+}
+
+fn set_theme(app: &mut App, name: &str) -> Result<()> {
+    app.theme = Theme::load(name)?.for_color_mode(app.config.color_mode);
+    app.status_message = Some(format!("Theme: {}", name));
+    Ok(())
+}
+
+/// Switch to the theme after the current one, skipping theme files that
+/// fail to load
+pub fn cycle_theme(app: &mut App) {
+    let themes = Theme::available();
+    let current = themes.iter().position(|name| *name == app.theme.name).unwrap_or(themes.len() - 1);
+
+    let mut error = None;
+    for offset in 1..=themes.len() {
+        match set_theme(app, &themes[(current + offset) % themes.len()]) {
+            Ok(()) => break,
+            Err(err) => {
+                error.get_or_insert(err);
+            }
+        }
+    }
+    if let Some(err) = error {
+        app.status_message = Some(format!("{:#}", err));
+    }
+}
+
+/// Open another workspace, reporting a workspace that can't be opened
+pub fn switch_workspace(app: &mut App, name: &str) {
+    match app.switch_workspace(name) {
+        Ok(()) => app.status_message = Some(format!("Switched to workspace '{}'", name)),
+        Err(err) => app.status_message = Some(format!("{:#}", err)),
+    }
+}
+
+/// Tab completion for the command line. A single match is filled in;
+/// several matches are extended to their common prefix and listed.
+pub fn complete(app: &mut App) {
+    let input = app.input_buffer.clone();
+    let (prefix, word, candidates): (String, &str, Vec<String>) = match input.split_once(' ') {
+        None => {
+            let names = COMMANDS
+                .iter()
+                .map(|c| c.name.to_string())
+                .chain(Action::ALL.iter().map(|a| a.name().to_string()))
+                .collect();
+            (String::new(), input.as_str(), names)
+        }
+        Some((command, arg)) => {
+            let names = match command {
+                "move" => app.lists.iter().map(|l| l.name.clone()).collect(),
+                "sort" => TaskSort::ALL.iter().map(|s| s.name().to_string()).collect(),
+                "filter" => ListFilter::ALL.iter().map(|f| f.name().to_string()).collect(),
+                "export" => EXPORT_FORMATS.iter().map(|f| f.to_string()).collect(),
+                "theme" => Theme::available(),
+                "workspace" => app.config.workspace_names(),
+                _ => Vec::new(),
+            };
+            (format!("{} ", command), arg, names)
+        }
+    };
+
+    let word_lower = word.to_lowercase();
+    let matches: Vec<String> = candidates
+        .into_iter()
+        .filter(|c| c.to_lowercase().starts_with(&word_lower))
+        .collect();
+
+    match matches.as_slice() {
+        [] => {}
+        [only] => {
+            app.input_buffer = format!("{}{}", prefix, only);
+            if prefix.is_empty() {
+                app.input_buffer.push(' ');
+            }
+        }
+        _ => {
+            let common = common_prefix(&matches);
+            if common.len() > word.len() {
+                app.input_buffer = format!("{}{}", prefix, common);
+            }
+            app.status_message = Some(matches.join("  "));
+        }
+    }
+}
+
+fn common_prefix(words: &[String]) -> String {
+    let mut prefix = words[0].clone();
+    for word in &words[1..] {
+        while !word.to_lowercase().starts_with(&prefix.to_lowercase()) {
+            prefix.pop();
+        }
+    }
+    prefix
+}
+
+/// Step back through earlier command lines
+pub fn history_previous(app: &mut App) {
+    if app.command_history.is_empty() {
+        return;
+    }
+    let index = match app.command_history_index {
+        Some(index) => index.saturating_sub(1),
+        None => app.command_history.len() - 1,
+    };
+    app.command_history_index = Some(index);
+    app.input_buffer = app.command_history[index].clone();
+}
+
+/// Step forward through earlier command lines, ending on an empty line
+pub fn history_next(app: &mut App) {
+    let Some(index) = app.command_history_index else {
+        return;
+    };
+    if index + 1 < app.command_history.len() {
+        app.command_history_index = Some(index + 1);
+        app.input_buffer = app.command_history[index + 1].clone();
+    } else {
+        app.command_history_index = None;
+        app.input_buffer.clear();
+    }
+}
+
+/// Actions listed in the command palette, best matches for the typed query
+/// first
+pub fn palette_actions(app: &App) -> Vec<Action> {
+    let query = app.input_buffer.trim();
+    let mut scored: Vec<(i32, Action)> = Action::ALL
+        .iter()
+        .copied()
+        .filter(|action| !matches!(action, Action::Confirm | Action::Complete | Action::OpenPalette))
+        .filter_map(|action| {
+            if query.is_empty() {
+                return Some((0, action));
+            }
+            let score = fuzzy::fuzzy_score(query, action.label())
+                .into_iter()
+                .chain(fuzzy::fuzzy_score(query, action.name()))
+                .max()?;
+            Some((score, action))
+        })
+        .collect();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(_, action)| action).collect()
+}
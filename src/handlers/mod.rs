@@ -1,2 +1,4 @@
+pub mod actions;
+pub mod commands;
 pub mod input;
-
+pub mod mouse;
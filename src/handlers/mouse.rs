@@ -0,0 +1,321 @@
+use super::actions;
+use crate::action::Action;
+use crate::app::{App, AppState, SidebarItem};
+use crate::ui::screens::{board, completed_history, upcoming};
+use anyhow::Result;
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use std::time::{Duration, Instant};
+
+/// Longest gap between the clicks of a double click
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+/// Rows moved by one step of the scroll wheel
+const SCROLL_ROWS: usize = 3;
+/// Width of a task's checkbox and the space after it
+const CHECKBOX_WIDTH: u16 = 2;
+
+pub fn handle_mouse(app: &mut App, event: MouseEvent) -> Result<()> {
+    match event.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            // A click abandons any half typed key sequence or unanswered question
+            app.pending_keys.clear();
+            app.count = None;
+            app.pending_confirmation = None;
+            app.status_message = None;
+            let double_click = is_double_click(app, event);
+            click(app, event.column, event.row, double_click)
+        }
+        MouseEventKind::Drag(MouseButton::Left) => {
+            drag(app, event.column, event.row);
+            Ok(())
+        }
+        MouseEventKind::Up(MouseButton::Left) => drop_task(app, event.column),
+        MouseEventKind::ScrollDown => scroll(app, Action::MoveDown),
+        MouseEventKind::ScrollUp => scroll(app, Action::MoveUp),
+        _ => Ok(()),
+    }
+}
+
+/// Whether this click lands on the same cell as the previous one, soon
+/// enough after it. A third click starts a new double click.
+fn is_double_click(app: &mut App, event: MouseEvent) -> bool {
+    let now = Instant::now();
+    let double_click = app.mouse.last_click.is_some_and(|(at, column, row)| {
+        now.duration_since(at) <= DOUBLE_CLICK_INTERVAL && column == event.column && row == event.row
+    });
+    app.mouse.last_click = if double_click { None } else { Some((now, event.column, event.row)) };
+    double_click
+}
+
+fn click(app: &mut App, column: u16, row: u16, double_click: bool) -> Result<()> {
+    match app.state {
+        AppState::ListOverview | AppState::MoveTask => {
+            let Some(row) = app.view_state.sidebar.row_at(column, row) else {
+                return Ok(());
+            };
+            if row < app.get_sidebar_items().len() {
+                app.selected_task_index = row;
+                if double_click {
+                    actions::dispatch(app, Action::Select)?;
+                }
+            }
+        }
+        AppState::TaskList if actions::is_board(app) => {
+            let Some(board_column) = board::column_at(app, column) else {
+                return Ok(());
+            };
+            let view = &app.view_state.board[board_column];
+            let (row, column) = (view.row_at(column, row), view.column_at(column));
+            if board_column != app.board.column {
+                app.clear_selection();
+                app.board.column = board_column;
+                actions::clamp_selection(app);
+            }
+            if let Some(row) = row {
+                click_task(app, row, column, double_click)?;
+            }
+        }
+        AppState::TaskList | AppState::MyDay => {
+            let in_my_day = app.state == AppState::MyDay;
+            if let Some(row) = app.view_state.sidebar.row_at(column, row) {
+                click_sidebar(app, row)?;
+            } else if let Some(row) = app.view_state.suggestions.row_at(column, row).filter(|_| in_my_day) {
+                click_suggestion(app, row, double_click)?;
+            } else if let Some(row) = app.view_state.tasks.row_at(column, row) {
+                if in_my_day {
+                    actions::focus_suggestions(app, false);
+                }
+                click_task(app, row, app.view_state.tasks.column_at(column), double_click)?;
+            }
+        }
+        AppState::Timesheet => {
+            if let Some(row) = app.view_state.timesheet.row_at(column, row) {
+                if row < actions::row_count(app) {
+                    app.selected_task_index = row;
+                }
+            }
+        }
+        AppState::Trash => {
+            if let Some(row) = app.view_state.trash.row_at(column, row) {
+                if row < app.get_trash_items().len() {
+                    app.selected_task_index = row;
+                }
+            }
+        }
+        AppState::Workspaces => {
+            let Some(row) = app.view_state.workspaces.row_at(column, row) else {
+                return Ok(());
+            };
+            if row < app.workspace_summaries.len() {
+                app.selected_task_index = row;
+                if double_click {
+                    actions::dispatch(app, Action::Select)?;
+                }
+            }
+        }
+        AppState::Calendar => {
+            let Some(row) = app.view_state.calendar.row_at(column, row) else {
+                return Ok(());
+            };
+            if row < app.get_calendar_tasks(app.calendar.day).len() {
+                app.selected_task_index = row;
+                if double_click {
+                    actions::dispatch(app, Action::Select)?;
+                }
+            }
+        }
+        AppState::Upcoming => {
+            let task_index = app
+                .view_state
+                .upcoming
+                .row_at(column, row)
+                .and_then(|row| upcoming::task_index_at_row(app, row));
+            if let Some(index) = task_index {
+                app.selected_task_index = index;
+                if double_click {
+                    actions::dispatch(app, Action::Select)?;
+                }
+            }
+        }
+        AppState::CompletedHistory => {
+            let task_index = app
+                .view_state
+                .history
+                .row_at(column, row)
+                .and_then(|row| completed_history::task_index_at_row(app, row));
+            if let Some(index) = task_index {
+                app.selected_task_index = index;
+            }
+        }
+        AppState::CommandPalette => {
+            // Clicking an action runs it
+            if let Some(row) = app.view_state.palette.row_at(column, row) {
+                if row < super::commands::palette_actions(app).len() {
+                    app.palette_index = row;
+                    actions::dispatch(app, Action::Confirm)?;
+                }
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Open a list, or expand or collapse a group, from the sidebar of a task
+/// screen
+fn click_sidebar(app: &mut App, row: usize) -> Result<()> {
+    match app.get_sidebar_items().get(row) {
+        Some(SidebarItem::Group { name, .. }) => app.toggle_group_collapsed(name)?,
+        Some(SidebarItem::List(list_id)) => {
+            app.clear_selection();
+            app.set_current_list(*list_id);
+        }
+        None => {}
+    }
+    Ok(())
+}
+
+/// Select a suggestion in My Day. A double click adds it to My Day.
+fn click_suggestion(app: &mut App, row: usize, double_click: bool) -> Result<()> {
+    actions::focus_suggestions(app, true);
+    if row < actions::row_count(app) {
+        app.selected_task_index = row;
+        if double_click {
+            actions::dispatch(app, Action::Select)?;
+        }
+    }
+    Ok(())
+}
+
+/// Select a task, toggling it if the click was on its checkbox. A double
+/// click edits the task, and pressing on a task starts dragging it. Tasks
+/// on a board can always be dragged to another column.
+fn click_task(app: &mut App, row: usize, column: u16, double_click: bool) -> Result<()> {
+    let tasks = app.get_screen_tasks();
+    let Some(task) = app.get_task_at_display_index(&tasks, row) else {
+        return Ok(());
+    };
+    let task_id = task.id;
+    // Marked tasks are drawn with a marker in front of the checkbox
+    let checkbox_column = if app.has_selection() && app.get_target_task_ids().contains(&task_id) {
+        CHECKBOX_WIDTH
+    } else {
+        0
+    };
+
+    app.selected_task_index = row;
+    if (checkbox_column..checkbox_column + CHECKBOX_WIDTH).contains(&column) {
+        app.toggle_task_completion(task_id)?;
+        actions::clamp_selection(app);
+    } else if double_click {
+        actions::dispatch(app, Action::EditTask)?;
+    } else if actions::can_reorder(app) || actions::is_board(app) {
+        app.mouse.drag_task_id = Some(task_id);
+    }
+    Ok(())
+}
+
+/// Follow a dragged task with the cursor to show where it will be dropped.
+/// Tasks can only be dropped among tasks of the same section.
+fn drag(app: &mut App, column: u16, row: u16) {
+    let Some(task_id) = app.mouse.drag_task_id else {
+        return;
+    };
+    if actions::is_board(app) {
+        drag_on_board(app, task_id, column, row);
+        return;
+    }
+    // Only the row matters while dragging, so the pointer may leave the list sideways
+    let area = app.view_state.tasks.area;
+    let Some(row) = app.view_state.tasks.row_at(area.x + 1, row) else {
+        return;
+    };
+
+    let tasks = app.get_screen_tasks();
+    let dragged = tasks.iter().find(|t| t.id == task_id);
+    let target = app.get_task_at_display_index(&tasks, row);
+    if let (Some(dragged), Some(target)) = (dragged, target) {
+        if dragged.is_completed == target.is_completed {
+            let message = format!("Moving '{}'", dragged.title);
+            app.selected_task_index = row;
+            app.status_message = Some(message);
+        }
+    }
+}
+
+/// Show which column a task dragged on a board will land in. Within its
+/// own column it's reordered like in a list.
+fn drag_on_board(app: &mut App, task_id: u32, column: u16, row: u16) {
+    let Some(board_column) = board::column_at(app, column) else {
+        return;
+    };
+    let Some(title) = app.tasks.iter().find(|t| t.id == task_id).map(|t| t.title.clone()) else {
+        return;
+    };
+    if board_column == app.board.column {
+        let area = app.view_state.board[board_column].area;
+        let row = app.view_state.board[board_column].row_at(area.x + 1, row);
+        if let Some(row) = row.filter(|&row| row < actions::row_count(app) && actions::can_reorder(app)) {
+            app.selected_task_index = row;
+        }
+        app.status_message = Some(format!("Moving '{}'", title));
+    } else if let Some(status) = app.current_board().map(|list| list.statuses()[board_column].clone()) {
+        app.status_message = Some(format!("Moving '{}' to {}", title, status));
+    }
+}
+
+/// Drop a dragged task on the row under the cursor, or on a board, in the
+/// column under the cursor
+fn drop_task(app: &mut App, column: u16) -> Result<()> {
+    let Some(task_id) = app.mouse.drag_task_id.take() else {
+        return Ok(());
+    };
+    app.status_message = None;
+
+    if actions::is_board(app) {
+        match board::column_at(app, column) {
+            Some(board_column) if board_column != app.board.column => {
+                app.move_tasks_to_column(&[task_id], board_column)?;
+                app.board.column = board_column;
+                match actions::display_index_of(app, task_id) {
+                    Some(index) => app.selected_task_index = index,
+                    None => actions::clamp_selection(app),
+                }
+                return Ok(());
+            }
+            Some(_) if actions::can_reorder(app) => {}
+            _ => return Ok(()),
+        }
+    }
+
+    let tasks = app.get_screen_tasks();
+    let Some(target_id) = app.get_task_at_display_index(&tasks, app.selected_task_index).map(|t| t.id) else {
+        return Ok(());
+    };
+    if target_id != task_id {
+        actions::reorder_task(app, task_id, target_id)?;
+    }
+    Ok(())
+}
+
+/// Move the cursor with the scroll wheel, which scrolls the list along with it
+fn scroll(app: &mut App, action: Action) -> Result<()> {
+    let scrollable = matches!(
+        app.state,
+        AppState::ListOverview
+            | AppState::TaskList
+            | AppState::MyDay
+            | AppState::Upcoming
+            | AppState::MoveTask
+            | AppState::Trash
+            | AppState::CompletedHistory
+            | AppState::CommandPalette
+            | AppState::Workspaces
+            | AppState::Timesheet
+    );
+    if scrollable {
+        for _ in 0..SCROLL_ROWS {
+            actions::dispatch(app, action)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,232 @@
+use crate::models::{List, Task};
+
+/// A single reversible change. `before`/`after` are `None` when the item
+/// didn't exist on that side of the change, and `index` is its position in
+/// the owning vector so undo can put it back where it was. `trashed` tells
+/// whether the item lives in the trash rather than the live collection.
+#[derive(Debug, Clone)]
+pub enum Change {
+    Task {
+        trashed: bool,
+        index: usize,
+        before: Option<Task>,
+        after: Option<Task>,
+    },
+    List {
+        trashed: bool,
+        index: usize,
+        before: Option<List>,
+        after: Option<List>,
+    },
+    MyDayOrder {
+        before: Vec<u32>,
+        after: Vec<u32>,
+    },
+    /// Task ids in storage order, which is the manual order of each list
+    TaskOrder {
+        before: Vec<u32>,
+        after: Vec<u32>,
+    },
+}
+
+/// A user-level operation made up of one or more changes
+#[derive(Debug, Clone)]
+pub struct Command {
+    pub description: String,
+    pub changes: Vec<Change>,
+}
+
+/// The state a command is recorded against
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub tasks: Vec<Task>,
+    pub lists: Vec<List>,
+    pub trash_tasks: Vec<Task>,
+    pub trash_lists: Vec<List>,
+    pub my_day_task_order: Vec<u32>,
+}
+
+impl Command {
+    /// Build a command from the difference between two snapshots.
+    /// Returns `None` if nothing changed.
+    pub fn from_snapshots(description: &str, before: &Snapshot, after: &Snapshot) -> Option<Self> {
+        let mut changes = Vec::new();
+
+        for trashed in [false, true] {
+            let (old_tasks, new_tasks) = if trashed {
+                (&before.trash_tasks, &after.trash_tasks)
+            } else {
+                (&before.tasks, &after.tasks)
+            };
+            changes.extend(
+                diff(old_tasks, new_tasks, |t| t.id)
+                    .into_iter()
+                    .map(|(index, before, after)| Change::Task { trashed, index, before, after }),
+            );
+
+            let (old_lists, new_lists) = if trashed {
+                (&before.trash_lists, &after.trash_lists)
+            } else {
+                (&before.lists, &after.lists)
+            };
+            changes.extend(
+                diff(old_lists, new_lists, |l| l.id)
+                    .into_iter()
+                    .map(|(index, before, after)| Change::List { trashed, index, before, after }),
+            );
+        }
+
+        if before.my_day_task_order != after.my_day_task_order {
+            changes.push(Change::MyDayOrder {
+                before: before.my_day_task_order.clone(),
+                after: after.my_day_task_order.clone(),
+            });
+        }
+
+        // The same tasks in a different order, e.g. a task dragged within its list
+        let old_order: Vec<u32> = before.tasks.iter().map(|t| t.id).collect();
+        let new_order: Vec<u32> = after.tasks.iter().map(|t| t.id).collect();
+        if old_order != new_order && same_ids(&old_order, &new_order) {
+            changes.push(Change::TaskOrder { before: old_order, after: new_order });
+        }
+
+        if changes.is_empty() {
+            None
+        } else {
+            Some(Self { description: description.to_string(), changes })
+        }
+    }
+}
+
+/// Pair up items by id and return `(index, before, after)` for every item
+/// that was added, removed or modified
+fn diff<T: Clone + PartialEq>(
+    before: &[T],
+    after: &[T],
+    id: impl Fn(&T) -> u32,
+) -> Vec<(usize, Option<T>, Option<T>)> {
+    let mut changes = Vec::new();
+
+    for (index, old) in before.iter().enumerate() {
+        match after.iter().find(|item| id(item) == id(old)) {
+            Some(new) if new == old => {}
+            new => changes.push((index, Some(old.clone()), new.cloned())),
+        }
+    }
+    for (index, new) in after.iter().enumerate() {
+        if !before.iter().any(|item| id(item) == id(new)) {
+            changes.push((index, None, Some(new.clone())));
+        }
+    }
+
+    changes
+}
+
+fn same_ids(a: &[u32], b: &[u32]) -> bool {
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+    a.sort_unstable();
+    b.sort_unstable();
+    a == b
+}
+
+/// Undo and redo stacks. The history lives for the whole session.
+#[derive(Debug, Default)]
+pub struct History {
+    undo_stack: Vec<Command>,
+    redo_stack: Vec<Command>,
+}
+
+impl History {
+    /// Record a newly performed command, which invalidates anything redoable
+    pub fn push(&mut self, command: Command) {
+        self.undo_stack.push(command);
+        self.redo_stack.clear();
+    }
+
+    pub fn take_undo(&mut self) -> Option<Command> {
+        let command = self.undo_stack.pop()?;
+        self.redo_stack.push(command.clone());
+        Some(command)
+    }
+
+    pub fn take_redo(&mut self) -> Option<Command> {
+        let command = self.redo_stack.pop()?;
+        self.undo_stack.push(command.clone());
+        Some(command)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(tasks: &[Task]) -> Snapshot {
+        Snapshot {
+            tasks: tasks.to_vec(),
+            lists: Vec::new(),
+            trash_tasks: Vec::new(),
+            trash_lists: Vec::new(),
+            my_day_task_order: Vec::new(),
+        }
+    }
+
+    fn task(id: u32, title: &str) -> Task {
+        Task::new(id, title.to_string(), 1)
+    }
+
+    #[test]
+    fn no_changes_records_nothing() {
+        let tasks = [task(1, "a")];
+        assert!(Command::from_snapshots("Nothing", &snapshot(&tasks), &snapshot(&tasks)).is_none());
+    }
+
+    #[test]
+    fn records_added_removed_and_edited_tasks() {
+        let (a, b, c) = (task(1, "a"), task(2, "b"), task(3, "c"));
+        let mut edited = b.clone();
+        edited.title = "b2".to_string();
+
+        let command =
+            Command::from_snapshots("Edit", &snapshot(&[a.clone(), b.clone()]), &snapshot(&[edited, c])).unwrap();
+        let changed: Vec<(Option<u32>, Option<String>)> = command
+            .changes
+            .iter()
+            .map(|change| match change {
+                Change::Task { before, after, .. } => {
+                    (before.as_ref().map(|t| t.id), after.as_ref().map(|t| t.title.clone()))
+                }
+                other => panic!("unexpected change {:?}", other),
+            })
+            .collect();
+        assert_eq!(changed, [(Some(1), None), (Some(2), Some("b2".to_string())), (None, Some("c".to_string()))]);
+    }
+
+    #[test]
+    fn reordering_is_a_task_order_change() {
+        let (a, b) = (task(1, "a"), task(2, "b"));
+        let command = Command::from_snapshots("Reorder", &snapshot(&[a.clone(), b.clone()]), &snapshot(&[b, a])).unwrap();
+        assert!(matches!(
+            command.changes.as_slice(),
+            [Change::TaskOrder { before, after }] if before == &[1, 2] && after == &[2, 1]
+        ));
+    }
+
+    #[test]
+    fn new_command_clears_redo() {
+        let command = |description: &str| Command { description: description.to_string(), changes: Vec::new() };
+        let mut history = History::default();
+        history.push(command("first"));
+        history.push(command("second"));
+
+        assert_eq!(history.take_undo().map(|c| c.description).as_deref(), Some("second"));
+        assert_eq!(history.take_redo().map(|c| c.description).as_deref(), Some("second"));
+        assert_eq!(history.take_undo().map(|c| c.description).as_deref(), Some("second"));
+
+        history.push(command("third"));
+        assert!(history.take_redo().is_none());
+        assert_eq!(history.take_undo().map(|c| c.description).as_deref(), Some("third"));
+        assert_eq!(history.take_undo().map(|c| c.description).as_deref(), Some("first"));
+        assert!(history.take_undo().is_none());
+    }
+}
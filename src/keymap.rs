@@ -0,0 +1,834 @@
+use crate::app::{App, AppState};
+use crate::config;
+use crate::action::Action;
+use anyhow::{anyhow, bail, Context, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::HashMap;
+
+/// Keymap sections. The text entry screens share the `editor` section, the
+/// command line and palette share `prompt`, and keys that aren't bound in
+/// either are typed into the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Screen {
+    ListOverview,
+    TaskList,
+    Board,
+    MyDay,
+    Suggestions,
+    Upcoming,
+    MoveTask,
+    Trash,
+    CompletedHistory,
+    Workspaces,
+    Calendar,
+    Stats,
+    Timesheet,
+    Editor,
+    Prompt,
+}
+
+impl Screen {
+    pub const ALL: &'static [Screen] = &[
+        Screen::ListOverview,
+        Screen::TaskList,
+        Screen::Board,
+        Screen::MyDay,
+        Screen::Suggestions,
+        Screen::Upcoming,
+        Screen::MoveTask,
+        Screen::Trash,
+        Screen::CompletedHistory,
+        Screen::Workspaces,
+        Screen::Calendar,
+        Screen::Stats,
+        Screen::Timesheet,
+        Screen::Editor,
+        Screen::Prompt,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Screen::ListOverview => "list_overview",
+            Screen::TaskList => "task_list",
+            Screen::Board => "board",
+            Screen::MyDay => "my_day",
+            Screen::Suggestions => "my_day_suggestions",
+            Screen::Upcoming => "upcoming",
+            Screen::MoveTask => "move_task",
+            Screen::Trash => "trash",
+            Screen::CompletedHistory => "completed_history",
+            Screen::Workspaces => "workspaces",
+            Screen::Calendar => "calendar",
+            Screen::Stats => "stats",
+            Screen::Timesheet => "timesheet",
+            Screen::Editor => "editor",
+            Screen::Prompt => "prompt",
+        }
+    }
+
+    pub fn from_state(state: &AppState) -> Screen {
+        match state {
+            AppState::ListOverview => Screen::ListOverview,
+            AppState::TaskList => Screen::TaskList,
+            AppState::MyDay => Screen::MyDay,
+            AppState::Upcoming => Screen::Upcoming,
+            AppState::MoveTask => Screen::MoveTask,
+            AppState::Trash => Screen::Trash,
+            AppState::CompletedHistory => Screen::CompletedHistory,
+            AppState::Workspaces => Screen::Workspaces,
+            AppState::Calendar => Screen::Calendar,
+            AppState::Stats => Screen::Stats,
+            AppState::Timesheet => Screen::Timesheet,
+            AppState::TaskEditor
+            | AppState::ListEditor
+            | AppState::NoteEditor
+            | AppState::BulkEdit
+            | AppState::QuickAdd => {
+                Screen::Editor
+            }
+            AppState::CommandLine | AppState::CommandPalette => Screen::Prompt,
+        }
+    }
+
+    /// The keymap section in effect. Typing a history search uses the
+    /// editor bindings so that letters go into the query.
+    pub fn current(app: &App) -> Screen {
+        if app.state == AppState::CompletedHistory && app.history_filter.searching {
+            Screen::Editor
+        } else {
+            Screen::of(app, &app.state)
+        }
+    }
+
+    /// The keymap section of a screen. A list shown as a board and the
+    /// suggestions pane of My Day have bindings of their own.
+    pub fn of(app: &App, state: &AppState) -> Screen {
+        match state {
+            AppState::TaskList if app.current_board().is_some() => Screen::Board,
+            AppState::MyDay if app.my_day.suggestions_focused => Screen::Suggestions,
+            _ => Screen::from_state(state),
+        }
+    }
+
+    /// Default bindings, in the order they are listed in the footer help
+    fn default_bindings(&self) -> &'static [(Action, &'static [&'static str])] {
+        match self {
+            Screen::ListOverview => &[
+                (Action::MoveUp, &["up", "k"]),
+                (Action::MoveDown, &["down", "j"]),
+                (Action::GoTop, &["g g"]),
+                (Action::GoBottom, &["G"]),
+                (Action::HalfPageDown, &["ctrl+d"]),
+                (Action::HalfPageUp, &["ctrl+u"]),
+                (Action::PageDown, &["pagedown"]),
+                (Action::PageUp, &["pageup"]),
+                (Action::Select, &["enter"]),
+                (Action::ToggleGroup, &["space"]),
+                (Action::NewList, &["ctrl+n"]),
+                (Action::SetGroup, &["ctrl+g"]),
+                (Action::DeleteSelected, &["delete", "backspace"]),
+                (Action::JumpToMark, &["'"]),
+                (Action::OpenMyDay, &["ctrl+y"]),
+                (Action::OpenCalendar, &["ctrl+l"]),
+                (Action::OpenUpcoming, &["ctrl+a"]),
+                (Action::OpenHistory, &["ctrl+o"]),
+                (Action::OpenTrash, &["ctrl+b"]),
+                (Action::OpenStats, &["ctrl+s"]),
+                (Action::OpenTimesheet, &["ctrl+k"]),
+                (Action::OpenWorkspaces, &["ctrl+w"]),
+                (Action::Undo, &["u"]),
+                (Action::Redo, &["ctrl+r"]),
+                (Action::OpenCommandLine, &[":"]),
+                (Action::OpenPalette, &["ctrl+p"]),
+                (Action::Quit, &["ctrl+q"]),
+            ],
+            Screen::TaskList => &[
+                (Action::MoveUp, &["up", "k"]),
+                (Action::MoveDown, &["down", "j"]),
+                (Action::GoTop, &["g g"]),
+                (Action::GoBottom, &["G"]),
+                (Action::HalfPageDown, &["ctrl+d"]),
+                (Action::HalfPageUp, &["ctrl+u"]),
+                (Action::PageDown, &["pagedown"]),
+                (Action::PageUp, &["pageup"]),
+                (Action::Select, &["enter"]),
+                (Action::ToggleComplete, &["space"]),
+                (Action::ReorderUp, &["ctrl+up"]),
+                (Action::ReorderDown, &["ctrl+down"]),
+                (Action::NewTask, &["ctrl+n"]),
+                (Action::QuickAdd, &["a"]),
+                (Action::EditTask, &["ctrl+e"]),
+                (Action::OpenNotes, &["ctrl+v"]),
+                (Action::MoveTask, &["ctrl+t"]),
+                (Action::DeleteSelected, &["d d", "delete", "backspace"]),
+                (Action::ToggleMyDay, &["t"]),
+                (Action::ToggleTimer, &["T"]),
+                (Action::OpenMyDay, &["ctrl+y"]),
+                (Action::OpenCalendar, &["ctrl+l"]),
+                (Action::OpenUpcoming, &["ctrl+a"]),
+                (Action::OpenWorkspaces, &["ctrl+w"]),
+                (Action::ToggleVisual, &["v"]),
+                (Action::ToggleMark, &["x"]),
+                (Action::SetDueDate, &["D"]),
+                (Action::AddTag, &["#"]),
+                (Action::RepeatLast, &["."]),
+                (Action::SetMark, &["m"]),
+                (Action::JumpToMark, &["'"]),
+                (Action::ToggleAllGroups, &["z"]),
+                (Action::ToggleBoard, &["B"]),
+                (Action::Undo, &["u"]),
+                (Action::Redo, &["ctrl+r"]),
+                (Action::Back, &["esc"]),
+                (Action::OpenCommandLine, &[":"]),
+                (Action::OpenPalette, &["ctrl+p"]),
+                (Action::Quit, &["ctrl+q"]),
+            ],
+            Screen::Board => &[
+                (Action::MoveLeft, &["left", "h"]),
+                (Action::MoveRight, &["right", "l"]),
+                (Action::MoveUp, &["up", "k"]),
+                (Action::MoveDown, &["down", "j"]),
+                (Action::MoveTaskLeft, &["shift+left", "H"]),
+                (Action::MoveTaskRight, &["shift+right", "L"]),
+                (Action::GoTop, &["g g"]),
+                (Action::GoBottom, &["G"]),
+                (Action::HalfPageDown, &["ctrl+d"]),
+                (Action::HalfPageUp, &["ctrl+u"]),
+                (Action::PageDown, &["pagedown"]),
+                (Action::PageUp, &["pageup"]),
+                (Action::Select, &["enter"]),
+                (Action::ToggleComplete, &["space"]),
+                (Action::ReorderUp, &["ctrl+up"]),
+                (Action::ReorderDown, &["ctrl+down"]),
+                (Action::NewTask, &["ctrl+n"]),
+                (Action::QuickAdd, &["a"]),
+                (Action::OpenNotes, &["ctrl+v"]),
+                (Action::MoveTask, &["ctrl+t"]),
+                (Action::DeleteSelected, &["d d", "delete", "backspace"]),
+                (Action::ToggleMyDay, &["t"]),
+                (Action::ToggleTimer, &["T"]),
+                (Action::ToggleVisual, &["v"]),
+                (Action::ToggleMark, &["x"]),
+                (Action::SetDueDate, &["D"]),
+                (Action::AddTag, &["#"]),
+                (Action::RepeatLast, &["."]),
+                (Action::SetMark, &["m"]),
+                (Action::JumpToMark, &["'"]),
+                (Action::ToggleBoard, &["B"]),
+                (Action::OpenMyDay, &["ctrl+y"]),
+                (Action::OpenCalendar, &["ctrl+l"]),
+                (Action::OpenUpcoming, &["ctrl+a"]),
+                (Action::OpenWorkspaces, &["ctrl+w"]),
+                (Action::Undo, &["u"]),
+                (Action::Redo, &["ctrl+r"]),
+                (Action::Back, &["esc"]),
+                (Action::OpenCommandLine, &[":"]),
+                (Action::OpenPalette, &["ctrl+p"]),
+                (Action::Quit, &["ctrl+q"]),
+            ],
+            Screen::MyDay => &[
+                (Action::MoveUp, &["up", "k"]),
+                (Action::MoveDown, &["down", "j"]),
+                (Action::GoTop, &["g g"]),
+                (Action::GoBottom, &["G"]),
+                (Action::HalfPageDown, &["ctrl+d"]),
+                (Action::HalfPageUp, &["ctrl+u"]),
+                (Action::PageDown, &["pagedown"]),
+                (Action::PageUp, &["pageup"]),
+                (Action::ToggleComplete, &["space"]),
+                (Action::ReorderUp, &["ctrl+up"]),
+                (Action::ReorderDown, &["ctrl+down"]),
+                (Action::QuickAdd, &["a"]),
+                (Action::OpenNotes, &["ctrl+v"]),
+                (Action::MoveTask, &["ctrl+t"]),
+                (Action::ToggleMyDay, &["t"]),
+                (Action::ToggleTimer, &["T"]),
+                (Action::DeleteSelected, &["d d", "delete", "backspace"]),
+                (Action::ToggleVisual, &["v"]),
+                (Action::ToggleMark, &["x"]),
+                (Action::SetDueDate, &["D"]),
+                (Action::AddTag, &["#"]),
+                (Action::RepeatLast, &["."]),
+                (Action::SetMark, &["m"]),
+                (Action::JumpToMark, &["'"]),
+                (Action::ToggleAllGroups, &["z"]),
+                (Action::ToggleSuggestions, &["tab"]),
+                (Action::OpenCalendar, &["ctrl+l"]),
+                (Action::OpenUpcoming, &["ctrl+a"]),
+                (Action::OpenWorkspaces, &["ctrl+w"]),
+                (Action::Undo, &["u"]),
+                (Action::Redo, &["ctrl+r"]),
+                (Action::Back, &["esc"]),
+                (Action::OpenCommandLine, &[":"]),
+                (Action::OpenPalette, &["ctrl+p"]),
+                (Action::Quit, &["ctrl+q"]),
+            ],
+            Screen::Suggestions => &[
+                (Action::MoveUp, &["up", "k"]),
+                (Action::MoveDown, &["down", "j"]),
+                (Action::GoTop, &["g g"]),
+                (Action::GoBottom, &["G"]),
+                (Action::HalfPageDown, &["ctrl+d"]),
+                (Action::HalfPageUp, &["ctrl+u"]),
+                (Action::PageDown, &["pagedown"]),
+                (Action::PageUp, &["pageup"]),
+                (Action::Select, &["enter"]),
+                (Action::ToggleMyDay, &["t", "+"]),
+                (Action::ToggleComplete, &["space"]),
+                (Action::ToggleTimer, &["T"]),
+                (Action::ToggleVisual, &["v"]),
+                (Action::ToggleMark, &["x"]),
+                (Action::SetDueDate, &["D"]),
+                (Action::RepeatLast, &["."]),
+                (Action::ToggleSuggestions, &["tab"]),
+                (Action::Undo, &["u"]),
+                (Action::Redo, &["ctrl+r"]),
+                (Action::Back, &["esc"]),
+                (Action::OpenCommandLine, &[":"]),
+                (Action::OpenPalette, &["ctrl+p"]),
+                (Action::Quit, &["ctrl+q"]),
+            ],
+            Screen::Upcoming => &[
+                (Action::MoveUp, &["up", "k"]),
+                (Action::MoveDown, &["down", "j"]),
+                (Action::GoTop, &["g g"]),
+                (Action::GoBottom, &["G"]),
+                (Action::HalfPageDown, &["ctrl+d"]),
+                (Action::HalfPageUp, &["ctrl+u"]),
+                (Action::PageDown, &["pagedown"]),
+                (Action::PageUp, &["pageup"]),
+                (Action::Select, &["enter"]),
+                (Action::ToggleComplete, &["space"]),
+                (Action::QuickAdd, &["a"]),
+                (Action::OpenNotes, &["ctrl+v"]),
+                (Action::MoveTask, &["ctrl+t"]),
+                (Action::ToggleMyDay, &["t"]),
+                (Action::ToggleTimer, &["T"]),
+                (Action::DeleteSelected, &["d d", "delete", "backspace"]),
+                (Action::ToggleVisual, &["v"]),
+                (Action::ToggleMark, &["x"]),
+                (Action::SetDueDate, &["D"]),
+                (Action::AddTag, &["#"]),
+                (Action::RepeatLast, &["."]),
+                (Action::SetMark, &["m"]),
+                (Action::JumpToMark, &["'"]),
+                (Action::OpenMyDay, &["ctrl+y"]),
+                (Action::OpenCalendar, &["ctrl+l"]),
+                (Action::Undo, &["u"]),
+                (Action::Redo, &["ctrl+r"]),
+                (Action::Back, &["esc"]),
+                (Action::OpenCommandLine, &[":"]),
+                (Action::OpenPalette, &["ctrl+p"]),
+                (Action::Quit, &["ctrl+q"]),
+            ],
+            Screen::MoveTask => &[
+                (Action::MoveUp, &["up", "k"]),
+                (Action::MoveDown, &["down", "j"]),
+                (Action::GoTop, &["g g"]),
+                (Action::GoBottom, &["G"]),
+                (Action::HalfPageDown, &["ctrl+d"]),
+                (Action::HalfPageUp, &["ctrl+u"]),
+                (Action::PageDown, &["pagedown"]),
+                (Action::PageUp, &["pageup"]),
+                (Action::Select, &["enter"]),
+                (Action::ToggleGroup, &["space"]),
+                (Action::Back, &["esc"]),
+                (Action::Quit, &["ctrl+q"]),
+            ],
+            Screen::Trash => &[
+                (Action::MoveUp, &["up", "k"]),
+                (Action::MoveDown, &["down", "j"]),
+                (Action::GoTop, &["g g"]),
+                (Action::GoBottom, &["G"]),
+                (Action::HalfPageDown, &["ctrl+d"]),
+                (Action::HalfPageUp, &["ctrl+u"]),
+                (Action::PageDown, &["pagedown"]),
+                (Action::PageUp, &["pageup"]),
+                (Action::Restore, &["enter", "r"]),
+                (Action::Purge, &["delete", "backspace"]),
+                (Action::EmptyTrash, &["ctrl+x"]),
+                (Action::Undo, &["u"]),
+                (Action::Redo, &["ctrl+r"]),
+                (Action::Back, &["esc"]),
+                (Action::OpenCommandLine, &[":"]),
+                (Action::OpenPalette, &["ctrl+p"]),
+                (Action::Quit, &["ctrl+q"]),
+            ],
+            Screen::CompletedHistory => &[
+                (Action::MoveUp, &["up", "k"]),
+                (Action::MoveDown, &["down", "j"]),
+                (Action::GoTop, &["g g"]),
+                (Action::GoBottom, &["G"]),
+                (Action::HalfPageDown, &["ctrl+d"]),
+                (Action::HalfPageUp, &["ctrl+u"]),
+                (Action::PageDown, &["pagedown"]),
+                (Action::PageUp, &["pageup"]),
+                (Action::ToggleComplete, &["space"]),
+                (Action::Search, &["/"]),
+                (Action::CycleListFilter, &["tab"]),
+                (Action::Back, &["esc"]),
+                (Action::OpenCommandLine, &[":"]),
+                (Action::OpenPalette, &["ctrl+p"]),
+                (Action::Quit, &["ctrl+q"]),
+            ],
+            Screen::Workspaces => &[
+                (Action::MoveUp, &["up", "k"]),
+                (Action::MoveDown, &["down", "j"]),
+                (Action::GoTop, &["g g"]),
+                (Action::GoBottom, &["G"]),
+                (Action::Select, &["enter"]),
+                (Action::Back, &["esc"]),
+                (Action::Quit, &["ctrl+q"]),
+            ],
+            Screen::Calendar => &[
+                (Action::MoveLeft, &["left", "h"]),
+                (Action::MoveRight, &["right", "l"]),
+                (Action::MoveUp, &["up", "k"]),
+                (Action::MoveDown, &["down", "j"]),
+                (Action::PageUp, &["pageup", "["]),
+                (Action::PageDown, &["pagedown", "]"]),
+                (Action::GoToday, &["t"]),
+                (Action::NextTask, &["tab"]),
+                (Action::Select, &["enter"]),
+                (Action::ToggleComplete, &["space"]),
+                (Action::Reschedule, &["m"]),
+                (Action::Undo, &["u"]),
+                (Action::Redo, &["ctrl+r"]),
+                (Action::Back, &["esc"]),
+                (Action::OpenCommandLine, &[":"]),
+                (Action::OpenPalette, &["ctrl+p"]),
+                (Action::Quit, &["ctrl+q"]),
+            ],
+            Screen::Stats => &[
+                (Action::OpenMyDay, &["ctrl+y"]),
+                (Action::OpenHistory, &["ctrl+o"]),
+                (Action::Back, &["esc"]),
+                (Action::OpenCommandLine, &[":"]),
+                (Action::OpenPalette, &["ctrl+p"]),
+                (Action::Quit, &["ctrl+q"]),
+            ],
+            Screen::Timesheet => &[
+                (Action::MoveUp, &["up", "k"]),
+                (Action::MoveDown, &["down", "j"]),
+                (Action::GoTop, &["g g"]),
+                (Action::GoBottom, &["G"]),
+                (Action::HalfPageDown, &["ctrl+d"]),
+                (Action::HalfPageUp, &["ctrl+u"]),
+                (Action::PageDown, &["pagedown"]),
+                (Action::PageUp, &["pageup"]),
+                (Action::ToggleTimer, &["T"]),
+                (Action::Back, &["esc"]),
+                (Action::OpenCommandLine, &[":"]),
+                (Action::OpenPalette, &["ctrl+p"]),
+                (Action::Quit, &["ctrl+q"]),
+            ],
+            Screen::Editor => &[
+                (Action::Confirm, &["enter"]),
+                (Action::Back, &["esc"]),
+                (Action::Quit, &["ctrl+q"]),
+            ],
+            Screen::Prompt => &[
+                (Action::Confirm, &["enter"]),
+                (Action::Complete, &["tab"]),
+                (Action::MoveUp, &["up"]),
+                (Action::MoveDown, &["down"]),
+                (Action::Back, &["esc"]),
+                (Action::Quit, &["ctrl+q"]),
+            ],
+        }
+    }
+}
+
+/// A single key press with its modifiers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    /// Parse a chord such as `ctrl+n`, `enter`, `G` or `#`
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut parts: Vec<&str> = input.split('+').collect();
+        // A trailing empty part means the key itself is '+'
+        let key = if input.ends_with("++") || input == "+" {
+            parts.truncate(parts.len().saturating_sub(2));
+            "+"
+        } else {
+            parts.pop().unwrap_or_default()
+        };
+
+        for modifier in parts {
+            match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => modifiers |= KeyModifiers::CONTROL,
+                "alt" => modifiers |= KeyModifiers::ALT,
+                "shift" => modifiers |= KeyModifiers::SHIFT,
+                other => bail!("Unknown modifier '{}' in '{}'", other, input),
+            }
+        }
+
+        let code = match key.to_lowercase().as_str() {
+            "enter" | "return" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "space" => KeyCode::Char(' '),
+            "tab" => KeyCode::Tab,
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            _ => {
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => bail!("Unknown key '{}' in '{}'", key, input),
+                }
+            }
+        };
+
+        Ok(Self::normalize(code, modifiers))
+    }
+
+    /// Most terminals send Ctrl+M, Ctrl+I and Ctrl+H as Enter, Tab and
+    /// Backspace, and shift is already part of a typed character, so
+    /// chords are compared in that form.
+    fn normalize(code: KeyCode, mut modifiers: KeyModifiers) -> Self {
+        let code = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::CONTROL) => match c.to_ascii_lowercase() {
+                'm' => {
+                    modifiers.remove(KeyModifiers::CONTROL);
+                    KeyCode::Enter
+                }
+                'i' => {
+                    modifiers.remove(KeyModifiers::CONTROL);
+                    KeyCode::Tab
+                }
+                'h' => {
+                    modifiers.remove(KeyModifiers::CONTROL);
+                    KeyCode::Backspace
+                }
+                _ => KeyCode::Char(c),
+            },
+            other => other,
+        };
+        let code = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::Char(c.to_ascii_uppercase()),
+            other => other,
+        };
+        if matches!(code, KeyCode::Char(_)) {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        Self { code, modifiers }
+    }
+
+    pub fn display(&self) -> String {
+        let key = match self.code {
+            KeyCode::Enter => "Enter".to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Tab => "Tab".to_string(),
+            KeyCode::Backspace => "Backspace".to_string(),
+            KeyCode::Delete => "Del".to_string(),
+            KeyCode::Up => "↑".to_string(),
+            KeyCode::Down => "↓".to_string(),
+            KeyCode::Left => "←".to_string(),
+            KeyCode::Right => "→".to_string(),
+            KeyCode::Home => "Home".to_string(),
+            KeyCode::End => "End".to_string(),
+            KeyCode::PageUp => "PgUp".to_string(),
+            KeyCode::PageDown => "PgDn".to_string(),
+            KeyCode::Char(c) if self.modifiers.is_empty() => c.to_string(),
+            KeyCode::Char(c) => c.to_ascii_uppercase().to_string(),
+            other => format!("{:?}", other),
+        };
+
+        let mut prefix = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            prefix.push_str("Ctrl+");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            prefix.push_str("Alt+");
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            prefix.push_str("Shift+");
+        }
+        format!("{}{}", prefix, key)
+    }
+}
+
+impl From<KeyEvent> for KeyChord {
+    fn from(key: KeyEvent) -> Self {
+        Self::normalize(key.code, key.modifiers)
+    }
+}
+
+/// One or more chords pressed in sequence, e.g. `g g`
+pub type KeySequence = Vec<KeyChord>;
+
+fn parse_sequence(input: &str) -> Result<KeySequence> {
+    let sequence = input
+        .split_whitespace()
+        .map(KeyChord::parse)
+        .collect::<Result<KeySequence>>()?;
+    if sequence.is_empty() {
+        bail!("Empty key binding");
+    }
+    Ok(sequence)
+}
+
+fn display_sequence(sequence: &KeySequence) -> String {
+    sequence.iter().map(|chord| chord.display()).collect::<Vec<_>>().join("")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeyResolution {
+    Action(Action),
+    /// The keys so far are the start of a longer binding
+    Pending,
+    Unbound,
+}
+
+pub struct Keymap {
+    bindings: HashMap<Screen, Vec<(Action, Vec<KeySequence>)>>,
+}
+
+impl Keymap {
+    pub fn default_keymap() -> Self {
+        let bindings = Screen::ALL
+            .iter()
+            .map(|screen| {
+                let screen_bindings = screen
+                    .default_bindings()
+                    .iter()
+                    .map(|(action, keys)| {
+                        let sequences = keys
+                            .iter()
+                            .map(|key| parse_sequence(key).expect("default key bindings are valid"))
+                            .collect();
+                        (*action, sequences)
+                    })
+                    .collect();
+                (*screen, screen_bindings)
+            })
+            .collect();
+        Self { bindings }
+    }
+
+    /// Load the defaults with any overrides from `keymap.toml` in the config
+    /// directory applied, and refuse to start if bindings conflict
+    pub fn load() -> Result<Self> {
+        let mut keymap = Self::default_keymap();
+
+        if let Some(path) = config::config_dir().map(|dir| dir.join("keymap.toml")) {
+            if path.exists() {
+                let contents = std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                keymap
+                    .apply_overrides(&contents)
+                    .with_context(|| format!("Invalid keymap file {}", path.display()))?;
+            }
+        }
+
+        let conflicts = keymap.find_conflicts();
+        if !conflicts.is_empty() {
+            return Err(anyhow!("Conflicting key bindings:\n  {}", conflicts.join("\n  ")));
+        }
+        Ok(keymap)
+    }
+
+    /// Each `[screen]` table maps action names to a list of key bindings,
+    /// replacing that action's default keys on that screen
+    fn apply_overrides(&mut self, contents: &str) -> Result<()> {
+        let overrides: HashMap<String, HashMap<String, Vec<String>>> = toml::from_str(contents)?;
+
+        for (screen_name, actions) in overrides {
+            let screen = Screen::ALL
+                .iter()
+                .copied()
+                .find(|screen| screen.name() == screen_name)
+                .ok_or_else(|| anyhow!("Unknown screen '{}'", screen_name))?;
+            let screen_bindings = self.bindings.entry(screen).or_default();
+
+            for (action_name, keys) in actions {
+                let action = Action::from_name(&action_name)
+                    .ok_or_else(|| anyhow!("Unknown action '{}' in [{}]", action_name, screen_name))?;
+                let sequences = keys
+                    .iter()
+                    .map(|key| parse_sequence(key))
+                    .collect::<Result<Vec<_>>>()
+                    .with_context(|| format!("Invalid binding for '{}' in [{}]", action_name, screen_name))?;
+
+                match screen_bindings.iter_mut().find(|(bound, _)| *bound == action) {
+                    Some((_, existing)) => *existing = sequences,
+                    None => screen_bindings.push((action, sequences)),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Bindings on the same screen that are identical, or where one is the
+    /// start of another and so could never be reached
+    pub fn find_conflicts(&self) -> Vec<String> {
+        let mut conflicts = Vec::new();
+
+        for screen in Screen::ALL {
+            let Some(screen_bindings) = self.bindings.get(screen) else {
+                continue;
+            };
+            let flattened: Vec<(Action, &KeySequence)> = screen_bindings
+                .iter()
+                .flat_map(|(action, sequences)| sequences.iter().map(move |sequence| (*action, sequence)))
+                .collect();
+
+            for (i, (first_action, first)) in flattened.iter().enumerate() {
+                for (second_action, second) in flattened.iter().skip(i + 1) {
+                    let shorter = first.len().min(second.len());
+                    if first[..shorter] == second[..shorter] {
+                        conflicts.push(format!(
+                            "[{}] '{}' ({}) conflicts with '{}' ({})",
+                            screen.name(),
+                            display_sequence(first),
+                            first_action.name(),
+                            display_sequence(second),
+                            second_action.name(),
+                        ));
+                    }
+                }
+            }
+        }
+
+        conflicts
+    }
+
+    /// Look up the keys pressed so far on a screen
+    pub fn resolve(&self, screen: Screen, keys: &[KeyChord]) -> KeyResolution {
+        let Some(screen_bindings) = self.bindings.get(&screen) else {
+            return KeyResolution::Unbound;
+        };
+
+        let mut pending = false;
+        for (action, sequences) in screen_bindings {
+            for sequence in sequences {
+                if sequence.as_slice() == keys {
+                    return KeyResolution::Action(*action);
+                }
+                if sequence.len() > keys.len() && sequence.starts_with(keys) {
+                    pending = true;
+                }
+            }
+        }
+
+        if pending {
+            KeyResolution::Pending
+        } else {
+            KeyResolution::Unbound
+        }
+    }
+
+    /// Keys bound to an action on a screen, e.g. `Ctrl+N` or `u/Ctrl+Z`
+    pub fn keys_for(&self, screen: Screen, action: Action) -> Option<String> {
+        let (_, sequences) = self.bindings.get(&screen)?.iter().find(|(bound, _)| *bound == action)?;
+        if sequences.is_empty() {
+            return None;
+        }
+        Some(sequences.iter().map(display_sequence).collect::<Vec<_>>().join("/"))
+    }
+
+    /// Footer help for a screen, one item per action with active bindings
+    pub fn help_items(&self, screen: Screen) -> Vec<String> {
+        let Some(screen_bindings) = self.bindings.get(&screen) else {
+            return Vec::new();
+        };
+
+        screen_bindings
+            .iter()
+            .filter(|(_, sequences)| !sequences.is_empty())
+            .map(|(action, sequences)| {
+                let keys: Vec<String> = sequences.iter().map(display_sequence).collect();
+                format!("[{}: {}]", keys.join("/"), action.label())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chord(code: KeyCode, modifiers: KeyModifiers) -> KeyChord {
+        KeyChord { code, modifiers }
+    }
+
+    #[test]
+    fn parses_named_keys_and_modifiers() {
+        assert_eq!(KeyChord::parse("enter").unwrap(), chord(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(KeyChord::parse("ctrl+n").unwrap(), chord(KeyCode::Char('n'), KeyModifiers::CONTROL));
+        assert_eq!(
+            KeyChord::parse("Ctrl+Alt+Up").unwrap(),
+            chord(KeyCode::Up, KeyModifiers::CONTROL | KeyModifiers::ALT)
+        );
+        assert_eq!(KeyChord::parse("space").unwrap(), chord(KeyCode::Char(' '), KeyModifiers::NONE));
+        assert_eq!(KeyChord::parse("#").unwrap(), chord(KeyCode::Char('#'), KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn plus_can_be_the_key() {
+        assert_eq!(KeyChord::parse("+").unwrap(), chord(KeyCode::Char('+'), KeyModifiers::NONE));
+        assert_eq!(KeyChord::parse("ctrl++").unwrap(), chord(KeyCode::Char('+'), KeyModifiers::CONTROL));
+    }
+
+    #[test]
+    fn shift_is_part_of_the_character() {
+        assert_eq!(KeyChord::parse("G").unwrap(), chord(KeyCode::Char('G'), KeyModifiers::NONE));
+        assert_eq!(KeyChord::parse("shift+g").unwrap(), KeyChord::parse("G").unwrap());
+        assert_eq!(KeyChord::parse("shift+tab").unwrap(), chord(KeyCode::Tab, KeyModifiers::SHIFT));
+    }
+
+    #[test]
+    fn ctrl_letters_sent_as_other_keys() {
+        assert_eq!(KeyChord::parse("ctrl+m").unwrap(), KeyChord::parse("enter").unwrap());
+        assert_eq!(KeyChord::parse("ctrl+i").unwrap(), KeyChord::parse("tab").unwrap());
+        assert_eq!(KeyChord::parse("ctrl+h").unwrap(), KeyChord::parse("backspace").unwrap());
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_modifiers() {
+        assert!(KeyChord::parse("hyper+x").is_err());
+        assert!(KeyChord::parse("ctrl+nope").is_err());
+        assert!(KeyChord::parse("").is_err());
+    }
+
+    #[test]
+    fn default_keymap_has_no_conflicts() {
+        assert_eq!(Keymap::default_keymap().find_conflicts(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn finds_same_and_prefix_conflicts() {
+        let mut keymap = Keymap::default_keymap();
+        keymap.apply_overrides("[task_list]\nundo = [\"ctrl+n\"]\n").unwrap();
+        let conflicts = keymap.find_conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert!(conflicts[0].contains("new_task") && conflicts[0].contains("undo"), "{}", conflicts[0]);
+
+        let mut keymap = Keymap::default_keymap();
+        keymap.apply_overrides("[task_list]\nundo = [\"g\"]\n").unwrap();
+        let conflicts = keymap.find_conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert!(conflicts[0].starts_with("[task_list] 'gg' (go_top)"), "{}", conflicts[0]);
+    }
+
+    #[test]
+    fn same_key_on_different_screens_is_fine() {
+        let mut keymap = Keymap::default_keymap();
+        keymap.apply_overrides("[trash]\nundo = [\"ctrl+n\"]\n").unwrap();
+        assert!(keymap.find_conflicts().is_empty());
+    }
+}
@@ -1,4 +1,5 @@
 use anyhow::Result;
+use clap::Parser;
 use crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event},
     execute,
@@ -9,18 +10,38 @@ use ratatui::{
     Terminal,
 };
 use std::io;
+use std::process::ExitCode;
+use std::time::Duration;
 
+mod action;
 mod app;
+mod cli;
+mod config;
 mod handlers;
+mod history;
+mod keymap;
 mod models;
+mod subcommands;
 mod theme;
 mod ui;
 mod utils;
+mod workspace;
 
 use app::App;
+use cli::Cli;
+use config::Config;
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() -> Result<ExitCode> {
+    // Load data and config before touching the terminal so errors print normally
+    let cli = Cli::parse();
+    let mut config = Config::load(cli.config.as_deref())?;
+    cli.apply(&mut config);
+    if let Some(command) = cli.command {
+        return Ok(subcommands::run(command, config, cli.json));
+    }
+    let mut app = App::new(config)?;
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -28,8 +49,7 @@ async fn main() -> Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Create app and run
-    let mut app = App::new()?;
+    // Run app
     let res = run_app(&mut terminal, &mut app).await;
 
     // Restore terminal
@@ -45,20 +65,33 @@ async fn main() -> Result<()> {
         eprintln!("{err:?}");
     }
 
-    Ok(())
+    Ok(ExitCode::SUCCESS)
 }
 
+/// How often the screen is redrawn without input, so countdowns to timed
+/// tasks, the overdue color and a running timer keep up with the clock
+const REDRAW_INTERVAL: Duration = Duration::from_secs(1);
+
 async fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()> {
     loop {
+        // A session left open overnight still archives and starts a new day
+        if app.tidy_up()? {
+            handlers::actions::clamp_selection(app);
+        }
         terminal.draw(|f| ui::draw(f, app))?;
 
         if app.should_quit {
             break;
         }
 
-        if let Event::Key(key) = event::read()? {
-            handlers::input::handle_input(app, key)?;
+        if !event::poll(REDRAW_INTERVAL)? {
+            continue;
+        }
+        match event::read()? {
+            Event::Key(key) => handlers::input::handle_input(app, key)?,
+            Event::Mouse(mouse) => handlers::mouse::handle_mouse(app, mouse)?,
+            _ => {}
         }
     }
     Ok(())
-}
\ No newline at end of file
+}
@@ -1,13 +1,26 @@
+use crate::models::Task;
 use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Board columns of lists that don't set their own
+pub const DEFAULT_STATUSES: &[&str] = &["To Do", "Doing", "Done"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct List {
     pub id: u32,
     pub name: String,
     pub color: Option<String>,
+    pub group: Option<String>,
+    /// Columns of the list's board, the last of which holds the completed
+    /// tasks. Empty for the default columns.
+    #[serde(default)]
+    pub statuses: Vec<String>,
+    /// Whether the list is shown as a board rather than a list
+    #[serde(default)]
+    pub board: bool,
     pub created_at: DateTime<Local>,
     pub updated_at: DateTime<Local>,
+    pub deleted_at: Option<DateTime<Local>>,
 }
 
 impl List {
@@ -17,11 +30,52 @@ impl List {
             id,
             name,
             color: None,
+            group: None,
+            statuses: Vec::new(),
+            board: false,
             created_at: now,
             updated_at: now,
+            deleted_at: None,
+        }
+    }
+
+    pub fn set_group(&mut self, group: Option<String>) {
+        self.group = group;
+        self.updated_at = Local::now();
+    }
+
+    pub fn set_statuses(&mut self, statuses: Vec<String>) {
+        self.statuses = statuses;
+        self.updated_at = Local::now();
+    }
+
+    pub fn set_board(&mut self, board: bool) {
+        self.board = board;
+        self.updated_at = Local::now();
+    }
+
+    /// The board's column names
+    pub fn statuses(&self) -> Vec<String> {
+        if self.statuses.is_empty() {
+            DEFAULT_STATUSES.iter().map(|status| status.to_string()).collect()
+        } else {
+            self.statuses.clone()
         }
     }
 
+    /// The board column a task of this list is in. Completed tasks are in
+    /// the last column, whatever their status.
+    pub fn column_of(&self, task: &Task) -> usize {
+        let statuses = self.statuses();
+        let done = statuses.len() - 1;
+        if task.is_completed {
+            return done;
+        }
+        task.status
+            .as_ref()
+            .and_then(|status| statuses[..done].iter().position(|s| s.eq_ignore_ascii_case(status)))
+            .unwrap_or(0)
+    }
 }
 
 impl Default for List {
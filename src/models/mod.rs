@@ -1,7 +1,9 @@
 pub mod task;
 pub mod list;
 pub mod storage;
+pub mod time_entry;
 
 pub use task::*;
 pub use list::*;
 pub use storage::*;
+pub use time_entry::*;
@@ -1,8 +1,9 @@
-use crate::models::{List, Task};
+use crate::models::{List, Task, TimeEntry};
 use anyhow::Result;
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Local, NaiveDate, Timelike};
 use csv::{Reader, Writer};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fs::{File, OpenOptions};
 use std::path::Path;
 
@@ -21,6 +22,20 @@ struct TaskRecord {
     notes: Option<String>,
     created_at: String,
     updated_at: String,
+    #[serde(default)]
+    deleted_at: Option<String>,
+    #[serde(default)]
+    tags: Option<String>,
+    #[serde(default)]
+    priority: Option<String>,
+    #[serde(default)]
+    has_due_time: Option<bool>,
+    #[serde(default)]
+    start_date: Option<String>,
+    #[serde(default)]
+    status: Option<String>,
+    #[serde(default)]
+    my_day_date: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,11 +45,38 @@ struct ListRecord {
     color: Option<String>,
     created_at: String,
     updated_at: String,
+    #[serde(default)]
+    group: Option<String>,
+    #[serde(default)]
+    deleted_at: Option<String>,
+    #[serde(default)]
+    statuses: Option<String>,
+    #[serde(default)]
+    board: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GroupRecord {
+    name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TimeEntryRecord {
+    id: u32,
+    task_id: u32,
+    started_at: String,
+    stopped_at: Option<String>,
 }
 
 pub struct Storage {
+    data_dir: String,
     tasks_file: String,
     lists_file: String,
+    trash_tasks_file: String,
+    trash_lists_file: String,
+    archive_file: String,
+    time_entries_file: String,
+    collapsed_groups_file: String,
     next_task_id: u32,
     next_list_id: u32,
 }
@@ -42,13 +84,23 @@ pub struct Storage {
 impl Storage {
     pub fn new(data_dir: &str) -> Self {
         Self {
+            data_dir: data_dir.to_string(),
             tasks_file: format!("{}/tasks.csv", data_dir),
             lists_file: format!("{}/lists.csv", data_dir),
+            trash_tasks_file: format!("{}/trash_tasks.csv", data_dir),
+            trash_lists_file: format!("{}/trash_lists.csv", data_dir),
+            archive_file: format!("{}/archive.csv", data_dir),
+            time_entries_file: format!("{}/time_entries.csv", data_dir),
+            collapsed_groups_file: format!("{}/collapsed_groups.csv", data_dir),
             next_task_id: 1,
             next_list_id: 1,
         }
     }
 
+    pub fn data_dir(&self) -> &str {
+        &self.data_dir
+    }
+
     pub fn load_all(&mut self) -> Result<(Vec<Task>, Vec<List>)> {
         // Ensure data directory exists
         if let Some(parent) = Path::new(&self.tasks_file).parent() {
@@ -57,20 +109,91 @@ impl Storage {
 
         let tasks = self.load_tasks()?;
         let lists = self.load_lists()?;
-
-        // Update next IDs
-        self.next_task_id = tasks.iter().map(|t| t.id).max().unwrap_or(0) + 1;
-        self.next_list_id = lists.iter().map(|l| l.id).max().unwrap_or(0) + 1;
+        let (trash_tasks, trash_lists) = self.load_trash()?;
+        let archived_tasks = self.load_archive()?;
+        let time_entries = self.load_time_entries()?;
+
+        // Update next IDs, counting trashed and archived items so ids never
+        // collide, and the tasks of time entries, which outlive purged tasks
+        self.next_task_id = tasks
+            .iter()
+            .chain(&trash_tasks)
+            .chain(&archived_tasks)
+            .map(|t| t.id)
+            .chain(time_entries.iter().map(|entry| entry.task_id))
+            .max()
+            .unwrap_or(0)
+            + 1;
+        self.next_list_id = lists.iter().chain(&trash_lists).map(|l| l.id).max().unwrap_or(0) + 1;
 
         Ok((tasks, lists))
     }
 
     pub fn load_tasks(&self) -> Result<Vec<Task>> {
-        if !Path::new(&self.tasks_file).exists() {
+        self.load_tasks_from(&self.tasks_file)
+    }
+
+    pub fn load_lists(&self) -> Result<Vec<List>> {
+        self.load_lists_from(&self.lists_file)
+    }
+
+    pub fn load_trash(&self) -> Result<(Vec<Task>, Vec<List>)> {
+        Ok((
+            self.load_tasks_from(&self.trash_tasks_file)?,
+            self.load_lists_from(&self.trash_lists_file)?,
+        ))
+    }
+
+    pub fn load_archive(&self) -> Result<Vec<Task>> {
+        self.load_tasks_from(&self.archive_file)
+    }
+
+    pub fn load_time_entries(&self) -> Result<Vec<TimeEntry>> {
+        if !Path::new(&self.time_entries_file).exists() {
+            return Ok(vec![]);
+        }
+
+        let file = File::open(&self.time_entries_file)?;
+        let mut reader = Reader::from_reader(file);
+        let mut entries = Vec::new();
+
+        for result in reader.deserialize() {
+            let record: TimeEntryRecord = result?;
+            entries.push(TimeEntry {
+                id: record.id,
+                task_id: record.task_id,
+                started_at: DateTime::parse_from_rfc3339(&record.started_at)?.with_timezone(&Local),
+                stopped_at: match &record.stopped_at {
+                    Some(stopped_at) => Some(DateTime::parse_from_rfc3339(stopped_at)?.with_timezone(&Local)),
+                    None => None,
+                },
+            });
+        }
+
+        Ok(entries)
+    }
+
+    pub fn load_collapsed_groups(&self) -> Result<HashSet<String>> {
+        if !Path::new(&self.collapsed_groups_file).exists() {
+            return Ok(HashSet::new());
+        }
+
+        let file = File::open(&self.collapsed_groups_file)?;
+        let mut reader = Reader::from_reader(file);
+        let mut groups = HashSet::new();
+        for result in reader.deserialize() {
+            let record: GroupRecord = result?;
+            groups.insert(record.name);
+        }
+        Ok(groups)
+    }
+
+    fn load_tasks_from(&self, path: &str) -> Result<Vec<Task>> {
+        if !Path::new(path).exists() {
             return Ok(vec![]);
         }
 
-        let file = File::open(&self.tasks_file)?;
+        let file = File::open(path)?;
         let mut reader = Reader::from_reader(file);
         let mut tasks = Vec::new();
 
@@ -83,12 +206,12 @@ impl Storage {
         Ok(tasks)
     }
 
-    pub fn load_lists(&self) -> Result<Vec<List>> {
-        if !Path::new(&self.lists_file).exists() {
+    fn load_lists_from(&self, path: &str) -> Result<Vec<List>> {
+        if !Path::new(path).exists() {
             return Ok(vec![]);
         }
 
-        let file = File::open(&self.lists_file)?;
+        let file = File::open(path)?;
         let mut reader = Reader::from_reader(file);
         let mut lists = Vec::new();
 
@@ -102,11 +225,69 @@ impl Storage {
     }
 
     pub fn save_tasks(&self, tasks: &[Task]) -> Result<()> {
+        self.save_tasks_to(&self.tasks_file, tasks)
+    }
+
+    pub fn save_lists(&self, lists: &[List]) -> Result<()> {
+        self.save_lists_to(&self.lists_file, lists)
+    }
+
+    pub fn save_trash(&self, tasks: &[Task], lists: &[List]) -> Result<()> {
+        self.save_tasks_to(&self.trash_tasks_file, tasks)?;
+        self.save_lists_to(&self.trash_lists_file, lists)
+    }
+
+    pub fn save_archive(&self, tasks: &[Task]) -> Result<()> {
+        self.save_tasks_to(&self.archive_file, tasks)
+    }
+
+    pub fn save_collapsed_groups(&self, groups: &HashSet<String>) -> Result<()> {
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&self.collapsed_groups_file)?;
+
+        let mut writer = Writer::from_writer(file);
+
+        let mut names: Vec<&String> = groups.iter().collect();
+        names.sort();
+        for name in names {
+            writer.serialize(GroupRecord { name: name.clone() })?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn save_time_entries(&self, entries: &[TimeEntry]) -> Result<()> {
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&self.time_entries_file)?;
+
+        let mut writer = Writer::from_writer(file);
+
+        for entry in entries {
+            writer.serialize(TimeEntryRecord {
+                id: entry.id,
+                task_id: entry.task_id,
+                started_at: entry.started_at.to_rfc3339(),
+                stopped_at: entry.stopped_at.map(|d| d.to_rfc3339()),
+            })?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn save_tasks_to(&self, path: &str, tasks: &[Task]) -> Result<()> {
         let file = OpenOptions::new()
             .write(true)
             .create(true)
             .truncate(true)
-            .open(&self.tasks_file)?;
+            .open(path)?;
 
         let mut writer = Writer::from_writer(file);
 
@@ -119,12 +300,12 @@ impl Storage {
         Ok(())
     }
 
-    pub fn save_lists(&self, lists: &[List]) -> Result<()> {
+    fn save_lists_to(&self, path: &str, lists: &[List]) -> Result<()> {
         let file = OpenOptions::new()
             .write(true)
             .create(true)
             .truncate(true)
-            .open(&self.lists_file)?;
+            .open(path)?;
 
         let mut writer = Writer::from_writer(file);
 
@@ -156,6 +337,24 @@ impl Storage {
             None
         };
 
+        // Files from before due times were kept apart had every date-only due
+        // date at 23:59:59, so any other time was typed in
+        let has_due_time = record
+            .has_due_time
+            .unwrap_or_else(|| due_date.is_some_and(|d| (d.hour(), d.minute(), d.second()) != (23, 59, 59)));
+
+        let start_date = if let Some(date_str) = record.start_date {
+            Some(NaiveDate::parse_from_str(&date_str, "%Y-%m-%d")?)
+        } else {
+            None
+        };
+
+        let my_day_date = if let Some(date_str) = &record.my_day_date {
+            Some(NaiveDate::parse_from_str(date_str, "%Y-%m-%d")?)
+        } else {
+            None
+        };
+
         let reminder_date = if let Some(date_str) = record.reminder_date {
             Some(DateTime::parse_from_rfc3339(&date_str)?.with_timezone(&Local))
         } else {
@@ -175,6 +374,17 @@ impl Storage {
             None
         };
 
+        let priority = if let Some(priority_str) = record.priority {
+            Some(match priority_str.as_str() {
+                "Low" => crate::models::Priority::Low,
+                "Medium" => crate::models::Priority::Medium,
+                "High" => crate::models::Priority::High,
+                _ => return Err(anyhow::anyhow!("Invalid priority: {}", priority_str)),
+            })
+        } else {
+            None
+        };
+
         let created_at = DateTime::parse_from_rfc3339(&record.created_at)?.with_timezone(&Local);
         let updated_at = DateTime::parse_from_rfc3339(&record.updated_at)?.with_timezone(&Local);
         let completed_at = if let Some(completed_at_str) = &record.completed_at {
@@ -183,33 +393,61 @@ impl Storage {
             None
         };
 
+        let deleted_at = if let Some(deleted_at_str) = &record.deleted_at {
+            Some(DateTime::parse_from_rfc3339(deleted_at_str)?.with_timezone(&Local))
+        } else {
+            None
+        };
+
         Ok(Task {
             id: record.id,
             title: record.title,
             description: record.description,
             list_id: record.list_id,
             due_date,
+            has_due_time,
+            start_date,
             reminder_date,
             recurring_frequency,
             is_completed: record.is_completed,
             completed_at,
             is_in_my_day: record.is_in_my_day,
             notes: record.notes,
+            tags: record
+                .tags
+                .map(|tags| tags.split(',').map(|tag| tag.trim().to_string()).filter(|tag| !tag.is_empty()).collect())
+                .unwrap_or_default(),
+            priority,
+            status: record.status,
+            my_day_date,
             created_at,
             updated_at,
+            deleted_at,
         })
     }
 
     fn list_from_record(&self, record: ListRecord) -> Result<List> {
         let created_at = DateTime::parse_from_rfc3339(&record.created_at)?.with_timezone(&Local);
         let updated_at = DateTime::parse_from_rfc3339(&record.updated_at)?.with_timezone(&Local);
+        let deleted_at = if let Some(deleted_at_str) = &record.deleted_at {
+            Some(DateTime::parse_from_rfc3339(deleted_at_str)?.with_timezone(&Local))
+        } else {
+            None
+        };
 
         Ok(List {
             id: record.id,
             name: record.name,
             color: record.color,
+            group: record.group,
+            statuses: record
+                .statuses
+                .map(|statuses| statuses.split(',').map(|status| status.trim().to_string()).filter(|status| !status.is_empty()).collect())
+                .unwrap_or_default(),
+            board: record.board.unwrap_or(false),
             created_at,
             updated_at,
+            deleted_at,
         })
     }
 
@@ -234,6 +472,17 @@ impl Storage {
             notes: task.notes.clone(),
             created_at: task.created_at.to_rfc3339(),
             updated_at: task.updated_at.to_rfc3339(),
+            deleted_at: task.deleted_at.map(|d| d.to_rfc3339()),
+            tags: Some(task.tags.join(",")).filter(|tags| !tags.is_empty()),
+            priority: task.priority.map(|p| match p {
+                crate::models::Priority::Low => "Low".to_string(),
+                crate::models::Priority::Medium => "Medium".to_string(),
+                crate::models::Priority::High => "High".to_string(),
+            }),
+            has_due_time: task.due_date.map(|_| task.has_due_time),
+            start_date: task.start_date.map(|d| d.format("%Y-%m-%d").to_string()),
+            status: task.status.clone(),
+            my_day_date: task.my_day_date.map(|d| d.format("%Y-%m-%d").to_string()),
         }
     }
 
@@ -244,6 +493,10 @@ impl Storage {
             color: list.color.clone(),
             created_at: list.created_at.to_rfc3339(),
             updated_at: list.updated_at.to_rfc3339(),
+            group: list.group.clone(),
+            deleted_at: list.deleted_at.map(|d| d.to_rfc3339()),
+            statuses: Some(list.statuses.join(",")).filter(|statuses| !statuses.is_empty()),
+            board: Some(list.board).filter(|board| *board),
         }
     }
 }
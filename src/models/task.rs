@@ -1,24 +1,41 @@
-use chrono::{DateTime, Local};
+use crate::utils::date_utils::DueDate;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Weekday};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Task {
     pub id: u32,
     pub title: String,
     pub description: Option<String>,
     pub list_id: u32,
     pub due_date: Option<DateTime<Local>>,
+    /// Whether the due date has a time of day, rather than being all day
+    #[serde(default)]
+    pub has_due_time: bool,
+    /// Day the task can be started on. Until then it's deferred.
+    #[serde(default)]
+    pub start_date: Option<NaiveDate>,
     pub reminder_date: Option<DateTime<Local>>,
     pub recurring_frequency: Option<RecurringFrequency>,
     pub is_completed: bool,
     pub completed_at: Option<DateTime<Local>>,
     pub is_in_my_day: bool,
+    /// Day the task was last added to My Day
+    #[serde(default)]
+    pub my_day_date: Option<NaiveDate>,
     pub notes: Option<String>,
+    pub tags: Vec<String>,
+    pub priority: Option<Priority>,
+    /// Board column of an open task. Unknown or missing statuses put it in
+    /// the first column.
+    #[serde(default)]
+    pub status: Option<String>,
     pub created_at: DateTime<Local>,
     pub updated_at: DateTime<Local>,
+    pub deleted_at: Option<DateTime<Local>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum RecurringFrequency {
     Daily,
     Weekdays,
@@ -27,6 +44,76 @@ pub enum RecurringFrequency {
     Yearly,
 }
 
+impl RecurringFrequency {
+    /// Name shown next to tasks that repeat
+    pub fn label(&self) -> &'static str {
+        match self {
+            RecurringFrequency::Daily => "Daily",
+            RecurringFrequency::Weekdays => "Weekdays",
+            RecurringFrequency::Weekly => "Weekly",
+            RecurringFrequency::Monthly => "Monthly",
+            RecurringFrequency::Yearly => "Yearly",
+        }
+    }
+
+    /// Due date of the instance that follows one due at `due_date`
+    pub fn next_due_date(&self, due_date: DateTime<Local>) -> DateTime<Local> {
+        match self {
+            RecurringFrequency::Daily => due_date + Duration::days(1),
+            RecurringFrequency::Weekdays => {
+                // Find the next weekday (Monday-Friday)
+                let mut next = due_date + Duration::days(1);
+                while matches!(next.weekday(), Weekday::Sat | Weekday::Sun) {
+                    next += Duration::days(1);
+                }
+                next
+            }
+            RecurringFrequency::Weekly => due_date + Duration::weeks(1),
+            // Approximate month and year
+            RecurringFrequency::Monthly => due_date + Duration::days(30),
+            RecurringFrequency::Yearly => due_date + Duration::days(365),
+        }
+    }
+}
+
+/// How urgent a task is. Tasks without a priority are shown as usual.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Priority {
+    Low,
+    Medium,
+    High,
+}
+
+impl Priority {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Priority::Low => "low",
+            Priority::Medium => "medium",
+            Priority::High => "high",
+        }
+    }
+
+    /// Parse a priority name, a short form of it, or a number where 1 is
+    /// the highest
+    pub fn from_name(name: &str) -> Option<Priority> {
+        match name.to_lowercase().as_str() {
+            "high" | "h" | "1" => Some(Priority::High),
+            "medium" | "med" | "m" | "2" => Some(Priority::Medium),
+            "low" | "l" | "3" => Some(Priority::Low),
+            _ => None,
+        }
+    }
+
+    /// Shown before the title of a task with this priority
+    pub fn marker(&self) -> &'static str {
+        match self {
+            Priority::Low => "! ",
+            Priority::Medium => "!! ",
+            Priority::High => "!!! ",
+        }
+    }
+}
+
 impl Task {
     pub fn new(id: u32, title: String, list_id: u32) -> Self {
         let now = Local::now();
@@ -36,14 +123,21 @@ impl Task {
             description: None,
             list_id,
             due_date: None,
+            has_due_time: false,
+            start_date: None,
             reminder_date: None,
             recurring_frequency: None,
             is_completed: false,
             completed_at: None,
             is_in_my_day: false,
+            my_day_date: None,
             notes: None,
+            tags: Vec::new(),
+            priority: None,
+            status: None,
             created_at: now,
             updated_at: now,
+            deleted_at: None,
         }
     }
 
@@ -57,15 +151,26 @@ impl Task {
         }
     }
 
+    /// Timed tasks are overdue as soon as their time passes, all-day tasks
+    /// once their day is over
     pub fn is_overdue(&self) -> bool {
-        if let Some(due_date) = self.due_date {
-            let now = Local::now();
-            due_date < now && !self.is_completed
-        } else {
-            false
+        match self.due_date {
+            Some(_) if self.is_completed => false,
+            Some(due_date) if self.has_due_time => due_date < Local::now(),
+            Some(due_date) => due_date.date_naive() < Local::now().date_naive(),
+            None => false,
         }
     }
 
+    /// Whether the task can't be started yet
+    pub fn is_deferred(&self) -> bool {
+        !self.is_completed && self.start_date.is_some_and(|start| start > Local::now().date_naive())
+    }
+
+    pub fn starts_today(&self) -> bool {
+        self.start_date == Some(Local::now().date_naive())
+    }
+
     pub fn toggle_completion(&mut self) {
         self.is_completed = !self.is_completed;
         if self.is_completed {
@@ -78,19 +183,36 @@ impl Task {
 
     pub fn add_to_my_day(&mut self) {
         self.is_in_my_day = true;
+        self.my_day_date = Some(Local::now().date_naive());
         self.updated_at = Local::now();
     }
 
     pub fn remove_from_my_day(&mut self) {
         self.is_in_my_day = false;
+        self.my_day_date = None;
+        self.updated_at = Local::now();
+    }
+
+    pub fn due(&self) -> Option<DueDate> {
+        self.due_date.map(|at| DueDate { at, has_time: self.has_due_time })
+    }
+
+    pub fn set_due_date(&mut self, due: Option<DueDate>) {
+        self.due_date = due.map(|due| due.at);
+        self.has_due_time = due.is_some_and(|due| due.has_time);
         self.updated_at = Local::now();
     }
 
-    pub fn set_due_date(&mut self, due_date: Option<DateTime<Local>>) {
-        self.due_date = due_date;
+
+    pub fn set_start_date(&mut self, start_date: Option<NaiveDate>) {
+        self.start_date = start_date;
         self.updated_at = Local::now();
     }
 
+    pub fn set_status(&mut self, status: Option<String>) {
+        self.status = status;
+        self.updated_at = Local::now();
+    }
 
     pub fn set_recurring_frequency(&mut self, frequency: Option<RecurringFrequency>) {
         self.recurring_frequency = frequency;
@@ -101,26 +223,19 @@ impl Task {
         self.notes = notes;
         self.updated_at = Local::now();
     }
-}
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TaskFilter {
-    pub list_id: Option<u32>,
-    pub show_completed: bool,
-    pub show_my_day_only: bool,
-    pub due_today_only: bool,
-    pub overdue_only: bool,
-}
-
-impl Default for TaskFilter {
-    fn default() -> Self {
-        Self {
-            list_id: None,
-            show_completed: true,
-            show_my_day_only: false,
-            due_today_only: false,
-            overdue_only: false,
+    pub fn add_tag(&mut self, tag: &str) {
+        let tag = Self::tag_name(tag);
+        if !tag.is_empty() && !self.tags.contains(&tag) {
+            self.tags.push(tag);
+            self.updated_at = Local::now();
         }
     }
+
+    /// A tag as it is stored: lowercase, without a leading `#`, and without
+    /// commas, which separate tags in the task file
+    pub fn tag_name(tag: &str) -> String {
+        tag.trim().trim_start_matches('#').replace(',', "").to_lowercase()
+    }
 }
 
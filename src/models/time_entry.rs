@@ -0,0 +1,36 @@
+use chrono::{DateTime, Duration, Local};
+use serde::{Deserialize, Serialize};
+
+/// Time spent on a task, from starting its timer to stopping it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimeEntry {
+    pub id: u32,
+    pub task_id: u32,
+    pub started_at: DateTime<Local>,
+    /// `None` while the timer is running
+    pub stopped_at: Option<DateTime<Local>>,
+}
+
+impl TimeEntry {
+    pub fn new(id: u32, task_id: u32, started_at: DateTime<Local>) -> Self {
+        Self {
+            id,
+            task_id,
+            started_at,
+            stopped_at: None,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.stopped_at.is_none()
+    }
+
+    /// When the entry ends, which is `now` for a running timer
+    pub fn end(&self, now: DateTime<Local>) -> DateTime<Local> {
+        self.stopped_at.unwrap_or(now)
+    }
+
+    pub fn duration(&self, now: DateTime<Local>) -> Duration {
+        (self.end(now) - self.started_at).max(Duration::zero())
+    }
+}
@@ -0,0 +1,287 @@
+use crate::app::{App, ListFilter};
+use crate::cli::Command;
+use crate::config::Config;
+use crate::models::{List, Task, TimeEntry};
+use crate::utils::date_utils::{self, DueDate};
+use crate::utils::{export, timesheet};
+use anyhow::{anyhow, Result};
+use chrono::{Local, NaiveDate};
+use std::fmt;
+use std::process::ExitCode;
+
+/// An argument that can't be used, such as a date that doesn't parse or an
+/// unknown list. These exit with status 2, like clap's own usage errors.
+#[derive(Debug)]
+struct UsageError(String);
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for UsageError {}
+
+fn usage_error(message: impl Into<String>) -> anyhow::Error {
+    UsageError(message.into()).into()
+}
+
+/// Run a subcommand against the tasks of the configured workspace. Exits
+/// with 0 on success, 2 for bad arguments and 1 for anything else, such as
+/// an unknown task id or a task file that can't be written.
+pub fn run(command: Command, config: Config, json: bool) -> ExitCode {
+    match App::without_interface(config).and_then(|mut app| execute(&mut app, command, json)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {:#}", err);
+            if err.downcast_ref::<UsageError>().is_some() {
+                ExitCode::from(2)
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+    }
+}
+
+fn execute(app: &mut App, command: Command, json: bool) -> Result<()> {
+    match command {
+        Command::Add { title, list, due, start, note, myday } => {
+            if title.trim().is_empty() {
+                return Err(usage_error("The title can't be empty"));
+            }
+            let list_id = match list {
+                Some(name) => find_list(app, &name)?,
+                None => app.default_list_id(),
+            };
+            let mut task = Task::new(0, title.trim().to_string(), list_id);
+            task.set_due_date(due.map(|due| parse_date(app, &due)).transpose()?);
+            task.set_start_date(start.map(|start| parse_date(app, &start)).transpose()?.map(|start| start.at.date_naive()));
+            task.notes = note.filter(|note| !note.is_empty());
+            app.add_task(task)?;
+
+            // The app gives the task its id, so take it back from there
+            let task_id = app.tasks.last().map(|t| t.id).unwrap_or_default();
+            if myday {
+                app.add_task_to_my_day(task_id)?;
+            }
+            print_changed(app, &[task_id], "Added", json)
+        }
+        Command::Ls { filter, list, all, deferred } => {
+            let list_id = list.map(|name| find_list(app, &name)).transpose()?;
+            let list_filter = if deferred { ListFilter::Deferred } else { ListFilter::Actionable };
+            let filter = filter.unwrap_or_default().to_lowercase();
+
+            // Tasks appear in the order of their lists, open ones first
+            let mut tasks: Vec<&Task> = app
+                .tasks
+                .iter()
+                .filter(|t| all || !t.is_completed)
+                .filter(|t| list_id.is_none_or(|id| t.list_id == id))
+                .filter(|t| list_filter.shows(t, app.config.deferred_tasks))
+                .filter(|t| matches_filter(t, &filter))
+                .collect();
+            tasks.sort_by_key(|t| (app.lists.iter().position(|l| l.id == t.list_id), t.is_completed));
+            print_tasks(app, &tasks, json)
+        }
+        Command::Done { ids } => {
+            for &id in &ids {
+                find_task(app, id)?;
+            }
+            // Completing twice would reopen the task, so only open ones are toggled
+            let open_ids: Vec<u32> = ids
+                .iter()
+                .copied()
+                .filter(|&id| app.tasks.iter().any(|t| t.id == id && !t.is_completed))
+                .collect();
+            if !open_ids.is_empty() {
+                app.complete_tasks(&open_ids)?;
+            }
+            print_changed(app, &ids, "Completed", json)
+        }
+        Command::Edit { id, title, due, start, note, tag } => {
+            let mut task = find_task(app, id)?.clone();
+            if title.is_none() && due.is_none() && start.is_none() && note.is_none() && tag.is_none() {
+                return Err(usage_error("Nothing to change, give at least one of --title, --due, --start, --note or --tag"));
+            }
+            if let Some(title) = title {
+                if title.trim().is_empty() {
+                    return Err(usage_error("The title can't be empty"));
+                }
+                task.title = title.trim().to_string();
+            }
+            if let Some(due) = due {
+                let due_date = if due.eq_ignore_ascii_case("none") { None } else { Some(parse_date(app, &due)?) };
+                task.set_due_date(due_date);
+            }
+            if let Some(start) = start {
+                let start_date = if start.eq_ignore_ascii_case("none") { None } else { Some(parse_date(app, &start)?.at.date_naive()) };
+                task.set_start_date(start_date);
+            }
+            if let Some(note) = note {
+                task.set_notes(Some(note).filter(|note| !note.is_empty()));
+            }
+            if let Some(tag) = tag {
+                task.add_tag(&tag);
+            }
+            task.updated_at = Local::now();
+            app.update_task(task)?;
+            print_changed(app, &[id], "Updated", json)
+        }
+        Command::Mv { id, list } => {
+            find_task(app, id)?;
+            let list_id = find_list(app, &list)?;
+            app.move_task_to_list(id, list_id)?;
+            print_changed(app, &[id], "Moved", json)
+        }
+        Command::Myday { ids, remove } => {
+            if ids.is_empty() {
+                let tasks: Vec<&Task> = app.get_my_day_tasks().into_iter().filter(|t| !t.is_completed).collect();
+                return print_tasks(app, &tasks, json);
+            }
+            for &id in &ids {
+                find_task(app, id)?;
+            }
+            if remove {
+                app.remove_tasks_from_my_day(&ids)?;
+                print_changed(app, &ids, "Removed from My Day", json)
+            } else {
+                app.run_batch("Add to My Day", |app| {
+                    for &id in &ids {
+                        app.add_task_to_my_day(id)?;
+                    }
+                    Ok(())
+                })?;
+                print_changed(app, &ids, "Added to My Day", json)
+            }
+        }
+        Command::Start { id } => {
+            find_task(app, id)?;
+            app.start_timer(id)?;
+            print_changed(app, &[id], "Timing", json)
+        }
+        Command::Stop => {
+            let entry = app.stop_timer()?.ok_or_else(|| anyhow!("No timer is running"))?;
+            let verb = format!("Stopped after {}", timesheet::duration_label(entry.duration(Local::now())));
+            print_changed(app, &[entry.task_id], &verb, json)
+        }
+        Command::Time { since, list, csv } => {
+            let since = since.map(|since| parse_date(app, &since)).transpose()?.map(|since| since.at.date_naive());
+            let list_id = list.map(|name| find_list(app, &name)).transpose()?;
+            let entries: Vec<&TimeEntry> = app
+                .time_entries
+                .iter()
+                .filter(|entry| since.is_none_or(|since| entry.end(Local::now()).date_naive() >= since))
+                .filter(|entry| list_id.is_none_or(|id| app.find_any_task(entry.task_id).is_some_and(|t| t.list_id == id)))
+                .collect();
+            print_time(app, &entries, since, csv, json)
+        }
+    }
+}
+
+fn find_task(app: &App, id: u32) -> Result<&Task> {
+    app.tasks.iter().find(|t| t.id == id).ok_or_else(|| anyhow!("No task with id {}", id))
+}
+
+fn find_list(app: &App, name: &str) -> Result<u32> {
+    app.find_list(name).map_err(|err| usage_error(format!("{:#}", err)))
+}
+
+fn parse_date(app: &App, input: &str) -> Result<DueDate> {
+    date_utils::parse_date_input(input, app.config.week_start, app.config.date_order)
+        .map_err(|err| usage_error(format!("{:#}", err)))
+}
+
+/// Whether a task matches an `ls` filter: a `#tag`, or text in its title or
+/// notes. The filter is already lowercase.
+fn matches_filter(task: &Task, filter: &str) -> bool {
+    if let Some(tag) = filter.strip_prefix('#') {
+        return task.tags.iter().any(|t| t == tag);
+    }
+    task.title.to_lowercase().contains(filter)
+        || task.notes.as_ref().is_some_and(|notes| notes.to_lowercase().contains(filter))
+}
+
+/// Print tasks that were just changed, each after a word saying what happened
+fn print_changed(app: &App, ids: &[u32], verb: &str, json: bool) -> Result<()> {
+    let tasks: Vec<&Task> = ids.iter().filter_map(|id| app.tasks.iter().find(|t| t.id == *id)).collect();
+    if json {
+        return print_tasks(app, &tasks, json);
+    }
+    for task in tasks {
+        println!("{}: {}", verb, task_line(app, task));
+    }
+    Ok(())
+}
+
+/// Print tasks as a JSON array, or one per line
+fn print_tasks(app: &App, tasks: &[&Task], json: bool) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(tasks)?);
+    } else {
+        for task in tasks {
+            println!("{}", task_line(app, task));
+        }
+    }
+    Ok(())
+}
+
+/// Print time entries as CSV or JSON, or as a timesheet by day, list and
+/// task. Days before `since` are left out of the timesheet.
+fn print_time(app: &App, entries: &[&TimeEntry], since: Option<NaiveDate>, csv: bool, json: bool) -> Result<()> {
+    let now = Local::now();
+    if csv {
+        let tasks: Vec<&Task> = app.tasks.iter().chain(&app.archived_tasks).chain(&app.trash_tasks).collect();
+        let lists: Vec<&List> = app.lists.iter().chain(&app.trash_lists).collect();
+        print!("{}", export::time_entries_to_csv(entries, &tasks, &lists, now)?);
+        return Ok(());
+    }
+    if json {
+        println!("{}", serde_json::to_string_pretty(entries)?);
+        return Ok(());
+    }
+
+    let entries: Vec<TimeEntry> = entries.iter().map(|entry| (*entry).clone()).collect();
+    let list_of = |task_id| app.find_any_task(task_id).map(|task| task.list_id);
+    for day in timesheet::by_day(&entries, list_of, now).iter().filter(|day| since.is_none_or(|since| day.date >= since)) {
+        println!("{}  {}", day.date.format(&app.config.date_format), timesheet::duration_label(day.total));
+        for list in &day.lists {
+            let name = list.list_id.and_then(|id| app.lists.iter().chain(&app.trash_lists).find(|l| l.id == id)).map(|l| l.name.as_str());
+            println!("  {}  {}", name.unwrap_or("Deleted tasks"), timesheet::duration_label(list.total));
+            for &(task_id, duration) in &list.tasks {
+                let title = app.find_any_task(task_id).map(|t| t.title.as_str()).unwrap_or("Deleted task");
+                println!("    {:>4} {}  {}", task_id, title, timesheet::duration_label(duration));
+            }
+        }
+    }
+    if let Some(entry) = app.running_timer() {
+        let title = app.find_any_task(entry.task_id).map(|t| t.title.as_str()).unwrap_or("");
+        println!("Running: {} ({})", title, timesheet::clock_label(entry.duration(now)));
+    }
+    Ok(())
+}
+
+/// A task on one line: id, checkbox, priority, title, list, dates and tags
+fn task_line(app: &App, task: &Task) -> String {
+    let checkbox = if task.is_completed { "[x]" } else { "[ ]" };
+    let priority = task.priority.map(|p| p.marker()).unwrap_or("");
+    let mut line = format!("{:>4} {} {}{}", task.id, checkbox, priority, task.title);
+
+    let mut details: Vec<String> = app.lists.iter().filter(|l| l.id == task.list_id).map(|l| l.name.clone()).collect();
+    if let Some(due) = task.due() {
+        let overdue = if task.is_overdue() { ", overdue" } else { "" };
+        let time = if due.has_time { due.at.format(" %H:%M").to_string() } else { String::new() };
+        details.push(format!("due {}{}{}", due.at.format(&app.config.date_format), time, overdue));
+    }
+    if let Some(start) = task.start_date.filter(|_| task.is_deferred()) {
+        details.push(format!("starts {}", start.format(&app.config.date_format)));
+    }
+    if task.is_in_my_day {
+        details.push("My Day".to_string());
+    }
+    line.push_str(&format!("  ({})", details.join(", ")));
+
+    for tag in &task.tags {
+        line.push_str(&format!(" #{}", tag));
+    }
+    line
+}
@@ -1,24 +1,363 @@
+use crate::config;
+use anyhow::{anyhow, Context, Result};
 use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::str::FromStr;
 
-/// Bluloco theme colors
-pub struct BlulocoTheme;
-
-impl BlulocoTheme {
+/// Colors the UI is drawn with, by what they are used for
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub name: String,
     // Background colors
-    pub const SURFACE: Color = Color::Rgb(40, 40, 40);           // Slightly lighter surface
-    
+    pub surface: Color,
+
     // Text colors
-    pub const TEXT_PRIMARY: Color = Color::Rgb(248, 248, 242);   // Primary text (white-ish)
-    pub const TEXT_SECONDARY: Color = Color::Rgb(189, 189, 189); // Secondary text (gray)
-    pub const TEXT_MUTED: Color = Color::Rgb(117, 113, 94);      // Muted text (brown-gray)
-    
+    pub text: Color,
+    pub text_secondary: Color,
+    pub text_muted: Color,
+
     // Accent colors
-    pub const ACCENT_BLUE: Color = Color::Rgb(102, 153, 204);    // Blue accent
-    
+    pub accent: Color,
+
     // Status colors
-    pub const WARNING: Color = Color::Rgb(230, 219, 116);        // Yellow for warning
-    pub const ERROR: Color = Color::Rgb(255, 85, 85);            // Red for error
-    
+    pub warning: Color,
+    pub error: Color,
+
     // Selection and focus
-    pub const FOCUS: Color = Color::Rgb(102, 153, 204);          // Blue for focus
+    pub focus: Color,
+
+    /// Colors given to list names in the sidebar, in turn
+    pub list_colors: Vec<Color>,
+}
+
+/// How many colors the terminal can show. Theme colors are approximated with
+/// the nearest color the terminal has.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum ColorMode {
+    /// Decide from the `COLORTERM` and `TERM` environment variables
+    #[default]
+    #[serde(rename = "auto")]
+    Auto,
+    #[serde(rename = "truecolor")]
+    TrueColor,
+    #[serde(rename = "256")]
+    Ansi256,
+    #[serde(rename = "16")]
+    Ansi16,
+}
+
+impl ColorMode {
+    /// The mode to draw in, detecting it when set to auto
+    pub fn resolve(self) -> ColorMode {
+        if self != ColorMode::Auto {
+            return self;
+        }
+
+        let colorterm = std::env::var("COLORTERM").unwrap_or_default();
+        let term = std::env::var("TERM").unwrap_or_default();
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            ColorMode::TrueColor
+        } else if term.contains("256color") {
+            ColorMode::Ansi256
+        } else if term.is_empty() && cfg!(windows) {
+            // Windows consoles don't set TERM but do support full color
+            ColorMode::TrueColor
+        } else {
+            ColorMode::Ansi16
+        }
+    }
+}
+
+impl Theme {
+    pub const BUILT_IN: &'static [&'static str] = &["dark", "light", "high-contrast"];
+
+    /// Bluloco dark, the original look
+    pub fn dark() -> Self {
+        Self {
+            name: "dark".to_string(),
+            surface: Color::Rgb(40, 40, 40),
+            text: Color::Rgb(248, 248, 242),
+            text_secondary: Color::Rgb(189, 189, 189),
+            text_muted: Color::Rgb(117, 113, 94),
+            accent: Color::Rgb(102, 153, 204),
+            warning: Color::Rgb(230, 219, 116),
+            error: Color::Rgb(255, 85, 85),
+            focus: Color::Rgb(102, 153, 204),
+            list_colors: vec![
+                Color::Rgb(248, 248, 242),
+                Color::Rgb(152, 195, 121),
+                Color::Rgb(97, 175, 239),
+                Color::Rgb(198, 120, 221),
+                Color::Rgb(86, 182, 194),
+                Color::Rgb(229, 192, 123),
+            ],
+        }
+    }
+
+    /// Bluloco light, for terminals with a light background
+    pub fn light() -> Self {
+        Self {
+            name: "light".to_string(),
+            surface: Color::Rgb(249, 249, 249),
+            text: Color::Rgb(56, 58, 66),
+            text_secondary: Color::Rgb(90, 93, 103),
+            text_muted: Color::Rgb(146, 147, 153),
+            accent: Color::Rgb(39, 95, 228),
+            warning: Color::Rgb(168, 125, 0),
+            error: Color::Rgb(213, 39, 83),
+            focus: Color::Rgb(39, 95, 228),
+            list_colors: vec![
+                Color::Rgb(56, 58, 66),
+                Color::Rgb(35, 151, 77),
+                Color::Rgb(39, 95, 228),
+                Color::Rgb(125, 60, 200),
+                Color::Rgb(0, 132, 158),
+                Color::Rgb(196, 92, 0),
+            ],
+        }
+    }
+
+    /// Plain ANSI colors on black, readable in any terminal
+    pub fn high_contrast() -> Self {
+        Self {
+            name: "high-contrast".to_string(),
+            surface: Color::Black,
+            text: Color::White,
+            text_secondary: Color::White,
+            text_muted: Color::Gray,
+            accent: Color::LightCyan,
+            warning: Color::LightYellow,
+            error: Color::LightRed,
+            focus: Color::LightYellow,
+            list_colors: vec![Color::White, Color::LightGreen, Color::LightCyan, Color::LightMagenta],
+        }
+    }
+
+    pub fn built_in(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "high-contrast" => Some(Self::high_contrast()),
+            _ => None,
+        }
+    }
+
+    /// Load a theme by name, from `themes/<name>.toml` in the config
+    /// directory or else from the built-in themes
+    pub fn load(name: &str) -> Result<Self> {
+        if let Some(path) = theme_file_path(name).filter(|path| path.exists()) {
+            let contents = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            return Self::from_toml(name, &contents)
+                .with_context(|| format!("Invalid theme file {}", path.display()));
+        }
+        Self::built_in(name).ok_or_else(|| anyhow!("Unknown theme '{}'", name))
+    }
+
+    /// Names of the built-in themes followed by the user's theme files
+    pub fn available() -> Vec<String> {
+        let mut names: Vec<String> = Self::BUILT_IN.iter().map(|name| name.to_string()).collect();
+
+        let mut files: Vec<String> = themes_dir()
+            .and_then(|dir| std::fs::read_dir(dir).ok())
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                if path.extension()? != "toml" {
+                    return None;
+                }
+                Some(path.file_stem()?.to_string_lossy().to_string())
+            })
+            .filter(|name| !names.contains(name))
+            .collect();
+        files.sort();
+
+        names.extend(files);
+        names
+    }
+
+    /// Every slot is optional and taken from the `base` theme when missing,
+    /// so a theme file only has to list what it changes
+    fn from_toml(name: &str, contents: &str) -> Result<Self> {
+        let file: ThemeFile = toml::from_str(contents)?;
+        let base_name = file.base.as_deref().unwrap_or("dark");
+        let base = Self::built_in(base_name).ok_or_else(|| anyhow!("Unknown base theme '{}'", base_name))?;
+
+        let slot = |value: &Option<String>, slot: &str, default: Color| -> Result<Color> {
+            match value {
+                Some(value) => parse_color(value, slot),
+                None => Ok(default),
+            }
+        };
+        let list_colors = match &file.list_colors {
+            Some(colors) => colors
+                .iter()
+                .map(|color| parse_color(color, "list_colors"))
+                .collect::<Result<Vec<_>>>()?,
+            None => base.list_colors.clone(),
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            surface: slot(&file.surface, "surface", base.surface)?,
+            text: slot(&file.text, "text", base.text)?,
+            text_secondary: slot(&file.text_secondary, "text_secondary", base.text_secondary)?,
+            text_muted: slot(&file.text_muted, "text_muted", base.text_muted)?,
+            accent: slot(&file.accent, "accent", base.accent)?,
+            warning: slot(&file.warning, "warning", base.warning)?,
+            error: slot(&file.error, "error", base.error)?,
+            focus: slot(&file.focus, "focus", base.focus)?,
+            list_colors,
+        })
+    }
+
+    /// This theme with every color replaced by the nearest one the terminal
+    /// can show
+    pub fn for_color_mode(&self, mode: ColorMode) -> Self {
+        let convert = |color: Color| match mode.resolve() {
+            ColorMode::Ansi256 => to_ansi256(color),
+            ColorMode::Ansi16 => to_ansi16(color),
+            ColorMode::TrueColor | ColorMode::Auto => color,
+        };
+
+        Self {
+            name: self.name.clone(),
+            surface: convert(self.surface),
+            text: convert(self.text),
+            text_secondary: convert(self.text_secondary),
+            text_muted: convert(self.text_muted),
+            accent: convert(self.accent),
+            warning: convert(self.warning),
+            error: convert(self.error),
+            focus: convert(self.focus),
+            list_colors: self.list_colors.iter().copied().map(convert).collect(),
+        }
+    }
+
+    /// Color of a list's name. Lists keep their color as others are added.
+    pub fn list_color(&self, list_id: u32) -> Color {
+        if self.list_colors.is_empty() {
+            return self.text;
+        }
+        self.list_colors[list_id as usize % self.list_colors.len()]
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+/// A theme as written in a theme file. Colors are names like `light-blue`,
+/// `#rrggbb` or a 256 color index.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ThemeFile {
+    base: Option<String>,
+    surface: Option<String>,
+    text: Option<String>,
+    text_secondary: Option<String>,
+    text_muted: Option<String>,
+    accent: Option<String>,
+    warning: Option<String>,
+    error: Option<String>,
+    focus: Option<String>,
+    list_colors: Option<Vec<String>>,
+}
+
+fn parse_color(value: &str, slot: &str) -> Result<Color> {
+    Color::from_str(value).map_err(|_| anyhow!("Invalid color '{}' for {}", value, slot))
+}
+
+/// Directory holding theme files, e.g. `~/.config/todo-tui/themes`
+fn themes_dir() -> Option<PathBuf> {
+    config::config_dir().map(|dir| dir.join("themes"))
+}
+
+fn theme_file_path(name: &str) -> Option<PathBuf> {
+    themes_dir().map(|dir| dir.join(format!("{}.toml", name)))
+}
+
+/// The 16 ANSI colors with their usual xterm values
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Levels of each channel in the 6x6x6 color cube of the 256 color palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn to_ansi256(color: Color) -> Color {
+    let Color::Rgb(r, g, b) = color else {
+        return color;
+    };
+
+    let cube_index = |value: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|&i| (CUBE_LEVELS[i] as i32 - value as i32).abs())
+            .unwrap_or(0) as u8
+    };
+    let (ri, gi, bi) = (cube_index(r), cube_index(g), cube_index(b));
+    let cube = (CUBE_LEVELS[ri as usize], CUBE_LEVELS[gi as usize], CUBE_LEVELS[bi as usize]);
+
+    // The grayscale ramp is closer for colors without much hue
+    let average = ((r as u32 + g as u32 + b as u32) / 3) as u8;
+    let gray_index = (average.saturating_sub(3) / 10).min(23);
+    let gray_level = 8 + gray_index * 10;
+
+    if distance((r, g, b), (gray_level, gray_level, gray_level)) < distance((r, g, b), cube) {
+        Color::Indexed(232 + gray_index)
+    } else {
+        Color::Indexed(16 + 36 * ri + 6 * gi + bi)
+    }
+}
+
+fn to_ansi16(color: Color) -> Color {
+    let rgb = match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Indexed(index) if index >= 16 => indexed_to_rgb(index),
+        Color::Indexed(index) => return ANSI16[index as usize].0,
+        _ => return color,
+    };
+    ANSI16
+        .iter()
+        .min_by_key(|(_, ansi)| distance(rgb, *ansi))
+        .map(|(color, _)| *color)
+        .unwrap_or(color)
+}
+
+/// RGB value of a color cube or grayscale entry of the 256 color palette
+fn indexed_to_rgb(index: u8) -> (u8, u8, u8) {
+    if index >= 232 {
+        let level = 8 + (index - 232) * 10;
+        return (level, level, level);
+    }
+    let cube = index - 16;
+    (
+        CUBE_LEVELS[(cube / 36) as usize],
+        CUBE_LEVELS[(cube / 6 % 6) as usize],
+        CUBE_LEVELS[(cube % 6) as usize],
+    )
+}
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let channel = |x: u8, y: u8| (x as i32 - y as i32).pow(2) as u32;
+    channel(a.0, b.0) + channel(a.1, b.1) + channel(a.2, b.2)
 }
@@ -1,25 +1,29 @@
 pub mod screens;
+pub mod scroll;
+pub mod sidebar;
 
 use crate::app::App;
-use crate::theme::BlulocoTheme;
+use crate::action::Action;
+use crate::handlers::commands;
+use crate::keymap::Screen;
+use crate::models::RecurringFrequency;
+use crate::utils::timesheet;
+use scroll::ViewState;
+use chrono::Local;
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
+    text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
 
-pub fn draw(f: &mut Frame, app: &App) {
+pub fn draw(f: &mut Frame, app: &mut App) {
     let terminal_height = f.size().height;
-    
-    // Dynamic footer sizing based on terminal height
-    let footer_height = if terminal_height < 20 {
-        2 // Smaller footer for very small terminals
-    } else if terminal_height < 30 {
-        3 // Standard footer for medium terminals
-    } else {
-        4 // Larger footer for big terminals
-    };
+
+    // The footer grows to fit its help, up to a third of the screen
+    let help = footer_lines(app, f.size().width.saturating_sub(2));
+    let footer_height = (help.len() as u16 + 2).min(terminal_height / 3).max(3);
     
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -30,13 +34,17 @@ pub fn draw(f: &mut Frame, app: &App) {
         ])
         .split(f.size());
 
+    // Screens update the scroll positions while drawing from a shared app
+    let mut view_state = std::mem::take(&mut app.view_state);
     draw_header(f, app, chunks[0]);
-    draw_main_content(f, app, chunks[1]);
-    draw_footer(f, app, chunks[2]);
+    draw_main_content(f, app, &mut view_state, chunks[1]);
+    app.view_state = view_state;
+
+    draw_footer(f, app, help, chunks[2]);
 }
 
 fn draw_header(f: &mut Frame, app: &App, area: Rect) {
-    let title = match app.state {
+    let title = match app.screen_state() {
         crate::app::AppState::ListOverview => "Todo TUI - Lists",
         crate::app::AppState::TaskList => {
             if let Some(list) = app.get_current_list() {
@@ -47,50 +55,178 @@ fn draw_header(f: &mut Frame, app: &App, area: Rect) {
         }
         crate::app::AppState::MyDay => "Todo TUI - My Day",
         crate::app::AppState::TaskEditor => "Todo TUI - Edit Task",
-        crate::app::AppState::ListEditor => "Todo TUI - Create List",
+        crate::app::AppState::ListEditor => match app.list_editor_mode {
+            crate::app::ListEditorMode::Create => "Todo TUI - Create List",
+            crate::app::ListEditorMode::Group => "Todo TUI - Set List Group",
+        },
         crate::app::AppState::NoteEditor => "Todo TUI - View Notes",
         crate::app::AppState::MoveTask => "Todo TUI - Move Task",
+        crate::app::AppState::Trash => "Todo TUI - Trash",
+        crate::app::AppState::CompletedHistory => "Todo TUI - Completed History",
+        crate::app::AppState::BulkEdit => "Todo TUI - Bulk Edit",
+        crate::app::AppState::Workspaces => "Todo TUI - Workspaces",
+        crate::app::AppState::QuickAdd => "Todo TUI - Quick Add",
+        crate::app::AppState::Calendar => "Todo TUI - Calendar",
+        crate::app::AppState::Upcoming => "Todo TUI - Upcoming",
+        crate::app::AppState::Stats => "Todo TUI - Statistics",
+        crate::app::AppState::Timesheet => "Todo TUI - Timesheet",
+        crate::app::AppState::CommandLine | crate::app::AppState::CommandPalette => "Todo TUI",
     };
 
+    // The workspace is only worth showing once there is more than one
+    let title = if app.config.workspaces.is_empty() {
+        title.to_string()
+    } else {
+        format!("{}  [{}]", title, app.config.workspace)
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent))
+        .style(Style::default().bg(app.theme.surface));
+    let inner = block.inner(area);
     let header = Paragraph::new(title)
-        .style(Style::default().fg(BlulocoTheme::TEXT_PRIMARY).add_modifier(Modifier::BOLD))
-        .block(Block::default()
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(BlulocoTheme::ACCENT_BLUE))
-            .style(Style::default().bg(BlulocoTheme::SURFACE)));
+        .style(Style::default().fg(app.theme.text).add_modifier(Modifier::BOLD))
+        .block(block);
 
     f.render_widget(header, area);
+
+    // The running timer, on the right
+    if let Some(entry) = app.running_timer() {
+        let title = app.find_any_task(entry.task_id).map(|task| task.title.as_str()).unwrap_or("");
+        let timer = Paragraph::new(Line::from(vec![
+            Span::styled(format!("▶ {} ", title), Style::default().fg(app.theme.text_secondary)),
+            Span::styled(
+                timesheet::clock_label(entry.duration(Local::now())),
+                Style::default().fg(app.theme.focus).add_modifier(Modifier::BOLD),
+            ),
+        ]))
+        .alignment(Alignment::Right);
+        f.render_widget(timer, inner);
+    }
 }
 
-fn draw_main_content(f: &mut Frame, app: &App, area: Rect) {
-    match app.state {
-        crate::app::AppState::ListOverview => screens::list_overview::draw(f, app, area),
-        crate::app::AppState::TaskList => screens::task_list::draw(f, app, area),
-        crate::app::AppState::MyDay => screens::my_day::draw(f, app, area),
+/// Time tracked on a task, shown after its title, with ▶ while its timer
+/// runs. Empty for tasks that were never timed.
+pub fn time_label(app: &App, task_id: u32) -> String {
+    let Some(time) = app.tracked_time(task_id) else {
+        return String::new();
+    };
+    let running = app.running_timer().is_some_and(|entry| entry.task_id == task_id);
+    format!(" [{}{}]", if running { "▶ " } else { "⏱ " }, timesheet::duration_label(time))
+}
+
+/// How often a task repeats, shown after its title. Empty for tasks that
+/// don't repeat.
+pub fn frequency_label(frequency: Option<&RecurringFrequency>) -> String {
+    frequency.map(|frequency| format!(" [{}]", frequency.label())).unwrap_or_default()
+}
+
+fn draw_main_content(f: &mut Frame, app: &App, view: &mut ViewState, area: Rect) {
+    match app.screen_state() {
+        crate::app::AppState::ListOverview => screens::list_overview::draw(f, app, view, area),
+        crate::app::AppState::TaskList if app.current_board().is_some() => screens::board::draw(f, app, view, area),
+        crate::app::AppState::TaskList => screens::task_list::draw(f, app, view, area),
+        crate::app::AppState::MyDay => screens::my_day::draw(f, app, view, area),
         crate::app::AppState::TaskEditor => screens::task_editor::draw(f, app, area),
         crate::app::AppState::ListEditor => screens::list_editor::draw(f, app, area),
         crate::app::AppState::NoteEditor => screens::note_editor::draw(f, app, area),
-        crate::app::AppState::MoveTask => screens::move_task::draw(f, app, area),
+        crate::app::AppState::MoveTask => screens::move_task::draw(f, app, view, area),
+        crate::app::AppState::Trash => screens::trash::draw(f, app, view, area),
+        crate::app::AppState::CompletedHistory => screens::completed_history::draw(f, app, view, area),
+        crate::app::AppState::BulkEdit => screens::bulk_edit::draw(f, app, area),
+        crate::app::AppState::Workspaces => screens::workspaces::draw(f, app, view, area),
+        crate::app::AppState::QuickAdd => screens::quick_add::draw(f, app, area),
+        crate::app::AppState::Calendar => screens::calendar::draw(f, app, view, area),
+        crate::app::AppState::Upcoming => screens::upcoming::draw(f, app, view, area),
+        crate::app::AppState::Stats => screens::stats::draw(f, app, area),
+        crate::app::AppState::Timesheet => screens::timesheet::draw(f, app, view, area),
+        crate::app::AppState::CommandLine | crate::app::AppState::CommandPalette => {}
+    }
+
+    // The palette floats over the screen it was opened from
+    if app.state == crate::app::AppState::CommandPalette {
+        screens::command_palette::draw(f, app, view, area);
     }
 }
 
-fn draw_footer(f: &mut Frame, app: &App, area: Rect) {
-    let help_text = match app.state {
-        crate::app::AppState::ListOverview => "[↑↓/jk: Navigate] [gg/G: Top/Bottom] [Enter: Select] [Ctrl+N: New List] [Ctrl+Y: My Day] [Ctrl+Q: Quit]",
-        crate::app::AppState::TaskList => "[↑↓/jk: Navigate] [gg/G: Top/Bottom] [Enter: Edit] [Space: Toggle] [Ctrl+N: New] [Ctrl+E: Edit] [Ctrl+V: View Notes] [Ctrl+T: Move] [Del/Backspace: Delete] [Ctrl+D: My Day] [Ctrl+M: My Day] [Esc: Back]",
-        crate::app::AppState::MyDay => "[↑↓/jk: Navigate] [gg/G: Top/Bottom] [Space: Toggle] [Ctrl+↑/↓: Reorder] [Ctrl+V: View Notes] [Ctrl+T: Move] [Ctrl+D: Remove] [Del/Backspace: Delete] [Esc: Back]",
-        crate::app::AppState::TaskEditor => "[Type: Edit] [Enter: Next/Save] [Esc: Cancel]",
-        crate::app::AppState::ListEditor => "[Type: List Name] [Enter: Save] [Esc: Cancel]",
-        crate::app::AppState::NoteEditor => "[Type: Edit] [Enter: Save] [Esc: Cancel]",
-        crate::app::AppState::MoveTask => "[↑↓/jk: Navigate] [gg/G: Top/Bottom] [Enter: Move Here] [Esc: Cancel]",
-    };
+/// Footer help wrapped to `width`, breaking lines only between bindings
+fn footer_lines(app: &App, width: u16) -> Vec<Line<'static>> {
+    if app.state == crate::app::AppState::CommandLine {
+        return vec![command_line_text(app)];
+    }
+
+    let screen = Screen::current(app);
+    let mut items = app.keymap.help_items(screen);
+    if screen == Screen::Editor {
+        items.insert(0, "[Type: Edit]".to_string());
+    }
+
+    let mut lines: Vec<String> = Vec::new();
+    for item in items {
+        match lines.last_mut() {
+            Some(line) if line.chars().count() + 1 + item.chars().count() <= width as usize => {
+                line.push(' ');
+                line.push_str(&item);
+            }
+            _ => lines.push(item),
+        }
+    }
+    lines.into_iter().map(Line::from).collect()
+}
+
+fn draw_footer(f: &mut Frame, app: &App, help: Vec<Line<'static>>, area: Rect) {
 
-    let footer = Paragraph::new(help_text)
-        .style(Style::default().fg(BlulocoTheme::TEXT_SECONDARY))
-        .block(Block::default()
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(BlulocoTheme::ACCENT_BLUE))
-            .style(Style::default().bg(BlulocoTheme::SURFACE)));
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent))
+        .style(Style::default().bg(app.theme.surface));
+    if let Some(message) = &app.status_message {
+        block = block.title(Span::styled(
+            format!(" {} ", message),
+            Style::default().fg(app.theme.warning).add_modifier(Modifier::BOLD),
+        ));
+    } else if let Some(keys) = pending_keys_text(app) {
+        block = block.title(Span::styled(
+            format!(" {} ", keys),
+            Style::default().fg(app.theme.focus).add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    let footer = Paragraph::new(help)
+        .style(Style::default().fg(app.theme.text_secondary))
+        .block(block);
 
     f.render_widget(footer, area);
 }
+
+/// The count and keys typed so far of an unfinished command, like vim's
+/// showcmd
+fn pending_keys_text(app: &App) -> Option<String> {
+    let mut keys = app.count.map(|count| count.to_string()).unwrap_or_default();
+    for chord in &app.pending_keys {
+        keys.push_str(&chord.display());
+    }
+    if app.pending_mark.is_some() {
+        keys.push_str(if app.pending_mark == Some(Action::SetMark) { "m" } else { "'" });
+    }
+    Some(keys).filter(|keys| !keys.is_empty())
+}
+
+/// The command line being typed, followed by the usage of the command
+fn command_line_text(app: &App) -> Line<'static> {
+    let command_name = app.input_buffer.split_whitespace().next().unwrap_or_default();
+    let usage = commands::COMMANDS
+        .iter()
+        .find(|c| c.name == command_name)
+        .map(|c| format!("   {} - {}", c.usage, c.description))
+        .unwrap_or_default();
+
+    Line::from(vec![
+        Span::styled(
+            format!(":{}█", app.input_buffer),
+            Style::default().fg(app.theme.focus).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(usage, Style::default().fg(app.theme.text_muted)),
+    ])
+}
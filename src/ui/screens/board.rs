@@ -0,0 +1,101 @@
+use crate::app::App;
+use crate::ui::{self, scroll::{self, ListView, ViewState}};
+use crate::utils::date_utils;
+use chrono::Local;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem},
+    Frame,
+};
+
+pub fn draw(f: &mut Frame, app: &App, view: &mut ViewState, area: Rect) {
+    let Some(board) = app.current_board() else {
+        return;
+    };
+    let statuses = board.statuses();
+    let done = statuses.len() - 1;
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Ratio(1, statuses.len() as u32); statuses.len()])
+        .split(area);
+    view.board.resize_with(statuses.len(), ListView::default);
+
+    let selected_ids = if app.has_selection() { app.get_target_task_ids() } else { Vec::new() };
+    let now = Local::now();
+
+    for (column, status) in statuses.iter().enumerate() {
+        let focused = column == app.board.column;
+        let tasks = app.get_board_column_tasks(column);
+
+        let items: Vec<ListItem> = tasks
+            .iter()
+            .enumerate()
+            .map(|(row, task)| {
+                let style = if focused && row == app.selected_task_index {
+                    Style::default().fg(app.theme.focus).add_modifier(Modifier::BOLD)
+                } else if selected_ids.contains(&task.id) {
+                    Style::default().fg(app.theme.accent).add_modifier(Modifier::REVERSED)
+                } else if task.is_completed || task.is_deferred() {
+                    Style::default().fg(app.theme.text_muted)
+                } else if task.is_overdue() {
+                    Style::default().fg(app.theme.error)
+                } else if task.is_due_today() {
+                    Style::default().fg(app.theme.warning)
+                } else {
+                    Style::default().fg(app.theme.text)
+                };
+
+                let selection_marker = if selected_ids.contains(&task.id) { "● " } else { "" };
+                let checkbox = if task.is_completed { "☑" } else { "☐" };
+                let my_day_indicator = if task.is_in_my_day { "⭐ " } else { "" };
+                let priority_marker = task.priority.map(|p| p.marker()).unwrap_or("");
+                let tag_info: String = task.tags.iter().map(|tag| format!(" #{}", tag)).collect();
+                let due_info = match task.due() {
+                    Some(due) if !task.is_completed => format!(" [{}]", date_utils::due_label(due, now)),
+                    _ => String::new(),
+                };
+
+                let time_info = ui::time_label(app, task.id);
+                let content = format!("{}{} {}{}{}{}{}{}", selection_marker, checkbox, my_day_indicator, priority_marker, task.title, tag_info, due_info, time_info);
+                ListItem::new(Line::from(Span::styled(content, style)))
+            })
+            .collect();
+
+        let mut title = format!("{} ({})", status, tasks.len());
+        if focused && app.has_selection() {
+            let mode = if app.visual_anchor.is_some() { "VISUAL, " } else { "" };
+            title.push_str(&format!(" [{}{} selected]", mode, selected_ids.len()));
+        }
+        let (border_style, title_style) = if focused {
+            (Style::default().fg(app.theme.focus), Style::default().fg(app.theme.focus).add_modifier(Modifier::BOLD))
+        } else if column == done {
+            (Style::default().fg(app.theme.accent), Style::default().fg(app.theme.text_muted))
+        } else {
+            (Style::default().fg(app.theme.accent), Style::default().fg(app.theme.text))
+        };
+
+        let list = List::new(items)
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_style(border_style)
+                .style(Style::default().bg(app.theme.surface))
+                .title(Span::styled(title, title_style)));
+
+        if focused {
+            view.page_height = scroll::visible_rows(columns[column]);
+        }
+        let selected = (focused && !tasks.is_empty()).then_some(app.selected_task_index);
+        scroll::render_list(f, &app.theme, list, columns[column], &mut view.board[column], selected);
+    }
+}
+
+/// Board column drawn at a screen column, if any
+pub fn column_at(app: &App, x: u16) -> Option<usize> {
+    app.view_state
+        .board
+        .iter()
+        .position(|column| (column.area.x..column.area.x + column.area.width).contains(&x))
+}
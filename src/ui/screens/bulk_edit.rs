@@ -0,0 +1,85 @@
+use crate::app::{App, BulkEditField};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Title
+            Constraint::Length(3), // Input field
+            Constraint::Min(0),    // Help text
+        ])
+        .split(area);
+
+    draw_title(f, app, chunks[0]);
+    draw_input(f, app, chunks[1]);
+    draw_help(f, app, chunks[2]);
+}
+
+fn draw_title(f: &mut Frame, app: &App, area: Rect) {
+    let task_count = app.get_target_task_ids_for(&app.previous_state).len();
+    let title = match app.bulk_edit_field {
+        BulkEditField::DueDate => format!("Set Due Date for {} task(s)", task_count),
+        BulkEditField::Tag => format!("Add Tag to {} task(s)", task_count),
+    };
+
+    let title_widget = Paragraph::new(title)
+        .style(Style::default().fg(app.theme.focus).add_modifier(Modifier::BOLD))
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface)));
+
+    f.render_widget(title_widget, area);
+}
+
+fn draw_input(f: &mut Frame, app: &App, area: Rect) {
+    let (placeholder, field_title) = match app.bulk_edit_field {
+        BulkEditField::DueDate => ("Enter due date, or leave empty to clear...", "Due Date"),
+        BulkEditField::Tag => ("Enter tag...", "Tag"),
+    };
+
+    let input_text = if app.input_buffer.is_empty() {
+        placeholder.to_string()
+    } else {
+        app.input_buffer.clone()
+    };
+
+    let style = if app.input_buffer.is_empty() {
+        Style::default().fg(app.theme.text_muted)
+    } else {
+        Style::default().fg(app.theme.text)
+    };
+
+    let input_widget = Paragraph::new(input_text)
+        .style(style)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface))
+            .title(field_title));
+
+    f.render_widget(input_widget, area);
+}
+
+fn draw_help(f: &mut Frame, app: &App, area: Rect) {
+    let help_text = match app.bulk_edit_field {
+        BulkEditField::DueDate => "Enter a due date (YYYY-MM-DD, 'tomorrow', 'next fri', '+2w', etc.) and press Enter to apply it to every selected task.\nPress Esc to cancel.",
+        BulkEditField::Tag => "Enter a tag and press Enter to add it to every selected task.\nPress Esc to cancel.",
+    };
+
+    let help_widget = Paragraph::new(help_text)
+        .style(Style::default().fg(app.theme.text_secondary))
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface))
+            .title("Help"));
+
+    f.render_widget(help_widget, area);
+}
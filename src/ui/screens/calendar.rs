@@ -0,0 +1,189 @@
+use crate::app::App;
+use crate::ui::scroll::{self, ViewState};
+use chrono::{Datelike, Duration, Local, Months, NaiveDate};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+use std::collections::HashMap;
+
+/// Tasks due on one day of the calendar
+#[derive(Debug, Default)]
+struct DayCounts {
+    due: usize,
+    overdue: usize,
+    completed: usize,
+}
+
+pub fn draw(f: &mut Frame, app: &App, view: &mut ViewState, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(70), // Month grid
+            Constraint::Percentage(30), // Tasks of the picked day
+        ])
+        .split(area);
+
+    draw_month(f, app, chunks[0]);
+    draw_day(f, app, view, chunks[1]);
+}
+
+fn draw_month(f: &mut Frame, app: &App, area: Rect) {
+    let day = app.calendar.day;
+    let first = day.with_day(1).unwrap_or(day);
+    let next_month = first.checked_add_months(Months::new(1)).unwrap_or(first);
+
+    // The grid starts on the first day of the week the month starts in
+    let week_start = app.config.week_start;
+    let offset = (first.weekday().num_days_from_monday() + 7 - week_start.num_days_from_monday()) % 7;
+    let grid_start = first - Duration::days(offset as i64);
+    let weeks = (offset as i64 + (next_month - first).num_days() + 6) / 7;
+    let counts = day_counts(app, grid_start, grid_start + Duration::weeks(weeks));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent))
+        .style(Style::default().bg(app.theme.surface))
+        .title(Line::from(vec![
+            Span::raw(format!("{}  ", day.format("%B %Y"))),
+            Span::styled("• due ", Style::default().fg(app.theme.warning)),
+            Span::styled("! overdue ", Style::default().fg(app.theme.error)),
+            Span::styled("✓ done", Style::default().fg(app.theme.text_muted)),
+        ]));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let mut row_constraints = vec![Constraint::Length(1)];
+    row_constraints.extend((0..weeks).map(|_| Constraint::Ratio(1, weeks as u32)));
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(row_constraints)
+        .split(inner);
+
+    for (i, cell) in week_cells(rows[0]).iter().enumerate() {
+        let weekday = (grid_start + Duration::days(i as i64)).weekday();
+        f.render_widget(Paragraph::new(weekday.to_string()).style(Style::default().fg(app.theme.text_secondary)), *cell);
+    }
+
+    let today = Local::now().date_naive();
+    for week in 0..weeks {
+        for (i, cell) in week_cells(rows[week as usize + 1]).iter().enumerate() {
+            let date = grid_start + Duration::days(week * 7 + i as i64);
+            let lines = day_cell(app, date, date.month() == day.month(), date == today, counts.get(&date));
+            f.render_widget(Paragraph::new(lines), *cell);
+        }
+    }
+}
+
+fn week_cells(area: Rect) -> std::rc::Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Ratio(1, 7); 7])
+        .split(area)
+}
+
+/// The day number, then how many tasks are due, overdue and done that day
+fn day_cell<'a>(app: &App, date: NaiveDate, in_month: bool, is_today: bool, counts: Option<&DayCounts>) -> Vec<Line<'a>> {
+    let mut number_style = if in_month {
+        Style::default().fg(app.theme.text)
+    } else {
+        Style::default().fg(app.theme.text_muted)
+    };
+    if is_today {
+        number_style = number_style.fg(app.theme.accent).add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+    }
+    if date == app.calendar.day {
+        number_style = Style::default().fg(app.theme.focus).add_modifier(Modifier::BOLD | Modifier::REVERSED);
+    }
+    let marker = if date == app.calendar.day && app.calendar.moving_task_id.is_some() { "→" } else { "" };
+
+    let mut count_spans = Vec::new();
+    if let Some(counts) = counts {
+        for (count, symbol, color) in [
+            (counts.due, "•", app.theme.warning),
+            (counts.overdue, "!", app.theme.error),
+            (counts.completed, "✓", app.theme.text_muted),
+        ] {
+            if count > 0 {
+                count_spans.push(Span::styled(format!("{}{} ", symbol, count), Style::default().fg(color)));
+            }
+        }
+    }
+
+    vec![
+        Line::from(Span::styled(format!("{}{:>2}", marker, date.day()), number_style)),
+        Line::from(count_spans),
+    ]
+}
+
+fn day_counts(app: &App, from: NaiveDate, to: NaiveDate) -> HashMap<NaiveDate, DayCounts> {
+    let mut counts: HashMap<NaiveDate, DayCounts> = HashMap::new();
+    for task in &app.tasks {
+        let Some(date) = task.due_date.map(|due| due.date_naive()).filter(|date| (from..to).contains(date)) else {
+            continue;
+        };
+        let day = counts.entry(date).or_default();
+        if task.is_completed {
+            day.completed += 1;
+        } else if task.is_overdue() {
+            day.overdue += 1;
+        } else {
+            day.due += 1;
+        }
+    }
+    counts
+}
+
+fn draw_day(f: &mut Frame, app: &App, view: &mut ViewState, area: Rect) {
+    let tasks = app.get_calendar_tasks(app.calendar.day);
+
+    let items: Vec<ListItem> = if tasks.is_empty() {
+        vec![ListItem::new(Line::from(Span::styled("Nothing due", Style::default().fg(app.theme.text_muted))))]
+    } else {
+        tasks
+            .iter()
+            .enumerate()
+            .map(|(i, task)| {
+                let style = if i == app.selected_task_index {
+                    Style::default().fg(app.theme.focus).add_modifier(Modifier::BOLD)
+                } else if task.is_completed || task.is_deferred() {
+                    Style::default().fg(app.theme.text_muted)
+                } else if task.is_overdue() {
+                    Style::default().fg(app.theme.error)
+                } else {
+                    Style::default().fg(app.theme.text)
+                };
+                let checkbox = if task.is_completed { "☑" } else { "☐" };
+                let time = match task.due() {
+                    Some(due) if due.has_time => format!("{} ", due.at.format("%H:%M")),
+                    _ => String::new(),
+                };
+                let moving = if app.calendar.moving_task_id == Some(task.id) { "→ " } else { "" };
+                let list_name = app.lists.iter().find(|l| l.id == task.list_id).map(|l| l.name.as_str()).unwrap_or("");
+                ListItem::new(Line::from(Span::styled(
+                    format!("{}{} {}{} ({})", moving, checkbox, time, task.title, list_name),
+                    style,
+                )))
+            })
+            .collect()
+    };
+
+    let title = match app.calendar.moving_task_id.and_then(|id| app.tasks.iter().find(|t| t.id == id)) {
+        Some(task) => format!("Moving '{}'", task.title),
+        None => app.calendar.day.format("%A, %B %-d").to_string(),
+    };
+
+    let list = List::new(items)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface))
+            .title(title));
+
+    view.page_height = scroll::visible_rows(area);
+    let selected = (!tasks.is_empty()).then_some(app.selected_task_index);
+    scroll::render_list(f, &app.theme, list, area, &mut view.calendar, selected);
+}
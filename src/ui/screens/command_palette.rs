@@ -0,0 +1,95 @@
+use crate::app::App;
+use crate::handlers::commands;
+use crate::keymap::Screen;
+use crate::ui::scroll::{self, ViewState};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    Frame,
+};
+
+pub fn draw(f: &mut Frame, app: &App, view: &mut ViewState, area: Rect) {
+    let popup = centered_rect(area, 60, 70);
+    f.render_widget(Clear, popup);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Query
+            Constraint::Min(0),    // Matching actions
+        ])
+        .split(popup);
+
+    draw_query(f, app, chunks[0]);
+    draw_actions(f, app, view, chunks[1]);
+}
+
+fn draw_query(f: &mut Frame, app: &App, area: Rect) {
+    let query_widget = Paragraph::new(format!("> {}█", app.input_buffer))
+        .style(Style::default().fg(app.theme.focus).add_modifier(Modifier::BOLD))
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface))
+            .title("Command Palette"));
+
+    f.render_widget(query_widget, area);
+}
+
+fn draw_actions(f: &mut Frame, app: &App, view: &mut ViewState, area: Rect) {
+    // Keys are shown for the screen the palette was opened from
+    let screen = Screen::of(app, &app.previous_state);
+    let actions = commands::palette_actions(app);
+
+    let items: Vec<ListItem> = actions
+        .iter()
+        .enumerate()
+        .map(|(i, action)| {
+            let style = if i == app.palette_index {
+                Style::default().fg(app.theme.focus).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(app.theme.text)
+            };
+            let keys = app.keymap.keys_for(screen, *action).unwrap_or_default();
+
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{:<20}", action.label()), style),
+                Span::styled(format!("{:<22}", action.name()), Style::default().fg(app.theme.text_muted)),
+                Span::styled(keys, Style::default().fg(app.theme.accent)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface))
+            .title(format!("Actions ({})", actions.len())));
+
+    view.page_height = scroll::visible_rows(area);
+    scroll::render_list(f, &app.theme, list, area, &mut view.palette, Some(app.palette_index));
+}
+
+/// A rectangle taking the given percentages of `area`, centred in it
+fn centered_rect(area: Rect, percent_x: u16, percent_y: u16) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(vertical[1])[1]
+}
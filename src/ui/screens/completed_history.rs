@@ -0,0 +1,149 @@
+use crate::app::App;
+use crate::ui::scroll::{self, ViewState};
+use chrono::Local;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+
+pub fn draw(f: &mut Frame, app: &App, view: &mut ViewState, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Search and filter
+            Constraint::Min(0),    // History
+        ])
+        .split(area);
+
+    draw_filter(f, app, chunks[0]);
+    draw_history(f, app, view, chunks[1]);
+}
+
+fn draw_filter(f: &mut Frame, app: &App, area: Rect) {
+    let list_name = match app.history_filter.list_id {
+        Some(list_id) => app
+            .lists
+            .iter()
+            .find(|l| l.id == list_id)
+            .map(|l| l.name.clone())
+            .unwrap_or_else(|| "Unknown".to_string()),
+        None => "All lists".to_string(),
+    };
+
+    let query_style = if app.history_filter.searching {
+        Style::default().fg(app.theme.focus).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(app.theme.text)
+    };
+    let cursor = if app.history_filter.searching { "█" } else { "" };
+
+    let filter_line = Line::from(vec![
+        Span::styled("Search: ", Style::default().fg(app.theme.text_secondary)),
+        Span::styled(format!("{}{}", app.history_filter.query, cursor), query_style),
+        Span::styled("   List: ", Style::default().fg(app.theme.text_secondary)),
+        Span::styled(list_name, Style::default().fg(app.theme.text)),
+    ]);
+
+    let filter_widget = Paragraph::new(filter_line)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface))
+            .title("Filter"));
+
+    f.render_widget(filter_widget, area);
+}
+
+fn draw_history(f: &mut Frame, app: &App, view: &mut ViewState, area: Rect) {
+    let tasks = app.get_completed_history();
+
+    let mut items: Vec<ListItem> = Vec::new();
+    let mut current_date = None;
+    // Date headers are rows too, so the selected task's row is found as we go
+    let mut selected_row = None;
+
+    for (i, task) in tasks.iter().enumerate() {
+        let Some(completed_at) = task.completed_at.map(|d| d.with_timezone(&Local)) else {
+            continue;
+        };
+
+        // Date header whenever the completion day changes
+        if current_date != Some(completed_at.date_naive()) {
+            current_date = Some(completed_at.date_naive());
+            let day_count = tasks
+                .iter()
+                .filter(|t| t.completed_at.map(|d| d.with_timezone(&Local).date_naive()) == current_date)
+                .count();
+            items.push(ListItem::new(Line::from(Span::styled(
+                format!("{} ({})", completed_at.format("%A, %B %d, %Y"), day_count),
+                Style::default().fg(app.theme.accent).add_modifier(Modifier::BOLD),
+            ))));
+        }
+
+        if i == app.selected_task_index {
+            selected_row = Some(items.len());
+        }
+        let style = if i == app.selected_task_index {
+            Style::default().fg(app.theme.focus).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(app.theme.text)
+        };
+
+        let list_name = app
+            .lists
+            .iter()
+            .chain(&app.trash_lists)
+            .find(|l| l.id == task.list_id)
+            .map(|l| l.name.as_str())
+            .unwrap_or("Unknown");
+
+        let archived_info = if app.archived_tasks.iter().any(|t| t.id == task.id) {
+            " [Archived]"
+        } else {
+            ""
+        };
+
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled(format!("  ☑ {} ({})", task.title, list_name), style),
+            Span::styled(
+                format!(" {}{}", completed_at.format("%H:%M"), archived_info),
+                Style::default().fg(app.theme.text_muted),
+            ),
+        ])));
+    }
+
+    let list = List::new(items)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface))
+            .title(format!("Completed History ({} tasks)", tasks.len())));
+
+    view.page_height = scroll::visible_rows(area);
+    scroll::render_list(f, &app.theme, list, area, &mut view.history, selected_row);
+}
+
+/// Index in the completed history of the task drawn on `row`, or `None` for a
+/// date header
+pub fn task_index_at_row(app: &App, row: usize) -> Option<usize> {
+    let mut current_date = None;
+    let mut rows = 0;
+
+    for (i, task) in app.get_completed_history().iter().enumerate() {
+        let Some(completed_on) = task.completed_at.map(|d| d.with_timezone(&Local).date_naive()) else {
+            continue;
+        };
+        if current_date != Some(completed_on) {
+            current_date = Some(completed_on);
+            rows += 1;
+        }
+        if rows == row {
+            return Some(i);
+        }
+        rows += 1;
+    }
+    None
+}
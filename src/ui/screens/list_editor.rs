@@ -1,5 +1,4 @@
-use crate::app::App;
-use crate::theme::BlulocoTheme;
+use crate::app::{App, ListEditorMode};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
@@ -22,50 +21,71 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect) {
     draw_help(f, app, chunks[2]);
 }
 
-fn draw_title(f: &mut Frame, _app: &App, area: Rect) {
-    let title_widget = Paragraph::new("Create New List")
-        .style(Style::default().fg(BlulocoTheme::FOCUS).add_modifier(Modifier::BOLD))
+fn draw_title(f: &mut Frame, app: &App, area: Rect) {
+    let title = match app.list_editor_mode {
+        ListEditorMode::Create => "Create New List".to_string(),
+        ListEditorMode::Group => {
+            let list_name = app
+                .lists
+                .iter()
+                .find(|l| Some(l.id) == app.editing_list_id)
+                .map(|l| l.name.as_str())
+                .unwrap_or("List");
+            format!("Set Group for: {}", list_name)
+        }
+    };
+
+    let title_widget = Paragraph::new(title)
+        .style(Style::default().fg(app.theme.focus).add_modifier(Modifier::BOLD))
         .block(Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(BlulocoTheme::ACCENT_BLUE))
-            .style(Style::default().bg(BlulocoTheme::SURFACE)));
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface)));
 
     f.render_widget(title_widget, area);
 }
 
 fn draw_input(f: &mut Frame, app: &App, area: Rect) {
+    let (placeholder, field_title) = match app.list_editor_mode {
+        ListEditorMode::Create => ("Enter list name...", "List Name"),
+        ListEditorMode::Group => ("Enter group name, or leave empty to ungroup...", "Group Name"),
+    };
+
     let input_text = if app.input_buffer.is_empty() {
-        "Enter list name...".to_string()
+        placeholder.to_string()
     } else {
         app.input_buffer.clone()
     };
 
     let style = if app.input_buffer.is_empty() {
-        Style::default().fg(BlulocoTheme::TEXT_MUTED)
+        Style::default().fg(app.theme.text_muted)
     } else {
-        Style::default().fg(BlulocoTheme::TEXT_PRIMARY)
+        Style::default().fg(app.theme.text)
     };
 
     let input_widget = Paragraph::new(input_text)
         .style(style)
         .block(Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(BlulocoTheme::ACCENT_BLUE))
-            .style(Style::default().bg(BlulocoTheme::SURFACE))
-            .title("List Name"));
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface))
+            .title(field_title));
 
     f.render_widget(input_widget, area);
 }
 
-fn draw_help(f: &mut Frame, _app: &App, area: Rect) {
-    let help_text = "Type the list name and press Enter to create the list.\nPress Esc to cancel.";
+fn draw_help(f: &mut Frame, app: &App, area: Rect) {
+    let help_text = match app.list_editor_mode {
+        ListEditorMode::Create => "Type the list name and press Enter to create the list.\nPress Esc to cancel.",
+        ListEditorMode::Group => "Type the group name and press Enter to save. Lists sharing a group name are shown together.\nPress Esc to cancel.",
+    };
     
     let help_widget = Paragraph::new(help_text)
-        .style(Style::default().fg(BlulocoTheme::TEXT_SECONDARY))
+        .style(Style::default().fg(app.theme.text_secondary))
         .block(Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(BlulocoTheme::ACCENT_BLUE))
-            .style(Style::default().bg(BlulocoTheme::SURFACE))
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface))
             .title("Help"));
 
     f.render_widget(help_widget, area);
@@ -1,56 +1,40 @@
 use crate::app::App;
-use crate::theme::BlulocoTheme;
+use crate::action::Action;
+use crate::keymap::Screen;
+use crate::ui::scroll::{self, ViewState};
+use crate::ui::sidebar;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Modifier, Style},
-    text::{Line, Span},
+    style::Style,
     widgets::{Block, Borders, List, ListItem, Paragraph},
     Frame,
 };
 
-pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+pub fn draw(f: &mut Frame, app: &App, view: &mut ViewState, area: Rect) {
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
+        .constraints([
+            Constraint::Percentage(app.config.sidebar_width),
+            Constraint::Percentage(100 - app.config.sidebar_width),
+        ])
         .split(area);
 
-    draw_lists(f, app, chunks[0]);
+    draw_lists(f, app, view, chunks[0]);
     draw_info(f, app, chunks[1]);
 }
 
-fn draw_lists(f: &mut Frame, app: &App, area: Rect) {
-    let items: Vec<ListItem> = app
-        .lists
-        .iter()
-        .enumerate()
-        .map(|(i, list)| {
-            let style = if i == app.selected_task_index {
-                Style::default().fg(BlulocoTheme::FOCUS).add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(BlulocoTheme::TEXT_PRIMARY)
-            };
-
-            let task_count = app.tasks.iter().filter(|t| t.list_id == list.id).count();
-            let completed_count = app.tasks.iter().filter(|t| t.list_id == list.id && t.is_completed).count();
-            
-            let content = if task_count > 0 {
-                format!("{} ({}/{})", list.name, completed_count, task_count)
-            } else {
-                list.name.clone()
-            };
-
-            ListItem::new(Line::from(Span::styled(content, style)))
-        })
-        .collect();
+fn draw_lists(f: &mut Frame, app: &App, view: &mut ViewState, area: Rect) {
+    let items: Vec<ListItem> = sidebar::build_items(app, |_, i| i == app.selected_task_index);
 
     let list = List::new(items)
         .block(Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(BlulocoTheme::ACCENT_BLUE))
-            .style(Style::default().bg(BlulocoTheme::SURFACE))
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface))
             .title("Lists"));
 
-    f.render_widget(list, area);
+    view.page_height = scroll::visible_rows(area);
+    scroll::render_list(f, &app.theme, list, area, &mut view.sidebar, Some(app.selected_task_index));
 }
 
 fn draw_info(f: &mut Frame, app: &App, area: Rect) {
@@ -59,23 +43,25 @@ fn draw_info(f: &mut Frame, app: &App, area: Rect) {
     let my_day_tasks = app.get_my_day_tasks().len();
     let overdue_tasks = app.tasks.iter().filter(|t| t.is_overdue()).count();
 
+    let key = |action| app.keymap.keys_for(Screen::ListOverview, action).unwrap_or_else(|| format!(":{}", action.name()));
     let info_text = format!(
         "Welcome to Todo TUI!\n\n\
         Total Tasks: {}\n\
         Completed: {}\n\
         My Day: {}\n\
         Overdue: {}\n\n\
-        Select a list to view tasks, or create a new list with Ctrl+N.\n\
-        Use Ctrl+M to view your My Day tasks.",
-        total_tasks, completed_tasks, my_day_tasks, overdue_tasks
+        Select a list to view tasks, or create a new list with {}.\n\
+        Use {} to view your My Day tasks, {} for statistics, or {} to find any command.",
+        total_tasks, completed_tasks, my_day_tasks, overdue_tasks,
+        key(Action::NewList), key(Action::OpenMyDay), key(Action::OpenStats), key(Action::OpenPalette)
     );
 
     let info = Paragraph::new(info_text)
-        .style(Style::default().fg(BlulocoTheme::TEXT_PRIMARY))
+        .style(Style::default().fg(app.theme.text))
         .block(Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(BlulocoTheme::ACCENT_BLUE))
-            .style(Style::default().bg(BlulocoTheme::SURFACE))
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface))
             .title("Overview"));
 
     f.render_widget(info, area);
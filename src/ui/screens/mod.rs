@@ -5,3 +5,14 @@ pub mod task_editor;
 pub mod list_editor;
 pub mod note_editor;
 pub mod move_task;
+pub mod trash;
+pub mod completed_history;
+pub mod bulk_edit;
+pub mod command_palette;
+pub mod workspaces;
+pub mod quick_add;
+pub mod calendar;
+pub mod upcoming;
+pub mod board;
+pub mod stats;
+pub mod timesheet;
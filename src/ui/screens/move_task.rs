@@ -1,14 +1,14 @@
 use crate::app::App;
-use crate::theme::BlulocoTheme;
+use crate::ui::scroll::{self, ViewState};
+use crate::ui::sidebar;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
-    text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph},
     Frame,
 };
 
-pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+pub fn draw(f: &mut Frame, app: &App, view: &mut ViewState, area: Rect) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -18,57 +18,39 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect) {
         .split(area);
 
     draw_title(f, app, chunks[0]);
-    draw_lists(f, app, chunks[1]);
+    draw_lists(f, app, view, chunks[1]);
 }
 
 fn draw_title(f: &mut Frame, app: &App, area: Rect) {
-    let task_name = if let Some(task) = &app.moving_task {
-        format!("Move '{}' to:", task.title)
-    } else {
-        "Move Task".to_string()
+    let task_name = match app.moving_task_ids.as_slice() {
+        [] => "Move Task".to_string(),
+        [task_id] => match app.tasks.iter().find(|t| t.id == *task_id) {
+            Some(task) => format!("Move '{}' to:", task.title),
+            None => "Move Task".to_string(),
+        },
+        task_ids => format!("Move {} tasks to:", task_ids.len()),
     };
 
     let title_widget = Paragraph::new(task_name)
-        .style(Style::default().fg(BlulocoTheme::FOCUS).add_modifier(Modifier::BOLD))
+        .style(Style::default().fg(app.theme.focus).add_modifier(Modifier::BOLD))
         .block(Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(BlulocoTheme::ACCENT_BLUE))
-            .style(Style::default().bg(BlulocoTheme::SURFACE)));
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface)));
 
     f.render_widget(title_widget, area);
 }
 
-fn draw_lists(f: &mut Frame, app: &App, area: Rect) {
-    let items: Vec<ListItem> = app
-        .lists
-        .iter()
-        .enumerate()
-        .map(|(i, list)| {
-            let style = if i == app.selected_task_index {
-                Style::default().fg(BlulocoTheme::FOCUS).add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(BlulocoTheme::TEXT_PRIMARY)
-            };
-
-            let task_count = app.tasks.iter().filter(|t| t.list_id == list.id).count();
-            let completed_count = app.tasks.iter().filter(|t| t.list_id == list.id && t.is_completed).count();
-            
-            let content = if task_count > 0 {
-                format!("{} ({}/{})", list.name, completed_count, task_count)
-            } else {
-                list.name.clone()
-            };
-
-            ListItem::new(Line::from(Span::styled(content, style)))
-        })
-        .collect();
+fn draw_lists(f: &mut Frame, app: &App, view: &mut ViewState, area: Rect) {
+    let items: Vec<ListItem> = sidebar::build_items(app, |_, i| i == app.selected_task_index);
 
     let list = List::new(items)
         .block(Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(BlulocoTheme::ACCENT_BLUE))
-            .style(Style::default().bg(BlulocoTheme::SURFACE))
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface))
             .title("Select Destination List"));
 
-    f.render_widget(list, area);
+    view.page_height = scroll::visible_rows(area);
+    scroll::render_list(f, &app.theme, list, area, &mut view.sidebar, Some(app.selected_task_index));
 }
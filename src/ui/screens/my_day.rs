@@ -1,5 +1,7 @@
-use crate::app::App;
-use crate::theme::BlulocoTheme;
+use crate::app::{App, SidebarItem, SuggestionReason};
+use crate::ui::scroll::{self, ViewState};
+use crate::ui::{self, sidebar};
+use crate::utils::date_utils;
 use chrono::Local;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -9,103 +11,87 @@ use ratatui::{
     Frame,
 };
 
-pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+pub fn draw(f: &mut Frame, app: &App, view: &mut ViewState, area: Rect) {
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
+        .constraints([
+            Constraint::Percentage(app.config.sidebar_width),
+            Constraint::Percentage(100 - app.config.sidebar_width),
+        ])
         .split(area);
 
-    draw_lists(f, app, chunks[0]);
-    draw_my_day_tasks(f, app, chunks[1]);
+    let panes = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage(60), // My Day
+            Constraint::Percentage(40), // Suggestions
+        ])
+        .split(chunks[1]);
+
+    draw_lists(f, app, view, chunks[0]);
+    draw_my_day_tasks(f, app, view, panes[0]);
+    draw_suggestions(f, app, view, panes[1]);
 }
 
-fn draw_lists(f: &mut Frame, app: &App, area: Rect) {
-    let items: Vec<ListItem> = app
-        .lists
-        .iter()
-        .map(|list| {
-            let style = if Some(list.id) == app.current_list_id {
-                Style::default().fg(BlulocoTheme::FOCUS).add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(BlulocoTheme::TEXT_PRIMARY)
-            };
-
-            let task_count = app.tasks.iter().filter(|t| t.list_id == list.id).count();
-            let completed_count = app.tasks.iter().filter(|t| t.list_id == list.id && t.is_completed).count();
-            
-            let content = if task_count > 0 {
-                format!("{} ({}/{})", list.name, completed_count, task_count)
-            } else {
-                list.name.clone()
-            };
-
-            ListItem::new(Line::from(Span::styled(content, style)))
-        })
-        .collect();
+fn draw_lists(f: &mut Frame, app: &App, view: &mut ViewState, area: Rect) {
+    let items: Vec<ListItem> = sidebar::build_items(app, |item, _| {
+        app.current_list_id.is_some_and(|id| *item == SidebarItem::List(id))
+    });
 
     let list = List::new(items)
         .block(Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(BlulocoTheme::ACCENT_BLUE))
-            .style(Style::default().bg(BlulocoTheme::SURFACE))
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface))
             .title("Lists"));
 
-    f.render_widget(list, area);
+    scroll::render_list(f, &app.theme, list, area, &mut view.sidebar, sidebar::current_list_row(app));
 }
 
-fn draw_my_day_tasks(f: &mut Frame, app: &App, area: Rect) {
+fn draw_my_day_tasks(f: &mut Frame, app: &App, view: &mut ViewState, area: Rect) {
     let tasks = app.get_my_day_tasks();
+    let focused = !app.my_day.suggestions_focused;
+    // The cursor row, kept while the suggestions have the cursor
+    let cursor = if focused { app.selected_task_index } else { app.my_day.inactive_index };
     
     // Separate active and completed tasks
     let active_tasks: Vec<_> = tasks.iter().filter(|t| !t.is_completed).collect();
     let completed_tasks: Vec<_> = tasks.iter().filter(|t| t.is_completed).collect();
     
+    let selected_ids = if focused && app.has_selection() { app.get_target_task_ids() } else { Vec::new() };
+
     let mut items: Vec<ListItem> = Vec::new();
     let mut current_index = 0;
     
     // Add active tasks
-    for (_i, task) in active_tasks.iter().enumerate() {
-        let style = if current_index == app.selected_task_index {
+    for task in active_tasks.iter() {
+        let style = if focused && current_index == cursor {
             // Selected task is always blue (focus color)
-            Style::default().fg(BlulocoTheme::FOCUS).add_modifier(Modifier::BOLD)
+            Style::default().fg(app.theme.focus).add_modifier(Modifier::BOLD)
+        } else if selected_ids.contains(&task.id) {
+            Style::default().fg(app.theme.accent).add_modifier(Modifier::REVERSED)
+        } else if task.is_deferred() {
+            Style::default().fg(app.theme.text_muted)
         } else if task.is_overdue() {
-            Style::default().fg(BlulocoTheme::ERROR)
+            Style::default().fg(app.theme.error)
         } else if task.is_due_today() {
-            Style::default().fg(BlulocoTheme::WARNING)
+            Style::default().fg(app.theme.warning)
         } else {
-            Style::default().fg(BlulocoTheme::TEXT_PRIMARY)
+            Style::default().fg(app.theme.text)
         };
 
         let checkbox = "☐";
+        let priority_marker = task.priority.map(|p| p.marker()).unwrap_or("");
         
-        let due_info = if let Some(due_date) = task.due_date {
-            let now = Local::now();
-            let days_until = (due_date.date_naive() - now.date_naive()).num_days();
-            
-            if days_until < 0 {
-                format!(" [Overdue {} days]", -days_until)
-            } else if days_until == 0 {
-                " [Today]".to_string()
-            } else if days_until == 1 {
-                " [Tomorrow]".to_string()
-            } else {
-                format!(" [{} days]", days_until)
-            }
-        } else {
-            String::new()
+        let due_info = match task.due() {
+            Some(due) => format!(" [{}]", date_utils::due_label(due, Local::now())),
+            None => String::new(),
         };
 
-        let frequency_info = if let Some(freq) = &task.recurring_frequency {
-            match freq {
-                crate::models::RecurringFrequency::Daily => " [Daily]".to_string(),
-                crate::models::RecurringFrequency::Weekdays => " [Weekdays]".to_string(),
-                crate::models::RecurringFrequency::Weekly => " [Weekly]".to_string(),
-                crate::models::RecurringFrequency::Monthly => " [Monthly]".to_string(),
-                crate::models::RecurringFrequency::Yearly => " [Yearly]".to_string(),
-            }
-        } else {
-            String::new()
-        };
+        let selection_marker = if selected_ids.contains(&task.id) { "● " } else { "" };
+        let tag_info: String = task.tags.iter().map(|tag| format!(" #{}", tag)).collect();
+
+        let frequency_info = ui::frequency_label(task.recurring_frequency.as_ref());
 
         // Show which list the task belongs to
         let list_name = app.lists
@@ -114,55 +100,44 @@ fn draw_my_day_tasks(f: &mut Frame, app: &App, area: Rect) {
             .map(|l| l.name.as_str())
             .unwrap_or("Unknown");
 
-        let content = format!("{} {} ({}){}{}", checkbox, task.title, list_name, due_info, frequency_info);
+        let start_info = match task.start_date {
+            Some(start) if task.is_deferred() => format!(" [{}]", date_utils::start_label(start, Local::now().date_naive())),
+            _ => String::new(),
+        };
+
+        let time_info = ui::time_label(app, task.id);
+        let content = format!("{}{} {}{} ({}){}{}{}{}{}", selection_marker, checkbox, priority_marker, task.title, list_name, tag_info, start_info, due_info, frequency_info, time_info);
         items.push(ListItem::new(Line::from(Span::styled(content, style))));
         current_index += 1;
     }
     
     // Add separator if there are both active and completed tasks
     if !active_tasks.is_empty() && !completed_tasks.is_empty() {
-        items.push(ListItem::new(Line::from(Span::styled("─────────────── Completed ───────────────", Style::default().fg(BlulocoTheme::TEXT_MUTED)))));
+        items.push(ListItem::new(Line::from(Span::styled("─────────────── Completed ───────────────", Style::default().fg(app.theme.text_muted)))));
         current_index += 1; // Increment index for the separator
     }
     
     // Add completed tasks
-    for (_i, task) in completed_tasks.iter().enumerate() {
-        let style = if current_index == app.selected_task_index {
-            Style::default().fg(BlulocoTheme::FOCUS).add_modifier(Modifier::BOLD)
+    for task in completed_tasks.iter() {
+        let style = if focused && current_index == cursor {
+            Style::default().fg(app.theme.focus).add_modifier(Modifier::BOLD)
+        } else if selected_ids.contains(&task.id) {
+            Style::default().fg(app.theme.accent).add_modifier(Modifier::REVERSED)
         } else {
-            Style::default().fg(BlulocoTheme::TEXT_MUTED)
+            Style::default().fg(app.theme.text_muted)
         };
 
         let checkbox = "☑";
         
-        let due_info = if let Some(due_date) = task.due_date {
-            let now = Local::now();
-            let days_until = (due_date.date_naive() - now.date_naive()).num_days();
-            
-            if days_until < 0 {
-                format!(" [Overdue {} days]", -days_until)
-            } else if days_until == 0 {
-                " [Today]".to_string()
-            } else if days_until == 1 {
-                " [Tomorrow]".to_string()
-            } else {
-                format!(" [{} days]", days_until)
-            }
-        } else {
-            String::new()
+        let due_info = match task.due() {
+            Some(due) => format!(" [{}]", date_utils::due_label(due, Local::now())),
+            None => String::new(),
         };
 
-        let frequency_info = if let Some(freq) = &task.recurring_frequency {
-            match freq {
-                crate::models::RecurringFrequency::Daily => " [Daily]".to_string(),
-                crate::models::RecurringFrequency::Weekdays => " [Weekdays]".to_string(),
-                crate::models::RecurringFrequency::Weekly => " [Weekly]".to_string(),
-                crate::models::RecurringFrequency::Monthly => " [Monthly]".to_string(),
-                crate::models::RecurringFrequency::Yearly => " [Yearly]".to_string(),
-            }
-        } else {
-            String::new()
-        };
+        let selection_marker = if selected_ids.contains(&task.id) { "● " } else { "" };
+        let tag_info: String = task.tags.iter().map(|tag| format!(" #{}", tag)).collect();
+
+        let frequency_info = ui::frequency_label(task.recurring_frequency.as_ref());
 
         // Show which list the task belongs to
         let list_name = app.lists
@@ -172,25 +147,103 @@ fn draw_my_day_tasks(f: &mut Frame, app: &App, area: Rect) {
             .unwrap_or("Unknown");
 
         let completion_info = if let Some(completed_at) = task.completed_at {
-            format!(" [Completed: {}]", completed_at.format("%Y-%m-%d"))
+            format!(" [Completed: {}]", completed_at.format(&app.config.date_format))
         } else {
             String::new()
         };
 
-        let content = format!("{} {} ({}){}{}{}", checkbox, task.title, list_name, due_info, frequency_info, completion_info);
+        let time_info = ui::time_label(app, task.id);
+        let content = format!("{}{} {} ({}){}{}{}{}{}", selection_marker, checkbox, task.title, list_name, tag_info, due_info, frequency_info, completion_info, time_info);
         items.push(ListItem::new(Line::from(Span::styled(content, style))));
         current_index += 1;
     }
 
     let today = Local::now().format("%A, %B %d, %Y");
-    let list_title = format!("My Day - {} ({} active, {} completed)", today, active_tasks.len(), completed_tasks.len());
+    let mut list_title = format!("My Day - {} ({} active, {} completed)", today, active_tasks.len(), completed_tasks.len());
+    if !selected_ids.is_empty() {
+        let mode = if app.visual_anchor.is_some() { "VISUAL, " } else { "" };
+        list_title.push_str(&format!(" [{}{} selected]", mode, selected_ids.len()));
+    }
 
     let list = List::new(items)
-        .block(Block::default()
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(BlulocoTheme::ACCENT_BLUE))
-            .style(Style::default().bg(BlulocoTheme::SURFACE))
-            .title(list_title));
+        .block(pane_block(app, list_title, focused));
+
+    if focused {
+        view.page_height = scroll::visible_rows(area);
+    }
+    scroll::render_list(f, &app.theme, list, area, &mut view.tasks, Some(cursor));
+}
+
+fn draw_suggestions(f: &mut Frame, app: &App, view: &mut ViewState, area: Rect) {
+    let suggestions = app.get_my_day_suggestions();
+    let focused = app.my_day.suggestions_focused;
+    let cursor = if focused { app.selected_task_index } else { app.my_day.inactive_index };
+    let selected_ids = if focused && app.has_selection() { app.get_target_task_ids() } else { Vec::new() };
+    let now = Local::now();
+
+    let items: Vec<ListItem> = if suggestions.is_empty() {
+        vec![ListItem::new(Line::from(Span::styled("No suggestions", Style::default().fg(app.theme.text_muted))))]
+    } else {
+        suggestions
+            .iter()
+            .enumerate()
+            .map(|(i, suggestion)| {
+                let task = suggestion.task;
+                let style = if focused && i == cursor {
+                    Style::default().fg(app.theme.focus).add_modifier(Modifier::BOLD)
+                } else if selected_ids.contains(&task.id) {
+                    Style::default().fg(app.theme.accent).add_modifier(Modifier::REVERSED)
+                } else {
+                    Style::default().fg(app.theme.text)
+                };
+                let reason_color = match suggestion.reason {
+                    SuggestionReason::Overdue => app.theme.error,
+                    SuggestionReason::DueSoon => app.theme.warning,
+                    SuggestionReason::Unfinished(_) | SuggestionReason::Recent => app.theme.text_secondary,
+                };
+
+                let selection_marker = if selected_ids.contains(&task.id) { "● " } else { "" };
+                let priority_marker = task.priority.map(|p| p.marker()).unwrap_or("");
+                let list_name = app.lists.iter().find(|l| l.id == task.list_id).map(|l| l.name.as_str()).unwrap_or("Unknown");
+                let due_info = match task.due() {
+                    Some(due) => format!(" [{}]", date_utils::due_label(due, now)),
+                    None => String::new(),
+                };
+
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{}☐ {}{} ({}){}", selection_marker, priority_marker, task.title, list_name, due_info), style),
+                    Span::styled(format!("  {}", suggestion.reason.label(now.date_naive())), Style::default().fg(reason_color)),
+                ]))
+            })
+            .collect()
+    };
+
+    let mut title = format!("Suggestions ({})", suggestions.len());
+    if !selected_ids.is_empty() {
+        let mode = if app.visual_anchor.is_some() { "VISUAL, " } else { "" };
+        title.push_str(&format!(" [{}{} selected]", mode, selected_ids.len()));
+    }
+
+    let list = List::new(items).block(pane_block(app, title, focused));
+
+    if focused {
+        view.page_height = scroll::visible_rows(area);
+    }
+    let selected = (!suggestions.is_empty()).then_some(cursor);
+    scroll::render_list(f, &app.theme, list, area, &mut view.suggestions, selected);
+}
 
-    f.render_widget(list, area);
+/// Border of the My Day and suggestions panes, in the focus color for the
+/// one with the cursor
+fn pane_block(app: &App, title: String, focused: bool) -> Block<'_> {
+    let (border_color, title_style) = if focused {
+        (app.theme.focus, Style::default().fg(app.theme.focus).add_modifier(Modifier::BOLD))
+    } else {
+        (app.theme.accent, Style::default().fg(app.theme.text))
+    };
+    Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color))
+        .style(Style::default().bg(app.theme.surface))
+        .title(Span::styled(title, title_style))
 }
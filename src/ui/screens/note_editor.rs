@@ -1,5 +1,4 @@
 use crate::app::App;
-use crate::theme::BlulocoTheme;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
@@ -30,11 +29,11 @@ fn draw_title(f: &mut Frame, app: &App, area: Rect) {
     };
 
     let title_widget = Paragraph::new(title)
-        .style(Style::default().fg(BlulocoTheme::FOCUS).add_modifier(Modifier::BOLD))
+        .style(Style::default().fg(app.theme.focus).add_modifier(Modifier::BOLD))
         .block(Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(BlulocoTheme::ACCENT_BLUE))
-            .style(Style::default().bg(BlulocoTheme::SURFACE)));
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface)));
 
     f.render_widget(title_widget, area);
 }
@@ -47,31 +46,31 @@ fn draw_notes(f: &mut Frame, app: &App, area: Rect) {
     };
 
     let style = if app.input_buffer.is_empty() {
-        Style::default().fg(BlulocoTheme::TEXT_MUTED)
+        Style::default().fg(app.theme.text_muted)
     } else {
-        Style::default().fg(BlulocoTheme::TEXT_PRIMARY)
+        Style::default().fg(app.theme.text)
     };
 
     let notes_widget = Paragraph::new(note_text)
         .style(style)
         .block(Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(BlulocoTheme::ACCENT_BLUE))
-            .style(Style::default().bg(BlulocoTheme::SURFACE))
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface))
             .title("Notes"));
 
     f.render_widget(notes_widget, area);
 }
 
-fn draw_help(f: &mut Frame, _app: &App, area: Rect) {
+fn draw_help(f: &mut Frame, app: &App, area: Rect) {
     let help_text = "Type your notes and press Enter to save.\nPress Esc to cancel.";
     
     let help_widget = Paragraph::new(help_text)
-        .style(Style::default().fg(BlulocoTheme::TEXT_SECONDARY))
+        .style(Style::default().fg(app.theme.text_secondary))
         .block(Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(BlulocoTheme::ACCENT_BLUE))
-            .style(Style::default().bg(BlulocoTheme::SURFACE))
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface))
             .title("Help"));
 
     f.render_widget(help_widget, area);
@@ -0,0 +1,120 @@
+use crate::app::App;
+use crate::handlers::actions;
+use crate::utils::quick_add::{self, QuickAdd};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Input field
+            Constraint::Length(8), // Preview of the parsed task
+            Constraint::Min(0),    // Help text
+        ])
+        .split(area);
+
+    // The preview is parsed afresh on every frame, so it follows the typing
+    let parsed = quick_add::parse(&app.input_buffer, app.config.week_start, app.config.date_order);
+    draw_input(f, app, chunks[0]);
+    draw_preview(f, app, &parsed, chunks[1]);
+    draw_help(f, app, chunks[2]);
+}
+
+fn draw_input(f: &mut Frame, app: &App, area: Rect) {
+    let (input_text, style) = if app.input_buffer.is_empty() {
+        ("Describe the task...".to_string(), Style::default().fg(app.theme.text_muted))
+    } else {
+        (app.input_buffer.clone(), Style::default().fg(app.theme.text))
+    };
+
+    let input_widget = Paragraph::new(input_text)
+        .style(style)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface))
+            .title("Quick Add"));
+
+    f.render_widget(input_widget, area);
+}
+
+fn draw_preview(f: &mut Frame, app: &App, parsed: &QuickAdd, area: Rect) {
+    let value_style = Style::default().fg(app.theme.text);
+    let unset = || Span::styled("-", Style::default().fg(app.theme.text_muted));
+
+    let title = if parsed.title.is_empty() {
+        Span::styled("A task needs a title", Style::default().fg(app.theme.error))
+    } else {
+        Span::styled(parsed.title.clone(), value_style.add_modifier(Modifier::BOLD))
+    };
+
+    let due = match parsed.due_date {
+        Some(due) if due.has_time => Span::styled(format!("{} {}", due.at.format(&app.config.date_format), due.at.format("%H:%M")), value_style),
+        Some(due) => Span::styled(format!("{} (all day)", due.at.format(&app.config.date_format)), value_style),
+        None => unset(),
+    };
+
+    let repeats = match &parsed.frequency {
+        Some(frequency) => Span::styled(frequency.label(), value_style),
+        None => unset(),
+    };
+
+    let tags = if parsed.tags.is_empty() {
+        unset()
+    } else {
+        Span::styled(parsed.tags.iter().map(|tag| format!("#{}", tag)).collect::<Vec<_>>().join(" "), value_style)
+    };
+
+    let priority = match parsed.priority {
+        Some(priority) => Span::styled(priority.name(), value_style),
+        None => unset(),
+    };
+
+    let list = match actions::quick_add_list(app, parsed) {
+        Ok(list_id) => {
+            let name = app.lists.iter().find(|l| l.id == list_id).map(|l| l.name.clone()).unwrap_or_default();
+            Span::styled(name, value_style)
+        }
+        Err(err) => Span::styled(format!("{:#}", err), Style::default().fg(app.theme.error)),
+    };
+
+    let label_style = Style::default().fg(app.theme.text_secondary);
+    let lines: Vec<Line> = [("Title", title), ("Due", due), ("Repeats", repeats), ("Tags", tags), ("Priority", priority), ("List", list)]
+        .into_iter()
+        .map(|(label, value)| Line::from(vec![Span::styled(format!("{:<10}", label), label_style), value]))
+        .collect();
+
+    let preview_widget = Paragraph::new(lines)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface))
+            .title("Preview"));
+
+    f.render_widget(preview_widget, area);
+}
+
+fn draw_help(f: &mut Frame, app: &App, area: Rect) {
+    let help_text = "Type the task with any of: a due date (tomorrow, next fri, in 3 days, 2024-05-01), a time (3pm, 15:30), \
+        a repeat (every day, every weekday, every week, every month, every year), #tags, \
+        a priority (!high, !medium, !low) and a list (@Work).\n\
+        Press Enter to add the task, or Esc to cancel.";
+
+    let help_widget = Paragraph::new(help_text)
+        .style(Style::default().fg(app.theme.text_secondary))
+        .wrap(Wrap { trim: true })
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface))
+            .title("Help"));
+
+    f.render_widget(help_widget, area);
+}
+
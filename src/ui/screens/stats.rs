@@ -0,0 +1,262 @@
+use crate::app::App;
+use crate::models::Task;
+use crate::utils::stats::{self, DAYS, WEEKS};
+use chrono::{Duration, Local};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    symbols,
+    text::{Line, Span},
+    widgets::{Axis, Bar, BarChart, BarGroup, Block, Borders, Chart, Dataset, GraphType, Paragraph, Sparkline},
+    Frame,
+};
+
+/// Most days the daily counts go back
+const MAX_DAYS: usize = 120;
+
+pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+    let tasks: Vec<&Task> = app.tasks.iter().chain(&app.archived_tasks).collect();
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),      // Totals
+            Constraint::Percentage(33), // Completions over time
+            Constraint::Percentage(33), // Overdue trend and weekdays
+            Constraint::Min(6),         // Lists and streaks
+        ])
+        .split(area);
+    let halves = |area: Rect| {
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(area)
+    };
+
+    draw_totals(f, app, &tasks, rows[0]);
+    let top = halves(rows[1]);
+    draw_daily(f, app, &tasks, top[0]);
+    draw_weekly(f, app, &tasks, top[1]);
+    let middle = halves(rows[2]);
+    draw_overdue(f, app, &tasks, middle[0]);
+    draw_weekdays(f, app, &tasks, middle[1]);
+    let bottom = halves(rows[3]);
+    draw_completion_times(f, app, &tasks, bottom[0]);
+    draw_streaks(f, app, &tasks, bottom[1]);
+}
+
+fn block<'a>(app: &App, title: &'a str) -> Block<'a> {
+    Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent))
+        .style(Style::default().bg(app.theme.surface))
+        .title(title)
+}
+
+fn draw_totals(f: &mut Frame, app: &App, tasks: &[&Task], area: Rect) {
+    let today = Local::now().date_naive();
+    let open = app.tasks.iter().filter(|t| !t.is_completed).count();
+    let overdue = app.tasks.iter().filter(|t| t.is_overdue()).count();
+    let completed = tasks.iter().filter(|t| t.is_completed).count();
+    let done_today = stats::completed_per_day(tasks, today, 1)[0];
+    let done_this_week = stats::completed_per_week(tasks, today, app.config.week_start, 1)[0];
+
+    let figure = |label: &str, value: String, color| {
+        vec![
+            Span::styled(format!("{}: ", label), Style::default().fg(app.theme.text_secondary)),
+            Span::styled(value, Style::default().fg(color).add_modifier(Modifier::BOLD)),
+            Span::raw("   "),
+        ]
+    };
+    let line: Vec<Span> = [
+        figure("Open", open.to_string(), app.theme.text),
+        figure("Overdue", overdue.to_string(), app.theme.error),
+        figure("Completed", completed.to_string(), app.theme.text),
+        figure("Today", done_today.to_string(), app.theme.focus),
+        figure("This week", done_this_week.to_string(), app.theme.focus),
+    ]
+    .concat();
+
+    f.render_widget(Paragraph::new(Line::from(line)).block(block(app, "Totals")), area);
+}
+
+/// Completions over the last days, with the tasks created for comparison.
+/// A wider screen shows more days, one per column.
+fn draw_daily(f: &mut Frame, app: &App, tasks: &[&Task], area: Rect) {
+    let today = Local::now().date_naive();
+    let days = (area.width.saturating_sub(2) as usize).clamp(DAYS, MAX_DAYS);
+    let completed = stats::completed_per_day(tasks, today, days);
+    let created = stats::created_per_day(tasks, today, days);
+    let title = format!(
+        "Last {} days: {} completed, {} created",
+        days,
+        completed.iter().sum::<u64>(),
+        created.iter().sum::<u64>()
+    );
+
+    let outer = block(app, &title);
+    let inner = outer.inner(area);
+    f.render_widget(outer, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Ratio(1, 2), Constraint::Length(1), Constraint::Ratio(1, 2)])
+        .split(inner);
+    // Both use the same scale so they can be compared
+    let max = completed.iter().chain(&created).copied().max().unwrap_or(0).max(1);
+    let label = |text: &'static str| Paragraph::new(text).style(Style::default().fg(app.theme.text_secondary));
+
+    f.render_widget(label("Completed"), rows[0]);
+    f.render_widget(Sparkline::default().data(&completed).max(max).style(Style::default().fg(app.theme.focus)), rows[1]);
+    f.render_widget(label("Created"), rows[2]);
+    f.render_widget(Sparkline::default().data(&created).max(max).style(Style::default().fg(app.theme.text_muted)), rows[3]);
+}
+
+fn draw_weekly(f: &mut Frame, app: &App, tasks: &[&Task], area: Rect) {
+    let today = Local::now().date_naive();
+    let this_week = stats::start_of_week(today, app.config.week_start);
+    let counts = stats::completed_per_week(tasks, today, app.config.week_start, WEEKS);
+
+    let bars: Vec<Bar> = counts
+        .iter()
+        .enumerate()
+        .map(|(i, &count)| {
+            let week = this_week - Duration::weeks((WEEKS - 1 - i) as i64);
+            Bar::default().value(count).label(Line::from(week.format("%-d/%-m").to_string()))
+        })
+        .collect();
+
+    // Bars as wide as the space allows, with room for the date under them
+    let bar_width = (area.width.saturating_sub(2) / WEEKS as u16).saturating_sub(1).max(1);
+    let chart = BarChart::default()
+        .block(block(app, "Completed per week"))
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(bar_width)
+        .bar_gap(1)
+        .bar_style(Style::default().fg(app.theme.accent))
+        .value_style(Style::default().fg(app.theme.surface).bg(app.theme.accent))
+        .label_style(Style::default().fg(app.theme.text_secondary));
+    f.render_widget(chart, area);
+}
+
+fn draw_overdue(f: &mut Frame, app: &App, tasks: &[&Task], area: Rect) {
+    let today = Local::now().date_naive();
+    let counts = stats::overdue_per_day(tasks, today, DAYS);
+    let points: Vec<(f64, f64)> = counts.iter().enumerate().map(|(i, &count)| (i as f64, count as f64)).collect();
+    let max = counts.iter().copied().max().unwrap_or(0).max(1);
+    let first_day = today - Duration::days(DAYS as i64 - 1);
+
+    let datasets = vec![Dataset::default()
+        .marker(symbols::Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(app.theme.error))
+        .data(&points)];
+    let label_style = Style::default().fg(app.theme.text_secondary);
+    let chart = Chart::new(datasets)
+        .block(block(app, "Overdue tasks"))
+        .x_axis(Axis::default()
+            .bounds([0.0, (DAYS - 1) as f64])
+            .style(label_style)
+            .labels(vec![
+                Span::raw(first_day.format("%b %-d").to_string()),
+                Span::raw(today.format("%b %-d").to_string()),
+            ]))
+        .y_axis(Axis::default()
+            .bounds([0.0, max as f64])
+            .style(label_style)
+            .labels(vec![Span::raw("0"), Span::raw(max.to_string())]));
+    f.render_widget(chart, area);
+}
+
+fn draw_weekdays(f: &mut Frame, app: &App, tasks: &[&Task], area: Rect) {
+    let counts = stats::completed_per_weekday(tasks, app.config.week_start);
+    let busiest = counts.iter().copied().max().unwrap_or(0);
+    let mut weekday = app.config.week_start;
+
+    let bars: Vec<Bar> = counts
+        .iter()
+        .map(|&count| {
+            let color = if count == busiest && count > 0 { app.theme.warning } else { app.theme.accent };
+            let bar = Bar::default()
+                .value(count)
+                .label(Line::from(weekday.to_string()))
+                .style(Style::default().fg(color));
+            weekday = weekday.succ();
+            bar
+        })
+        .collect();
+
+    let bar_width = (area.width.saturating_sub(2) / 7).saturating_sub(1).max(1);
+    let chart = BarChart::default()
+        .block(block(app, "Completions by weekday"))
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(bar_width)
+        .bar_gap(1)
+        .value_style(Style::default().fg(app.theme.surface).bg(app.theme.accent))
+        .label_style(Style::default().fg(app.theme.text_secondary));
+    f.render_widget(chart, area);
+}
+
+/// Average time from creating a task to completing it, one bar per list
+fn draw_completion_times(f: &mut Frame, app: &App, tasks: &[&Task], area: Rect) {
+    let averages = stats::average_completion_times(tasks);
+    let bars: Vec<Bar> = app
+        .lists
+        .iter()
+        .filter_map(|list| {
+            let average = averages.get(&list.id)?;
+            Some(Bar::default()
+                .value(average.num_hours().max(0) as u64)
+                .text_value(duration_label(*average))
+                .label(Line::from(list.name.clone())))
+        })
+        .collect();
+
+    let chart = BarChart::default()
+        .block(block(app, "Average time to complete, by list"))
+        .direction(Direction::Horizontal)
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(1)
+        .bar_gap(0)
+        .bar_style(Style::default().fg(app.theme.accent))
+        .value_style(Style::default().fg(app.theme.surface).bg(app.theme.accent))
+        .label_style(Style::default().fg(app.theme.text_secondary));
+    f.render_widget(chart, area);
+}
+
+fn draw_streaks(f: &mut Frame, app: &App, tasks: &[&Task], area: Rect) {
+    let streaks = stats::recurring_streaks(tasks, Local::now());
+    let bars: Vec<Bar> = streaks
+        .iter()
+        .map(|streak| {
+            Bar::default()
+                .value(streak.current as u64)
+                .text_value(format!("{} (best {})", streak.current, streak.best))
+                .label(Line::from(streak.title.clone()))
+        })
+        .collect();
+
+    let chart = BarChart::default()
+        .block(block(app, "Streaks of repeating tasks"))
+        .direction(Direction::Horizontal)
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(1)
+        .bar_gap(0)
+        // Streaks are drawn against the longest ever, so a broken one still shows
+        .max(streaks.iter().map(|streak| streak.best as u64).max().unwrap_or(0).max(1))
+        .bar_style(Style::default().fg(app.theme.warning))
+        .value_style(Style::default().fg(app.theme.surface).bg(app.theme.warning))
+        .label_style(Style::default().fg(app.theme.text_secondary));
+    f.render_widget(chart, area);
+}
+
+/// A duration in the largest unit that fits, e.g. "3d" or "5h"
+fn duration_label(duration: Duration) -> String {
+    if duration.num_days() >= 1 {
+        format!("{:.1}d", duration.num_hours() as f64 / 24.0)
+    } else if duration.num_hours() >= 1 {
+        format!("{}h", duration.num_hours())
+    } else {
+        format!("{}m", duration.num_minutes())
+    }
+}
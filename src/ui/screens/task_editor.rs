@@ -1,5 +1,4 @@
 use crate::app::App;
-use crate::theme::BlulocoTheme;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
@@ -31,11 +30,11 @@ fn draw_title(f: &mut Frame, app: &App, area: Rect) {
     };
 
     let title_widget = Paragraph::new(title)
-        .style(Style::default().fg(BlulocoTheme::FOCUS).add_modifier(Modifier::BOLD))
+        .style(Style::default().fg(app.theme.focus).add_modifier(Modifier::BOLD))
         .block(Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(BlulocoTheme::ACCENT_BLUE))
-            .style(Style::default().bg(BlulocoTheme::SURFACE)));
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface)));
 
     f.render_widget(title_widget, area);
 }
@@ -43,17 +42,17 @@ fn draw_title(f: &mut Frame, app: &App, area: Rect) {
 fn draw_field_label(f: &mut Frame, app: &App, area: Rect) {
     let field_name = match app.task_editor_state {
         crate::app::TaskEditorState::Title => "Task Title",
-        crate::app::TaskEditorState::DueDate => "Due Date (YYYY-MM-DD, 'today', 'tomorrow', etc.)",
+        crate::app::TaskEditorState::DueDate => "Due Date (YYYY-MM-DD, 'tomorrow', 'next fri 5pm', 'in 3 days', etc.)",
         crate::app::TaskEditorState::Frequency => "Frequency (daily, weekdays, weekly, monthly, yearly, or 'none')",
         crate::app::TaskEditorState::Notes => "Notes (optional)",
     };
 
     let label_widget = Paragraph::new(field_name)
-        .style(Style::default().fg(BlulocoTheme::TEXT_PRIMARY))
+        .style(Style::default().fg(app.theme.text))
         .block(Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(BlulocoTheme::ACCENT_BLUE))
-            .style(Style::default().bg(BlulocoTheme::SURFACE)));
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface)));
 
     f.render_widget(label_widget, area);
 }
@@ -73,17 +72,17 @@ fn draw_input(f: &mut Frame, app: &App, area: Rect) {
     };
 
     let style = if app.input_buffer.is_empty() {
-        Style::default().fg(BlulocoTheme::TEXT_MUTED)
+        Style::default().fg(app.theme.text_muted)
     } else {
-        Style::default().fg(BlulocoTheme::TEXT_PRIMARY)
+        Style::default().fg(app.theme.text)
     };
 
     let input_widget = Paragraph::new(input_text)
         .style(style)
         .block(Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(BlulocoTheme::ACCENT_BLUE))
-            .style(Style::default().bg(BlulocoTheme::SURFACE))
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface))
             .title("Input"));
 
     f.render_widget(input_widget, area);
@@ -92,17 +91,17 @@ fn draw_input(f: &mut Frame, app: &App, area: Rect) {
 fn draw_help(f: &mut Frame, app: &App, area: Rect) {
     let help_text = match app.task_editor_state {
         crate::app::TaskEditorState::Title => "Enter the task title and press Enter to continue.\nPress Esc to cancel.",
-        crate::app::TaskEditorState::DueDate => "Enter due date (YYYY-MM-DD) or relative date like 'today', 'tomorrow'.\nPress Enter to skip or continue to next field.",
+        crate::app::TaskEditorState::DueDate => "Enter due date (YYYY-MM-DD) or relative date like 'tomorrow', 'friday 5pm', 'in 3 days' or 'eom'.\nPress Enter to skip or continue to next field.",
         crate::app::TaskEditorState::Frequency => "Enter frequency: 'daily', 'weekdays', 'weekly', 'monthly', 'yearly', or 'none'.\nPress Enter to skip or continue to next field.",
         crate::app::TaskEditorState::Notes => "Enter optional notes for this task.\nPress Enter to save the task or skip notes.",
     };
     
     let help_widget = Paragraph::new(help_text)
-        .style(Style::default().fg(BlulocoTheme::TEXT_SECONDARY))
+        .style(Style::default().fg(app.theme.text_secondary))
         .block(Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(BlulocoTheme::ACCENT_BLUE))
-            .style(Style::default().bg(BlulocoTheme::SURFACE))
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface))
             .title("Help"));
 
     f.render_widget(help_widget, area);
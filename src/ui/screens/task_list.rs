@@ -1,5 +1,7 @@
-use crate::app::App;
-use crate::theme::BlulocoTheme;
+use crate::app::{App, ListFilter, SidebarItem, TaskSort};
+use crate::ui::scroll::{self, ViewState};
+use crate::ui::{self, sidebar};
+use crate::utils::date_utils;
 use chrono::Local;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -9,179 +11,152 @@ use ratatui::{
     Frame,
 };
 
-pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+pub fn draw(f: &mut Frame, app: &App, view: &mut ViewState, area: Rect) {
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
+        .constraints([
+            Constraint::Percentage(app.config.sidebar_width),
+            Constraint::Percentage(100 - app.config.sidebar_width),
+        ])
         .split(area);
 
-    draw_lists(f, app, chunks[0]);
-    draw_tasks(f, app, chunks[1]);
+    draw_lists(f, app, view, chunks[0]);
+    draw_tasks(f, app, view, chunks[1]);
 }
 
-fn draw_lists(f: &mut Frame, app: &App, area: Rect) {
-    let items: Vec<ListItem> = app
-        .lists
-        .iter()
-        .map(|list| {
-            let style = if Some(list.id) == app.current_list_id {
-                Style::default().fg(BlulocoTheme::FOCUS).add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(BlulocoTheme::TEXT_PRIMARY)
-            };
-
-            let task_count = app.tasks.iter().filter(|t| t.list_id == list.id).count();
-            let completed_count = app.tasks.iter().filter(|t| t.list_id == list.id && t.is_completed).count();
-            
-            let content = if task_count > 0 {
-                format!("{} ({}/{})", list.name, completed_count, task_count)
-            } else {
-                list.name.clone()
-            };
-
-            ListItem::new(Line::from(Span::styled(content, style)))
-        })
-        .collect();
+fn draw_lists(f: &mut Frame, app: &App, view: &mut ViewState, area: Rect) {
+    let items: Vec<ListItem> = sidebar::build_items(app, |item, _| {
+        app.current_list_id.is_some_and(|id| *item == SidebarItem::List(id))
+    });
 
     let list = List::new(items)
         .block(Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(BlulocoTheme::ACCENT_BLUE))
-            .style(Style::default().bg(BlulocoTheme::SURFACE))
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface))
             .title("Lists"));
 
-    f.render_widget(list, area);
+    scroll::render_list(f, &app.theme, list, area, &mut view.sidebar, sidebar::current_list_row(app));
 }
 
-fn draw_tasks(f: &mut Frame, app: &App, area: Rect) {
+fn draw_tasks(f: &mut Frame, app: &App, view: &mut ViewState, area: Rect) {
     let tasks = app.get_current_list_tasks();
     
     // Separate active and completed tasks
     let active_tasks: Vec<_> = tasks.iter().filter(|t| !t.is_completed).collect();
     let completed_tasks: Vec<_> = tasks.iter().filter(|t| t.is_completed).collect();
     
+    let selected_ids = if app.has_selection() { app.get_target_task_ids() } else { Vec::new() };
+
     let mut items: Vec<ListItem> = Vec::new();
     let mut current_index = 0;
     
     // Add active tasks
-    for (_i, task) in active_tasks.iter().enumerate() {
+    for task in active_tasks.iter() {
         let style = if current_index == app.selected_task_index {
             // Selected task is always blue (focus color)
-            Style::default().fg(BlulocoTheme::FOCUS).add_modifier(Modifier::BOLD)
+            Style::default().fg(app.theme.focus).add_modifier(Modifier::BOLD)
+        } else if selected_ids.contains(&task.id) {
+            Style::default().fg(app.theme.accent).add_modifier(Modifier::REVERSED)
+        } else if task.is_deferred() {
+            Style::default().fg(app.theme.text_muted)
         } else if task.is_overdue() {
-            Style::default().fg(BlulocoTheme::ERROR)
+            Style::default().fg(app.theme.error)
         } else if task.is_due_today() {
-            Style::default().fg(BlulocoTheme::WARNING)
+            Style::default().fg(app.theme.warning)
         } else {
-            Style::default().fg(BlulocoTheme::TEXT_PRIMARY)
+            Style::default().fg(app.theme.text)
         };
 
         let checkbox = "☐";
         let my_day_indicator = if task.is_in_my_day { "⭐ " } else { "" };
+        let priority_marker = task.priority.map(|p| p.marker()).unwrap_or("");
         
-        let due_info = if let Some(due_date) = task.due_date {
-            let now = Local::now();
-            let days_until = (due_date.date_naive() - now.date_naive()).num_days();
-            
-            if days_until < 0 {
-                format!(" [Overdue {} days]", -days_until)
-            } else if days_until == 0 {
-                " [Today]".to_string()
-            } else if days_until == 1 {
-                " [Tomorrow]".to_string()
-            } else {
-                format!(" [{} days]", days_until)
-            }
-        } else {
-            String::new()
+        let due_info = match task.due() {
+            Some(due) => format!(" [{}]", date_utils::due_label(due, Local::now())),
+            None => String::new(),
         };
 
-        let frequency_info = if let Some(freq) = &task.recurring_frequency {
-            match freq {
-                crate::models::RecurringFrequency::Daily => " [Daily]".to_string(),
-                crate::models::RecurringFrequency::Weekdays => " [Weekdays]".to_string(),
-                crate::models::RecurringFrequency::Weekly => " [Weekly]".to_string(),
-                crate::models::RecurringFrequency::Monthly => " [Monthly]".to_string(),
-                crate::models::RecurringFrequency::Yearly => " [Yearly]".to_string(),
-            }
-        } else {
-            String::new()
+        let selection_marker = if selected_ids.contains(&task.id) { "● " } else { "" };
+        let tag_info: String = task.tags.iter().map(|tag| format!(" #{}", tag)).collect();
+
+        let frequency_info = ui::frequency_label(task.recurring_frequency.as_ref());
+
+        let start_info = match task.start_date {
+            Some(start) if task.is_deferred() => format!(" [{}]", date_utils::start_label(start, Local::now().date_naive())),
+            _ => String::new(),
         };
 
-        let content = format!("{} {}{}{}{}", checkbox, my_day_indicator, task.title, due_info, frequency_info);
+        let time_info = ui::time_label(app, task.id);
+        let content = format!("{}{} {}{}{}{}{}{}{}{}", selection_marker, checkbox, my_day_indicator, priority_marker, task.title, tag_info, start_info, due_info, frequency_info, time_info);
         items.push(ListItem::new(Line::from(Span::styled(content, style))));
         current_index += 1;
     }
     
     // Add separator if there are both active and completed tasks
     if !active_tasks.is_empty() && !completed_tasks.is_empty() {
-        items.push(ListItem::new(Line::from(Span::styled("─────────────── Completed ───────────────", Style::default().fg(BlulocoTheme::TEXT_MUTED)))));
+        items.push(ListItem::new(Line::from(Span::styled("─────────────── Completed ───────────────", Style::default().fg(app.theme.text_muted)))));
         current_index += 1; // Increment index for the separator
     }
     
     // Add completed tasks
-    for (_i, task) in completed_tasks.iter().enumerate() {
+    for task in completed_tasks.iter() {
         let style = if current_index == app.selected_task_index {
-            Style::default().fg(BlulocoTheme::FOCUS).add_modifier(Modifier::BOLD)
+            Style::default().fg(app.theme.focus).add_modifier(Modifier::BOLD)
+        } else if selected_ids.contains(&task.id) {
+            Style::default().fg(app.theme.accent).add_modifier(Modifier::REVERSED)
         } else {
-            Style::default().fg(BlulocoTheme::TEXT_MUTED)
+            Style::default().fg(app.theme.text_muted)
         };
 
         let checkbox = "☑";
         let my_day_indicator = if task.is_in_my_day { "⭐ " } else { "" };
         
-        let due_info = if let Some(due_date) = task.due_date {
-            let now = Local::now();
-            let days_until = (due_date.date_naive() - now.date_naive()).num_days();
-            
-            if days_until < 0 {
-                format!(" [Overdue {} days]", -days_until)
-            } else if days_until == 0 {
-                " [Today]".to_string()
-            } else if days_until == 1 {
-                " [Tomorrow]".to_string()
-            } else {
-                format!(" [{} days]", days_until)
-            }
-        } else {
-            String::new()
+        let due_info = match task.due() {
+            Some(due) => format!(" [{}]", date_utils::due_label(due, Local::now())),
+            None => String::new(),
         };
 
-        let frequency_info = if let Some(freq) = &task.recurring_frequency {
-            match freq {
-                crate::models::RecurringFrequency::Daily => " [Daily]".to_string(),
-                crate::models::RecurringFrequency::Weekdays => " [Weekdays]".to_string(),
-                crate::models::RecurringFrequency::Weekly => " [Weekly]".to_string(),
-                crate::models::RecurringFrequency::Monthly => " [Monthly]".to_string(),
-                crate::models::RecurringFrequency::Yearly => " [Yearly]".to_string(),
-            }
-        } else {
-            String::new()
-        };
+        let selection_marker = if selected_ids.contains(&task.id) { "● " } else { "" };
+        let tag_info: String = task.tags.iter().map(|tag| format!(" #{}", tag)).collect();
+
+        let frequency_info = ui::frequency_label(task.recurring_frequency.as_ref());
 
         let completion_info = if let Some(completed_at) = task.completed_at {
-            format!(" [Completed: {}]", completed_at.format("%Y-%m-%d"))
+            format!(" [Completed: {}]", completed_at.format(&app.config.date_format))
         } else {
             String::new()
         };
 
-        let content = format!("{} {}{}{}{}{}", checkbox, my_day_indicator, task.title, due_info, frequency_info, completion_info);
+        let time_info = ui::time_label(app, task.id);
+        let content = format!("{}{} {}{}{}{}{}{}{}", selection_marker, checkbox, my_day_indicator, task.title, tag_info, due_info, frequency_info, completion_info, time_info);
         items.push(ListItem::new(Line::from(Span::styled(content, style))));
         current_index += 1;
     }
 
-    let list_title = if let Some(list) = app.get_current_list() {
+    let mut list_title = if let Some(list) = app.get_current_list() {
         format!("Tasks in {} ({} active, {} completed)", list.name, active_tasks.len(), completed_tasks.len())
     } else {
         "Tasks".to_string()
     };
+    if app.task_sort != TaskSort::Manual {
+        list_title.push_str(&format!(" [Sorted by {}]", app.task_sort.name()));
+    }
+    if app.list_filter != ListFilter::Actionable {
+        list_title.push_str(&format!(" [Showing {}]", app.list_filter.name()));
+    }
+    if app.has_selection() {
+        let mode = if app.visual_anchor.is_some() { "VISUAL, " } else { "" };
+        list_title.push_str(&format!(" [{}{} selected]", mode, selected_ids.len()));
+    }
 
     let list = List::new(items)
         .block(Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(BlulocoTheme::ACCENT_BLUE))
-            .style(Style::default().bg(BlulocoTheme::SURFACE))
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface))
             .title(list_title));
 
-    f.render_widget(list, area);
+    view.page_height = scroll::visible_rows(area);
+    scroll::render_list(f, &app.theme, list, area, &mut view.tasks, Some(app.selected_task_index));
 }
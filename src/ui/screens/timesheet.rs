@@ -0,0 +1,92 @@
+use crate::app::App;
+use crate::action::Action;
+use crate::keymap::Screen;
+use crate::ui::scroll::{self, ViewState};
+use crate::utils::stats;
+use crate::utils::timesheet::{self, TimesheetDay};
+use chrono::{Duration, Local};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem},
+    Frame,
+};
+
+pub fn draw(f: &mut Frame, app: &App, view: &mut ViewState, area: Rect) {
+    let days = app.get_timesheet();
+    let running_task_id = app.running_timer().map(|entry| entry.task_id);
+
+    let mut rows: Vec<(String, Style)> = Vec::new();
+    for day in &days {
+        rows.push((
+            row(day.date.format("%A, %B %-d").to_string(), day.total, 0),
+            Style::default().fg(app.theme.accent).add_modifier(Modifier::BOLD),
+        ));
+        for list in &day.lists {
+            let name = list
+                .list_id
+                .and_then(|id| app.lists.iter().chain(&app.trash_lists).find(|l| l.id == id))
+                .map(|l| l.name.clone())
+                .unwrap_or_else(|| "Deleted tasks".to_string());
+            rows.push((row(name, list.total, 2), Style::default().fg(app.theme.text)));
+            for &(task_id, duration) in &list.tasks {
+                let task = app.find_any_task(task_id);
+                let running = if running_task_id == Some(task_id) { "▶ " } else { "" };
+                let title = task.map(|t| t.title.as_str()).unwrap_or("Deleted task");
+                let color = if task.is_some_and(|t| t.is_completed) { app.theme.text_muted } else { app.theme.text_secondary };
+                rows.push((row(format!("{}{}", running, title), duration, 4), Style::default().fg(color)));
+            }
+        }
+    }
+
+    let items: Vec<ListItem> = if rows.is_empty() {
+        let key = app.keymap.keys_for(Screen::TaskList, Action::ToggleTimer).unwrap_or_else(|| ":toggle_timer".to_string());
+        let hint = format!("No time tracked yet. Press {} on a task to start its timer", key);
+        vec![ListItem::new(Line::from(Span::styled(hint, Style::default().fg(app.theme.text_muted))))]
+    } else {
+        rows.into_iter()
+            .enumerate()
+            .map(|(i, (text, style))| {
+                let style = if i == app.selected_task_index {
+                    Style::default().fg(app.theme.focus).add_modifier(Modifier::BOLD)
+                } else {
+                    style
+                };
+                ListItem::new(Line::from(Span::styled(text, style)))
+            })
+            .collect()
+    };
+
+    let list = List::new(items)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface))
+            .title(title(app, &days)));
+
+    view.page_height = scroll::visible_rows(area);
+    let selected = (!days.is_empty()).then_some(app.selected_task_index);
+    scroll::render_list(f, &app.theme, list, area, &mut view.timesheet, selected);
+}
+
+/// A row with its time after it, indented to show what it belongs to
+fn row(label: String, duration: Duration, indent: usize) -> String {
+    format!("{:indent$}{}  {}", "", label, timesheet::duration_label(duration), indent = indent)
+}
+
+/// Time tracked today and this week
+fn title(app: &App, days: &[TimesheetDay]) -> String {
+    let today = Local::now().date_naive();
+    let week_start = stats::start_of_week(today, app.config.week_start);
+    let total = |from| {
+        days.iter()
+            .filter(|day| day.date >= from)
+            .fold(Duration::zero(), |total, day| total + day.total)
+    };
+    format!(
+        "Timesheet - today {}, this week {}",
+        timesheet::duration_label(total(today)),
+        timesheet::duration_label(total(week_start))
+    )
+}
@@ -0,0 +1,103 @@
+use crate::app::{App, TrashItem};
+use crate::ui::scroll::{self, ViewState};
+use chrono::Local;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+
+pub fn draw(f: &mut Frame, app: &App, view: &mut ViewState, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Title
+            Constraint::Min(0),    // Trashed items
+        ])
+        .split(area);
+
+    draw_title(f, app, chunks[0]);
+    draw_items(f, app, view, chunks[1]);
+}
+
+fn draw_title(f: &mut Frame, app: &App, area: Rect) {
+    let retention = if app.config.trash_retention_days == 0 {
+        "Items are kept until purged".to_string()
+    } else {
+        format!("Items are purged automatically after {} days", app.config.trash_retention_days)
+    };
+
+    let title_widget = Paragraph::new(retention)
+        .style(Style::default().fg(app.theme.text_secondary))
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface)));
+
+    f.render_widget(title_widget, area);
+}
+
+fn draw_items(f: &mut Frame, app: &App, view: &mut ViewState, area: Rect) {
+    let trash_items = app.get_trash_items();
+    let deleted_format = format!("{} %H:%M", app.config.date_format);
+
+    let items: Vec<ListItem> = trash_items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let style = if i == app.selected_task_index {
+                Style::default().fg(app.theme.focus).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(app.theme.text)
+            };
+
+            let (content, deleted_at) = match item {
+                TrashItem::List(list_id) => {
+                    let Some(list) = app.trash_lists.iter().find(|l| l.id == *list_id) else {
+                        return ListItem::new("");
+                    };
+                    let task_count = app
+                        .trash_tasks
+                        .iter()
+                        .filter(|t| t.list_id == list.id && t.deleted_at == list.deleted_at)
+                        .count();
+                    (format!("[List] {} ({} tasks)", list.name, task_count), list.deleted_at)
+                }
+                TrashItem::Task(task_id) => {
+                    let Some(task) = app.trash_tasks.iter().find(|t| t.id == *task_id) else {
+                        return ListItem::new("");
+                    };
+                    let list_name = app
+                        .lists
+                        .iter()
+                        .chain(&app.trash_lists)
+                        .find(|l| l.id == task.list_id)
+                        .map(|l| l.name.as_str())
+                        .unwrap_or("Unknown");
+                    (format!("{} ({})", task.title, list_name), task.deleted_at)
+                }
+            };
+
+            let deleted_info = deleted_at
+                .map(|d| format!(" [Deleted: {}]", d.with_timezone(&Local).format(&deleted_format)))
+                .unwrap_or_default();
+
+            ListItem::new(Line::from(vec![
+                Span::styled(content, style),
+                Span::styled(deleted_info, Style::default().fg(app.theme.text_muted)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface))
+            .title(format!("Trash ({} items)", trash_items.len())));
+
+    view.page_height = scroll::visible_rows(area);
+    scroll::render_list(f, &app.theme, list, area, &mut view.trash, Some(app.selected_task_index));
+}
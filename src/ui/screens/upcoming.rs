@@ -0,0 +1,143 @@
+use crate::app::{App, UpcomingEntry};
+use crate::ui::scroll::{self, ViewState};
+use crate::ui;
+use crate::utils::date_utils;
+use chrono::Local;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem},
+    Frame,
+};
+
+pub fn draw(f: &mut Frame, app: &App, view: &mut ViewState, area: Rect) {
+    let groups = app.get_upcoming_groups();
+    let selected_ids = if app.has_selection() { app.get_target_task_ids() } else { Vec::new() };
+    let now = Local::now();
+
+    let mut items: Vec<ListItem> = Vec::new();
+    let mut task_index = 0;
+    let mut selected_row = None;
+    let mut task_count = 0;
+
+    for group in &groups {
+        let header = match group.date {
+            Some(date) => format!("{} · {}", group.title, date.format("%b %-d")),
+            None => group.title.clone(),
+        };
+        let header_color = if group.title == "Overdue" { app.theme.error } else { app.theme.accent };
+        items.push(ListItem::new(Line::from(Span::styled(
+            header,
+            Style::default().fg(header_color).add_modifier(Modifier::BOLD),
+        ))));
+
+        for entry in &group.entries {
+            let content = match entry {
+                UpcomingEntry::Task(task) => {
+                    let style = if task_index == app.selected_task_index {
+                        Style::default().fg(app.theme.focus).add_modifier(Modifier::BOLD)
+                    } else if selected_ids.contains(&task.id) {
+                        Style::default().fg(app.theme.accent).add_modifier(Modifier::REVERSED)
+                    } else if task.is_overdue() {
+                        Style::default().fg(app.theme.error)
+                    } else if task.is_due_today() {
+                        Style::default().fg(app.theme.warning)
+                    } else {
+                        Style::default().fg(app.theme.text)
+                    };
+                    if task_index == app.selected_task_index {
+                        selected_row = Some(items.len());
+                    }
+                    task_index += 1;
+                    task_count += 1;
+
+                    let selection_marker = if selected_ids.contains(&task.id) { "● " } else { "" };
+                    let priority_marker = task.priority.map(|p| p.marker()).unwrap_or("");
+                    let tag_info: String = task.tags.iter().map(|tag| format!(" #{}", tag)).collect();
+                    let due_info = match task.due() {
+                        Some(due) => format!(" [{}]", date_utils::due_label(due, now)),
+                        None => String::new(),
+                    };
+                    Span::styled(
+                        format!(
+                            "  {}☐ {}{} ({}){}{}{}",
+                            selection_marker,
+                            priority_marker,
+                            task.title,
+                            list_name(app, task.list_id),
+                            tag_info,
+                            due_info,
+                            ui::frequency_label(task.recurring_frequency.as_ref()),
+                        ),
+                        style,
+                    )
+                }
+                UpcomingEntry::Projected(task, due_date) => {
+                    let time = if task.has_due_time { format!(" [{}]", due_date.format("%H:%M")) } else { String::new() };
+                    Span::styled(
+                        format!(
+                            "  ↻ {} ({}){}{}",
+                            task.title,
+                            list_name(app, task.list_id),
+                            time,
+                            ui::frequency_label(task.recurring_frequency.as_ref()),
+                        ),
+                        Style::default().fg(app.theme.text_muted).add_modifier(Modifier::ITALIC),
+                    )
+                }
+            };
+            items.push(ListItem::new(Line::from(content)));
+        }
+    }
+
+    if items.is_empty() {
+        items.push(ListItem::new(Line::from(Span::styled(
+            "Nothing to do",
+            Style::default().fg(app.theme.text_muted),
+        ))));
+    }
+
+    let mut title = format!("Upcoming ({} open)", task_count);
+    if app.has_selection() {
+        let mode = if app.visual_anchor.is_some() { "VISUAL, " } else { "" };
+        title.push_str(&format!(" [{}{} selected]", mode, selected_ids.len()));
+    }
+
+    let list = List::new(items)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface))
+            .title(title));
+
+    view.page_height = scroll::visible_rows(area);
+    scroll::render_list(f, &app.theme, list, area, &mut view.upcoming, selected_row);
+}
+
+/// Index in the Upcoming tasks of the task drawn on `row`, or `None` for a
+/// header or a projected occurrence
+pub fn task_index_at_row(app: &App, row: usize) -> Option<usize> {
+    let mut rows = 0;
+    let mut task_index = 0;
+
+    for group in app.get_upcoming_groups() {
+        // The group's header
+        rows += 1;
+        for entry in group.entries {
+            if let UpcomingEntry::Task(_) = entry {
+                if rows == row {
+                    return Some(task_index);
+                }
+                task_index += 1;
+            }
+            rows += 1;
+        }
+    }
+    None
+}
+
+fn list_name(app: &App, list_id: u32) -> &str {
+    app.lists.iter().find(|l| l.id == list_id).map(|l| l.name.as_str()).unwrap_or("Unknown")
+}
+
@@ -0,0 +1,79 @@
+use crate::app::App;
+use crate::ui::scroll::{self, ViewState};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+
+pub fn draw(f: &mut Frame, app: &App, view: &mut ViewState, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Title
+            Constraint::Min(0),    // Workspaces
+        ])
+        .split(area);
+
+    draw_title(f, app, chunks[0]);
+    draw_workspaces(f, app, view, chunks[1]);
+}
+
+fn draw_title(f: &mut Frame, app: &App, area: Rect) {
+    let location = match app.config.data_dir() {
+        Ok(dir) => format!("Tasks of '{}' are kept in {}", app.config.workspace, dir.display()),
+        Err(_) => String::new(),
+    };
+
+    let title_widget = Paragraph::new(location)
+        .style(Style::default().fg(app.theme.text_secondary))
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface)));
+
+    f.render_widget(title_widget, area);
+}
+
+fn draw_workspaces(f: &mut Frame, app: &App, view: &mut ViewState, area: Rect) {
+    let name_width = app.workspace_summaries.iter().map(|w| w.name.len()).max().unwrap_or(0);
+
+    let items: Vec<ListItem> = app
+        .workspace_summaries
+        .iter()
+        .enumerate()
+        .map(|(i, workspace)| {
+            let style = if i == app.selected_task_index {
+                Style::default().fg(app.theme.focus).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(app.theme.text)
+            };
+            let marker = if workspace.name == app.config.workspace { "● " } else { "  " };
+
+            let mut spans = vec![Span::styled(format!("{}{:<width$}  ", marker, workspace.name, width = name_width), style)];
+            match workspace.counts {
+                Some((open, overdue)) => {
+                    spans.push(Span::styled(format!("{} open", open), Style::default().fg(app.theme.text_secondary)));
+                    if overdue > 0 {
+                        spans.push(Span::styled(format!(", {} overdue", overdue), Style::default().fg(app.theme.error)));
+                    }
+                }
+                None => spans.push(Span::styled("Couldn't read tasks", Style::default().fg(app.theme.text_muted))),
+            }
+
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.surface))
+            .title("Workspaces"));
+
+    view.page_height = scroll::visible_rows(area);
+    scroll::render_list(f, &app.theme, list, area, &mut view.workspaces, Some(app.selected_task_index));
+}
@@ -0,0 +1,81 @@
+use crate::theme::Theme;
+use ratatui::{
+    layout::{Margin, Rect},
+    style::Style,
+    widgets::{List, ListState, Scrollbar, ScrollbarOrientation, ScrollbarState},
+    Frame,
+};
+
+/// Scroll positions of the scrollable lists, kept between frames
+#[derive(Debug, Default)]
+pub struct ViewState {
+    pub sidebar: ListView,
+    pub tasks: ListView,
+    pub trash: ListView,
+    pub history: ListView,
+    pub palette: ListView,
+    pub workspaces: ListView,
+    pub calendar: ListView,
+    pub upcoming: ListView,
+    pub suggestions: ListView,
+    pub timesheet: ListView,
+    /// One per column of the board, so each scrolls on its own
+    pub board: Vec<ListView>,
+    /// Visible rows of the list the cursor is in, used for paging
+    pub page_height: usize,
+}
+
+/// A scrollable list and where it was last drawn, so mouse clicks can be
+/// mapped back to rows
+#[derive(Debug, Default)]
+pub struct ListView {
+    pub state: ListState,
+    pub area: Rect,
+}
+
+impl ListView {
+    /// Row under a screen position, counted from the top of the list rather
+    /// than the top of the view. `None` outside the list's borders.
+    pub fn row_at(&self, column: u16, row: u16) -> Option<usize> {
+        let inner = self.area.inner(&Margin { vertical: 1, horizontal: 1 });
+        let position = Rect::new(column, row, 1, 1);
+        if !inner.intersects(position) {
+            return None;
+        }
+        Some(self.state.offset() + (row - inner.y) as usize)
+    }
+
+    /// Column of a screen position inside the list's borders
+    pub fn column_at(&self, column: u16) -> u16 {
+        column.saturating_sub(self.area.x + 1)
+    }
+}
+
+/// Render a bordered list scrolled so that row `selected` is visible, with a
+/// scrollbar on the right border when the rows don't fit
+pub fn render_list(f: &mut Frame, theme: &Theme, list: List, area: Rect, view: &mut ListView, selected: Option<usize>) {
+    let row_count = list.len();
+    view.area = area;
+    let state = &mut view.state;
+    state.select(selected.map(|row| row.min(row_count.saturating_sub(1))));
+    f.render_stateful_widget(list, area, state);
+
+    let visible_rows = visible_rows(area);
+    if row_count > visible_rows {
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(None)
+            .end_symbol(None)
+            .style(Style::default().fg(theme.accent));
+        let mut scrollbar_state = ScrollbarState::new(row_count.saturating_sub(visible_rows)).position(state.offset());
+        f.render_stateful_widget(
+            scrollbar,
+            area.inner(&Margin { vertical: 1, horizontal: 0 }),
+            &mut scrollbar_state,
+        );
+    }
+}
+
+/// Rows inside a bordered block
+pub fn visible_rows(area: Rect) -> usize {
+    area.height.saturating_sub(2) as usize
+}
@@ -0,0 +1,69 @@
+use crate::app::{App, SidebarItem};
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::ListItem,
+};
+
+/// Render the grouped list sidebar rows. `is_highlighted` decides which rows
+/// are drawn in the focus color, given the row and its index.
+pub fn build_items<'a>(
+    app: &App,
+    is_highlighted: impl Fn(&SidebarItem, usize) -> bool,
+) -> Vec<ListItem<'a>> {
+    app.get_sidebar_items()
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let highlighted = is_highlighted(item, i);
+            match item {
+                SidebarItem::Group { name, collapsed } => {
+                    let style = if highlighted {
+                        Style::default().fg(app.theme.focus).add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(app.theme.accent).add_modifier(Modifier::BOLD)
+                    };
+
+                    let marker = if *collapsed { "▸" } else { "▾" };
+                    let (completed_count, task_count) = app.get_group_task_counts(name);
+                    let content = if task_count > 0 {
+                        format!("{} {} ({}/{})", marker, name, completed_count, task_count)
+                    } else {
+                        format!("{} {}", marker, name)
+                    };
+
+                    ListItem::new(Line::from(Span::styled(content, style)))
+                }
+                SidebarItem::List(list_id) => {
+                    let style = if highlighted {
+                        Style::default().fg(app.theme.focus).add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(app.theme.list_color(*list_id))
+                    };
+
+                    let Some(list) = app.lists.iter().find(|l| l.id == *list_id) else {
+                        return ListItem::new("");
+                    };
+                    let indent = if list.group.is_some() { "  " } else { "" };
+
+                    let task_count = app.tasks.iter().filter(|t| t.list_id == list.id).count();
+                    let completed_count = app.tasks.iter().filter(|t| t.list_id == list.id && t.is_completed).count();
+
+                    let content = if task_count > 0 {
+                        format!("{}{} ({}/{})", indent, list.name, completed_count, task_count)
+                    } else {
+                        format!("{}{}", indent, list.name)
+                    };
+
+                    ListItem::new(Line::from(Span::styled(content, style)))
+                }
+            }
+        })
+        .collect()
+}
+
+/// Row of the open list, so the sidebar can keep it in view
+pub fn current_list_row(app: &App) -> Option<usize> {
+    let list_id = app.current_list_id?;
+    app.get_sidebar_items().iter().position(|item| *item == SidebarItem::List(list_id))
+}
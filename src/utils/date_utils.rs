@@ -1,43 +1,437 @@
-use chrono::{DateTime, Local, NaiveDate, NaiveDateTime};
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Datelike, Days, Local, Months, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+use serde::{Deserialize, Serialize};
 
-pub fn parse_date_input(input: &str) -> Option<DateTime<Local>> {
+/// Order of day, month and year in dates written with numbers, such as
+/// 03/04/2024. ISO dates like 2024-04-03 are understood whatever the order.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum DateOrder {
+    /// 03/04/2024 is March 4th, as in the US
+    #[default]
+    #[serde(rename = "mdy")]
+    MonthDayYear,
+    /// 03/04/2024 is April 3rd, as in most of Europe
+    #[serde(rename = "dmy")]
+    DayMonthYear,
+    /// 2024/04/03 is April 3rd
+    #[serde(rename = "ymd")]
+    YearMonthDay,
+}
+
+/// When a task is due. All-day dates are kept at the end of their day, so
+/// they sort after the timed tasks of the same day and only become overdue
+/// once the day is over.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DueDate {
+    pub at: DateTime<Local>,
+    pub has_time: bool,
+}
+
+impl DueDate {
+    /// Due on `date` at `time`, or all day without one
+    pub fn on(date: NaiveDate, time: Option<NaiveTime>) -> Result<DueDate> {
+        let due = date.and_time(time.unwrap_or_else(end_of_day));
+        let at = due
+            .and_local_timezone(Local)
+            .earliest()
+            .ok_or_else(|| anyhow!("{} doesn't exist in the local time zone", due.format("%Y-%m-%d %H:%M")))?;
+        Ok(DueDate { at, has_time: time.is_some() })
+    }
+}
+
+/// Parse a due date typed by the user, such as "tomorrow", "next tue",
+/// "in 3 days", "+2w", "eom", "03/04" or "friday 5pm". A date without a
+/// time is due all day, and a time without a date is today.
+/// "next week" is the first day of next week, which starts on `week_start`.
+pub fn parse_date_input(input: &str, week_start: Weekday, order: DateOrder) -> Result<DueDate> {
+    let (date, time) = parse_date_at(input, Local::now().naive_local(), week_start, order)?;
+    DueDate::on(date, time)
+}
+
+/// Parse a due date relative to `now` into its day, and its time if one
+/// was given
+pub fn parse_date_at(input: &str, now: NaiveDateTime, week_start: Weekday, order: DateOrder) -> Result<(NaiveDate, Option<NaiveTime>)> {
     let input = input.trim().to_lowercase();
-    
-    // Handle relative dates
-    match input.as_str() {
-        "today" => return Some(Local::now().date_naive().and_hms_opt(23, 59, 59)?.and_local_timezone(Local).single()?),
-        "tomorrow" => return Some((Local::now().date_naive() + chrono::Duration::days(1)).and_hms_opt(23, 59, 59)?.and_local_timezone(Local).single()?),
-        "next week" => return Some((Local::now().date_naive() + chrono::Duration::weeks(1)).and_hms_opt(23, 59, 59)?.and_local_timezone(Local).single()?),
-        "next month" => {
-            let next_month = Local::now().date_naive() + chrono::Duration::days(30);
-            return Some(next_month.and_hms_opt(23, 59, 59)?.and_local_timezone(Local).single()?);
+    let mut words: Vec<&str> = input.split_whitespace().collect();
+    if words.is_empty() {
+        bail!("No date given");
+    }
+
+    // A time can follow the date, optionally after "at"
+    let mut time = None;
+    if let Some(&last) = words.last() {
+        if let Some(found) = parse_time(last) {
+            time = Some(found);
+            words.pop();
+            if words.last() == Some(&"at") {
+                words.pop();
+            }
+        } else if looks_like_time(last) {
+            bail!("'{}' isn't a valid time", last);
         }
-        _ => {}
-    }
-    
-    // Try parsing various date formats
-    let formats = [
-        "%Y-%m-%d",
-        "%m/%d/%Y",
-        "%d/%m/%Y",
-        "%Y-%m-%d %H:%M",
-        "%m/%d/%Y %H:%M",
-        "%d/%m/%Y %H:%M",
-    ];
-    
-    for format in &formats {
-        if let Ok(naive_dt) = NaiveDateTime::parse_from_str(&input, format) {
-            return naive_dt.and_local_timezone(Local).single();
+    }
+
+    let today = now.date();
+    let date = if words.is_empty() { today } else { parse_day(&words, today, week_start, order)? };
+    Ok((date, time))
+}
+
+/// How long until a task is due, shown next to it: "Today", "Tomorrow",
+/// "3 days" or "Overdue 2 days" for all-day tasks. Timed tasks due within a
+/// day count down instead, as in "in 45m", "in 3h" or "2h overdue", and
+/// later ones show their time, as in "Tomorrow 09:00".
+pub fn due_label(due: DueDate, now: DateTime<Local>) -> String {
+    let days_until = (due.at.date_naive() - now.date_naive()).num_days();
+    if !due.has_time {
+        return match days_until {
+            ..=-1 => format!("Overdue {} days", -days_until),
+            0 => "Today".to_string(),
+            1 => "Tomorrow".to_string(),
+            _ => format!("{} days", days_until),
+        };
+    }
+
+    let minutes = (due.at - now).num_minutes();
+    let time = due.at.format("%H:%M");
+    match minutes {
+        ..=-1440 => format!("Overdue {} days", -days_until),
+        -1439..=-60 => format!("{}h overdue", -minutes / 60),
+        -59..=-1 => format!("{}m overdue", -minutes),
+        0 => "Due now".to_string(),
+        1..=59 => format!("in {}m", minutes),
+        _ if days_until == 0 => format!("in {}h", minutes / 60),
+        _ if days_until == 1 => format!("Tomorrow {}", time),
+        _ => format!("{} days, {}", days_until, time),
+    }
+}
+
+/// When a deferred task can be started: "Starts tomorrow" or "Starts in 3 days"
+pub fn start_label(start: NaiveDate, today: NaiveDate) -> String {
+    match (start - today).num_days() {
+        ..=0 => "Started".to_string(),
+        1 => "Starts tomorrow".to_string(),
+        days => format!("Starts in {} days", days),
+    }
+}
+
+/// Parse a time of day such as "15:30", "3pm" or "3:30pm"
+pub fn parse_time(input: &str) -> Option<NaiveTime> {
+    let input = input.trim().to_lowercase();
+    if let Ok(time) = NaiveTime::parse_from_str(&input, "%H:%M") {
+        return Some(time);
+    }
+
+    // chrono can't parse an hour without minutes, so 3pm is read as 3:00pm
+    let meridiem = ["am", "pm"].into_iter().find(|m| input.ends_with(m))?;
+    let clock = &input[..input.len() - meridiem.len()];
+    let clock = if clock.contains(':') { clock.to_string() } else { format!("{}:00", clock) };
+    NaiveTime::parse_from_str(&format!("{}{}", clock, meridiem), "%I:%M%p").ok()
+}
+
+/// Time given to dates typed without one
+fn end_of_day() -> NaiveTime {
+    NaiveTime::from_hms_opt(23, 59, 59).unwrap_or_default()
+}
+
+/// Whether a word was meant as a time, so it can be reported as a bad one
+fn looks_like_time(word: &str) -> bool {
+    word.starts_with(|c: char| c.is_ascii_digit()) && (word.contains(':') || word.ends_with("am") || word.ends_with("pm"))
+}
+
+fn parse_day(words: &[&str], today: NaiveDate, week_start: Weekday, order: DateOrder) -> Result<NaiveDate> {
+    let start_of_next_week = today + Days::new(7 - days_since(week_start, today.weekday()));
+    let date = match words {
+        ["today"] => today,
+        ["tomorrow" | "tmr"] => today + Days::new(1),
+        ["next", "week"] => start_of_next_week,
+        ["next", "month"] => first_of_month(today) + Months::new(1),
+        ["end", "of", "week"] | ["eow"] => start_of_next_week - Days::new(1),
+        ["end", "of", "month"] | ["eom"] => first_of_month(today) + Months::new(1) - Days::new(1),
+        ["next", day] => {
+            let weekday = parse_weekday(day).ok_or_else(|| anyhow!("'{}' isn't a day of the week", day))?;
+            start_of_next_week + Days::new(days_since(week_start, weekday))
+        }
+        ["in", count, unit] => {
+            let count: u32 = count.parse().map_err(|_| anyhow!("'{}' isn't a number", count))?;
+            add_offset(today, count, unit)?
+        }
+        [offset] if offset.starts_with('+') => {
+            // "+3d", "+2w", "+1m" or "+1y"
+            let offset = &offset[1..];
+            let unit_start = offset.find(|c: char| !c.is_ascii_digit()).unwrap_or(offset.len());
+            let count: u32 = offset[..unit_start].parse().map_err(|_| anyhow!("'+{}' needs a number after the +", offset))?;
+            add_offset(today, count, &offset[unit_start..])?
+        }
+        [day] if parse_weekday(day).is_some() => {
+            // The coming one, which may be today
+            let weekday = parse_weekday(day).unwrap_or(Weekday::Mon);
+            today + Days::new(days_since(today.weekday(), weekday))
         }
+        [word] if word.starts_with(|c: char| c.is_ascii_digit()) => parse_numeric_date(word, today, order)?,
+        _ => parse_named_month(words, today)?,
+    };
+    Ok(date)
+}
+
+/// Days from `from` forward to the next `to`, zero if they are the same
+fn days_since(from: Weekday, to: Weekday) -> u64 {
+    ((7 + to.num_days_from_monday() - from.num_days_from_monday()) % 7) as u64
+}
+
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+pub fn parse_weekday(word: &str) -> Option<Weekday> {
+    let weekday = match word {
+        "monday" | "mon" => Weekday::Mon,
+        "tuesday" | "tue" | "tues" => Weekday::Tue,
+        "wednesday" | "wed" => Weekday::Wed,
+        "thursday" | "thu" | "thur" | "thurs" => Weekday::Thu,
+        "friday" | "fri" => Weekday::Fri,
+        "saturday" | "sat" => Weekday::Sat,
+        "sunday" | "sun" => Weekday::Sun,
+        _ => return None,
+    };
+    Some(weekday)
+}
+
+fn add_offset(today: NaiveDate, count: u32, unit: &str) -> Result<NaiveDate> {
+    let date = match unit {
+        "d" | "day" | "days" => today.checked_add_days(Days::new(count.into())),
+        "w" | "week" | "weeks" => today.checked_add_days(Days::new(u64::from(count) * 7)),
+        "m" | "month" | "months" => today.checked_add_months(Months::new(count)),
+        "y" | "year" | "years" => today.checked_add_months(Months::new(count.saturating_mul(12))),
+        _ => bail!("'{}' isn't a unit, use days, weeks, months or years", unit),
+    };
+    date.ok_or_else(|| anyhow!("That date is too far away"))
+}
+
+/// A date written with numbers, e.g. 2024-04-03, 03/04/2024 or 03/04. A
+/// date without a year is the next one to come.
+fn parse_numeric_date(word: &str, today: NaiveDate, order: DateOrder) -> Result<NaiveDate> {
+    let invalid = || anyhow!("'{}' isn't a valid date", word);
+    let parts: Vec<&str> = word.split(['/', '.', '-']).collect();
+    let numbers: Vec<u32> = parts
+        .iter()
+        .map(|part| part.parse())
+        .collect::<Result<_, _>>()
+        .map_err(|_| anyhow!("Couldn't understand '{}'", word))?;
+
+    let (year, month, day) = match (numbers.as_slice(), order) {
+        // A four digit year first is always year, month, day
+        ([year, month, day], _) if parts[0].len() == 4 => (Some(*year as i32), *month, *day),
+        ([month, day], DateOrder::MonthDayYear | DateOrder::YearMonthDay) => (None, *month, *day),
+        ([day, month], DateOrder::DayMonthYear) => (None, *month, *day),
+        ([month, day, year], DateOrder::MonthDayYear) => (Some(full_year(*year, parts[2])), *month, *day),
+        ([day, month, year], DateOrder::DayMonthYear) => (Some(full_year(*year, parts[2])), *month, *day),
+        ([year, month, day], DateOrder::YearMonthDay) => (Some(full_year(*year, parts[0])), *month, *day),
+        _ => return Err(invalid()),
+    };
+    date_in_year(year, month, day, today).ok_or_else(invalid)
+}
+
+/// Two digit years are in this century
+fn full_year(year: u32, written: &str) -> i32 {
+    if written.len() <= 2 {
+        2000 + year as i32
+    } else {
+        year as i32
+    }
+}
+
+/// A date with a month name, e.g. "june 1", "1st june" or "1 jun 2025"
+fn parse_named_month(words: &[&str], today: NaiveDate) -> Result<NaiveDate> {
+    let not_understood = || anyhow!("Couldn't understand '{}'", words.join(" "));
+    let (month, day, year) = match words {
+        [first, second, rest @ ..] if rest.len() <= 1 => match (parse_month(first), parse_month(second)) {
+            (Some(month), None) => (month, *second, rest.first()),
+            (None, Some(month)) => (month, *first, rest.first()),
+            _ => return Err(not_understood()),
+        },
+        _ => return Err(not_understood()),
+    };
+
+    let day: u32 = day
+        .trim_end_matches(|c: char| c.is_ascii_alphabetic())
+        .parse()
+        .map_err(|_| not_understood())?;
+    let year = match year {
+        Some(year) => Some(year.parse().map_err(|_| anyhow!("'{}' isn't a year", year))?),
+        None => None,
+    };
+    date_in_year(year, month, day, today).ok_or_else(|| anyhow!("'{}' isn't a valid date", words.join(" ")))
+}
+
+fn parse_month(word: &str) -> Option<u32> {
+    const MONTHS: [&str; 12] = [
+        "january", "february", "march", "april", "may", "june",
+        "july", "august", "september", "october", "november", "december",
+    ];
+    if word.len() < 3 {
+        return None;
     }
-    
-    // Try parsing date only
-    for format in &["%Y-%m-%d", "%m/%d/%Y", "%d/%m/%Y"] {
-        if let Ok(naive_date) = NaiveDate::parse_from_str(&input, format) {
-            return naive_date.and_hms_opt(23, 59, 59)?.and_local_timezone(Local).single();
+    MONTHS.iter().position(|month| month.starts_with(word)).map(|index| index as u32 + 1)
+}
+
+/// The date in `year`, or without a year the next time the day comes round
+fn date_in_year(year: Option<i32>, month: u32, day: u32, today: NaiveDate) -> Option<NaiveDate> {
+    match year {
+        Some(year) => NaiveDate::from_ymd_opt(year, month, day),
+        None => {
+            let this_year = NaiveDate::from_ymd_opt(today.year(), month, day);
+            match this_year {
+                Some(date) if date >= today => Some(date),
+                _ => NaiveDate::from_ymd_opt(today.year() + 1, month, day),
+            }
         }
     }
-    
-    None
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Wednesday 15 May 2024, 10:00
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 5, 15).and_then(|d| d.and_hms_opt(10, 0, 0)).unwrap()
+    }
+
+    fn parse(input: &str) -> Result<NaiveDateTime> {
+        let (date, time) = parse_date_at(input, now(), Weekday::Mon, DateOrder::MonthDayYear)?;
+        Ok(date.and_time(time.unwrap_or_else(end_of_day)))
+    }
+
+    fn day(input: &str) -> String {
+        match parse(input) {
+            Ok(date) => date.format("%Y-%m-%d %H:%M").to_string(),
+            Err(err) => format!("error: {}", err),
+        }
+    }
+
+    #[test]
+    fn keywords() {
+        assert_eq!(day("today"), "2024-05-15 23:59");
+        assert_eq!(day(" Tomorrow "), "2024-05-16 23:59");
+        assert_eq!(day("next week"), "2024-05-20 23:59");
+        assert_eq!(day("next month"), "2024-06-01 23:59");
+        assert_eq!(day("end of week"), "2024-05-19 23:59");
+        assert_eq!(day("eom"), "2024-05-31 23:59");
+        assert_eq!(day("end of month"), "2024-05-31 23:59");
+    }
+
+    #[test]
+    fn week_start_moves_next_week() {
+        let (sunday_start, _) = parse_date_at("next week", now(), Weekday::Sun, DateOrder::MonthDayYear).unwrap();
+        assert_eq!(sunday_start, NaiveDate::from_ymd_opt(2024, 5, 19).unwrap());
+    }
+
+    #[test]
+    fn weekdays() {
+        assert_eq!(day("friday"), "2024-05-17 23:59");
+        assert_eq!(day("wed"), "2024-05-15 23:59");
+        assert_eq!(day("tues"), "2024-05-21 23:59");
+        assert_eq!(day("next tue"), "2024-05-21 23:59");
+        assert_eq!(day("next friday"), "2024-05-24 23:59");
+        assert_eq!(day("next mon"), "2024-05-20 23:59");
+    }
+
+    #[test]
+    fn offsets() {
+        assert_eq!(day("in 3 days"), "2024-05-18 23:59");
+        assert_eq!(day("in 1 week"), "2024-05-22 23:59");
+        assert_eq!(day("in 2 months"), "2024-07-15 23:59");
+        assert_eq!(day("+2w"), "2024-05-29 23:59");
+        assert_eq!(day("+10d"), "2024-05-25 23:59");
+        assert_eq!(day("+1m"), "2024-06-15 23:59");
+        assert_eq!(day("+1y"), "2025-05-15 23:59");
+    }
+
+    fn due(days: i64, hour: u32, minute: u32, has_time: bool) -> DueDate {
+        let at = (now() + chrono::Duration::days(days)).date().and_hms_opt(hour, minute, 0).unwrap();
+        DueDate { at: at.and_local_timezone(Local).earliest().unwrap(), has_time }
+    }
+
+    fn label(due: DueDate) -> String {
+        due_label(due, now().and_local_timezone(Local).earliest().unwrap())
+    }
+
+    #[test]
+    fn all_day_labels() {
+        assert_eq!(label(due(0, 23, 59, false)), "Today");
+        assert_eq!(label(due(1, 23, 59, false)), "Tomorrow");
+        assert_eq!(label(due(4, 23, 59, false)), "4 days");
+        assert_eq!(label(due(-2, 23, 59, false)), "Overdue 2 days");
+    }
+
+    #[test]
+    fn timed_labels_count_down() {
+        assert_eq!(label(due(0, 10, 45, true)), "in 45m");
+        assert_eq!(label(due(0, 13, 30, true)), "in 3h");
+        assert_eq!(label(due(0, 10, 0, true)), "Due now");
+        assert_eq!(label(due(0, 9, 15, true)), "45m overdue");
+        assert_eq!(label(due(0, 8, 0, true)), "2h overdue");
+        assert_eq!(label(due(-1, 20, 0, true)), "14h overdue");
+        assert_eq!(label(due(-3, 9, 0, true)), "Overdue 3 days");
+        assert_eq!(label(due(1, 9, 0, true)), "Tomorrow 09:00");
+        assert_eq!(label(due(3, 17, 30, true)), "3 days, 17:30");
+    }
+
+    #[test]
+    fn start_labels() {
+        let today = now().date();
+        assert_eq!(start_label(today + Days::new(1), today), "Starts tomorrow");
+        assert_eq!(start_label(today + Days::new(5), today), "Starts in 5 days");
+        assert_eq!(start_label(today, today), "Started");
+    }
+
+    #[test]
+    fn times() {
+        assert_eq!(day("5pm"), "2024-05-15 17:00");
+        assert_eq!(day("17:30"), "2024-05-15 17:30");
+        assert_eq!(day("at 9:15am"), "2024-05-15 09:15");
+        assert_eq!(day("friday 5pm"), "2024-05-17 17:00");
+        assert_eq!(day("tomorrow at 12pm"), "2024-05-16 12:00");
+        assert_eq!(day("2024-06-01 08:00"), "2024-06-01 08:00");
+    }
+
+    #[test]
+    fn numeric_dates_follow_the_date_order() {
+        assert_eq!(day("2024-06-01"), "2024-06-01 23:59");
+        assert_eq!(day("03/04/2025"), "2025-03-04 23:59");
+        assert_eq!(day("12/25"), "2024-12-25 23:59");
+        // Already past this year, so next year's
+        assert_eq!(day("03/04"), "2025-03-04 23:59");
+        assert_eq!(day("03/04/25"), "2025-03-04 23:59");
+
+        let dmy = |input| parse_date_at(input, now(), Weekday::Mon, DateOrder::DayMonthYear).unwrap().0;
+        assert_eq!(dmy("03/04/2025"), NaiveDate::from_ymd_opt(2025, 4, 3).unwrap());
+        assert_eq!(dmy("25.12.2024"), NaiveDate::from_ymd_opt(2024, 12, 25).unwrap());
+        assert_eq!(dmy("2024-06-01"), NaiveDate::from_ymd_opt(2024, 6, 1).unwrap());
+
+        let ymd = |input| parse_date_at(input, now(), Weekday::Mon, DateOrder::YearMonthDay).unwrap().0;
+        assert_eq!(ymd("2025/04/03"), NaiveDate::from_ymd_opt(2025, 4, 3).unwrap());
+    }
+
+    #[test]
+    fn month_names() {
+        assert_eq!(day("june 1"), "2024-06-01 23:59");
+        assert_eq!(day("1st jun"), "2024-06-01 23:59");
+        assert_eq!(day("march 3"), "2025-03-03 23:59");
+        assert_eq!(day("3 march 2024"), "2024-03-03 23:59");
+        assert_eq!(day("dec 24 6pm"), "2024-12-24 18:00");
+    }
+
+    #[test]
+    fn errors_say_what_was_not_understood() {
+        assert_eq!(day(""), "error: No date given");
+        assert_eq!(day("fryday"), "error: Couldn't understand 'fryday'");
+        assert_eq!(day("next fryday"), "error: 'fryday' isn't a day of the week");
+        assert_eq!(day("in three days"), "error: 'three' isn't a number");
+        assert_eq!(day("in 3 fortnights"), "error: 'fortnights' isn't a unit, use days, weeks, months or years");
+        assert_eq!(day("13/04/2024"), "error: '13/04/2024' isn't a valid date");
+        assert_eq!(day("2024-02-30"), "error: '2024-02-30' isn't a valid date");
+        assert_eq!(day("friday 25:00"), "error: '25:00' isn't a valid time");
+        assert_eq!(day("june 31"), "error: 'june 31' isn't a valid date");
+    }
+}
@@ -0,0 +1,80 @@
+use crate::models::{List, Task, TimeEntry};
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use serde::Serialize;
+
+/// Render lists and their tasks as Markdown checklists, one section per list
+pub fn to_markdown(lists: &[&List], tasks: &[&Task]) -> String {
+    let mut output = String::from("# Todo\n");
+
+    for list in lists {
+        output.push_str(&format!("\n## {}\n\n", list.name));
+
+        let list_tasks: Vec<&&Task> = tasks.iter().filter(|t| t.list_id == list.id).collect();
+        if list_tasks.is_empty() {
+            output.push_str("_No tasks_\n");
+            continue;
+        }
+
+        // Active tasks first, like the task list
+        let (active, completed): (Vec<&&Task>, Vec<&&Task>) = list_tasks.into_iter().partition(|t| !t.is_completed);
+        for task in active.into_iter().chain(completed) {
+            output.push_str(&task_line(task));
+        }
+    }
+
+    output
+}
+
+fn task_line(task: &Task) -> String {
+    let mut line = format!("- [{}] {}", if task.is_completed { "x" } else { " " }, task.title);
+
+    if let Some(due) = task.due() {
+        let format = if due.has_time { "%Y-%m-%d %H:%M" } else { "%Y-%m-%d" };
+        line.push_str(&format!(" (due {})", due.at.format(format)));
+    }
+    for tag in &task.tags {
+        line.push_str(&format!(" #{}", tag));
+    }
+    line.push('\n');
+
+    if let Some(notes) = task.notes.as_deref().filter(|n| !n.trim().is_empty()) {
+        for note_line in notes.lines() {
+            line.push_str(&format!("  > {}\n", note_line));
+        }
+    }
+
+    line
+}
+
+/// One row of the time export
+#[derive(Serialize)]
+struct TimeRow<'a> {
+    date: String,
+    list: &'a str,
+    task_id: u32,
+    task: &'a str,
+    started_at: String,
+    stopped_at: String,
+    minutes: i64,
+}
+
+/// Render time entries as CSV, one row per entry, for billing or a
+/// spreadsheet. A running timer has no stop time and counts up to `now`.
+pub fn time_entries_to_csv(entries: &[&TimeEntry], tasks: &[&Task], lists: &[&List], now: DateTime<Local>) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for entry in entries {
+        let task = tasks.iter().find(|t| t.id == entry.task_id);
+        let list = task.and_then(|task| lists.iter().find(|l| l.id == task.list_id));
+        writer.serialize(TimeRow {
+            date: entry.started_at.format("%Y-%m-%d").to_string(),
+            list: list.map(|l| l.name.as_str()).unwrap_or(""),
+            task_id: entry.task_id,
+            task: task.map(|t| t.title.as_str()).unwrap_or(""),
+            started_at: entry.started_at.to_rfc3339(),
+            stopped_at: entry.stopped_at.map(|d| d.to_rfc3339()).unwrap_or_default(),
+            minutes: entry.duration(now).num_minutes(),
+        })?;
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
@@ -0,0 +1,37 @@
+/// Score how well `query` matches `candidate` as a case-insensitive
+/// subsequence. Returns `None` if it doesn't match; higher scores are better
+/// matches, favouring consecutive characters and the starts of words.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let query: Vec<char> = query.to_lowercase().chars().filter(|c| !c.is_whitespace()).collect();
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+
+    let mut score = 0;
+    let mut query_index = 0;
+    let mut previous_match: Option<usize> = None;
+
+    for (i, c) in candidate.iter().enumerate() {
+        if query_index == query.len() {
+            break;
+        }
+        if *c != query[query_index] {
+            continue;
+        }
+
+        score += 1;
+        if previous_match == Some(i.wrapping_sub(1)) {
+            score += 3;
+        }
+        if i == 0 || matches!(candidate[i - 1], ' ' | '_' | '/' | '-') {
+            score += 5;
+        }
+        previous_match = Some(i);
+        query_index += 1;
+    }
+
+    if query_index == query.len() {
+        // Prefer shorter candidates when the match is otherwise equal
+        Some(score * 10 - candidate.len() as i32)
+    } else {
+        None
+    }
+}
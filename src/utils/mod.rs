@@ -1 +1,6 @@
 pub mod date_utils;
+pub mod export;
+pub mod fuzzy;
+pub mod quick_add;
+pub mod stats;
+pub mod timesheet;
@@ -0,0 +1,285 @@
+use crate::models::{Priority, RecurringFrequency, Task};
+use crate::utils::date_utils::{self, DateOrder, DueDate};
+use chrono::{DateTime, Local, NaiveTime, Weekday};
+
+/// Most words tried together as one date, e.g. "in 3 days at 5pm"
+const MAX_DATE_WORDS: usize = 5;
+
+/// A task described on one line, e.g.
+/// "Fix rerun symlinks tomorrow 3pm every weekday #infra !high @Serial".
+/// Words that aren't a date, time, repeat, `#tag`, `!priority` or `@list`
+/// make up the title.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QuickAdd {
+    pub title: String,
+    pub due_date: Option<DueDate>,
+    pub frequency: Option<RecurringFrequency>,
+    pub tags: Vec<String>,
+    pub priority: Option<Priority>,
+    /// List name as typed after `@`, which may not match a list
+    pub list: Option<String>,
+}
+
+/// Parse a quick-add line. Only the first date and time are used; later
+/// ones are left in the title.
+pub fn parse(input: &str, week_start: Weekday, order: DateOrder) -> QuickAdd {
+    let words: Vec<&str> = input.split_whitespace().collect();
+    let mut parsed = QuickAdd::default();
+    let mut title_words = Vec::new();
+    let mut date = None;
+    let mut time = None;
+
+    let mut index = 0;
+    while index < words.len() {
+        let word = words[index];
+        let rest = &words[index..];
+
+        let used = if let Some(tag) = word.strip_prefix('#').map(Task::tag_name).filter(|tag| !tag.is_empty()) {
+            if !parsed.tags.contains(&tag) {
+                parsed.tags.push(tag);
+            }
+            1
+        } else if let Some(priority) = word.strip_prefix('!').and_then(Priority::from_name) {
+            parsed.priority = Some(priority);
+            1
+        } else if let Some(list) = word.strip_prefix('@').filter(|list| !list.is_empty()) {
+            parsed.list = Some(list.to_string());
+            1
+        } else if let Some((frequency, used)) = parse_frequency(rest) {
+            parsed.frequency = Some(frequency);
+            used
+        } else if let Some((found, used)) = parse_time(rest).filter(|_| time.is_none()) {
+            time = Some(found);
+            used
+        } else if let Some((found, used)) = parse_date(rest, week_start, order).filter(|_| date.is_none()) {
+            date = Some(found);
+            used
+        } else {
+            title_words.push(word);
+            1
+        };
+        index += used;
+    }
+
+    parsed.title = title_words.join(" ");
+    let timed = |date: DateTime<Local>, time| {
+        let at = date.date_naive().and_time(time).and_local_timezone(Local).single()?;
+        Some(DueDate { at, has_time: true })
+    };
+    parsed.due_date = match (date, time) {
+        (Some(date), Some(time)) => timed(date.at, time),
+        // A time on its own is today
+        (None, Some(time)) => timed(Local::now(), time),
+        (date, None) => date,
+    };
+    parsed
+}
+
+/// "every day", "every weekday", "every week", "every month" or "every year"
+fn parse_frequency(words: &[&str]) -> Option<(RecurringFrequency, usize)> {
+    let [every, unit, ..] = words else {
+        return None;
+    };
+    if !every.eq_ignore_ascii_case("every") {
+        return None;
+    }
+    let frequency = match unit.to_lowercase().as_str() {
+        "day" => RecurringFrequency::Daily,
+        "weekday" => RecurringFrequency::Weekdays,
+        "week" => RecurringFrequency::Weekly,
+        "month" => RecurringFrequency::Monthly,
+        "year" => RecurringFrequency::Yearly,
+        _ => return None,
+    };
+    Some((frequency, 2))
+}
+
+/// A time such as "3pm" or "15:30", optionally after "at"
+fn parse_time(words: &[&str]) -> Option<(NaiveTime, usize)> {
+    match words {
+        [at, time, ..] if at.eq_ignore_ascii_case("at") => date_utils::parse_time(time).map(|time| (time, 2)),
+        [time, ..] => date_utils::parse_time(time).map(|time| (time, 1)),
+        [] => None,
+    }
+}
+
+/// The longest run of words at the start that reads as a date. A word that
+/// is often something else, such as "2.5" or "sun", isn't a date on its own.
+fn parse_date(words: &[&str], week_start: Weekday, order: DateOrder) -> Option<(DueDate, usize)> {
+    (1..=MAX_DATE_WORDS.min(words.len()))
+        .rev()
+        .filter(|&used| !is_ambiguous_date(words[0]) || !is_day_alone(&words[1..used]))
+        .find_map(|used| date_utils::parse_date_input(&words[..used].join(" "), week_start, order).ok().map(|date| (date, used)))
+}
+
+/// Whether the words after the day are nothing or only a time
+fn is_day_alone(rest: &[&str]) -> bool {
+    rest.is_empty() || parse_time(rest).is_some_and(|(_, used)| used == rest.len())
+}
+
+/// A number without a `/`, other than a date like 2024-05-01, or a short
+/// weekday such as "sun". These are only taken as dates next to other
+/// date words, as in "5 may" or "next sun".
+fn is_ambiguous_date(word: &str) -> bool {
+    let word = word.to_lowercase();
+    if word.starts_with(|c: char| c.is_ascii_digit()) {
+        let parts: Vec<&str> = word.split('-').collect();
+        let iso = parts.len() == 3 && parts[0].len() == 4;
+        !word.contains('/') && !iso
+    } else {
+        date_utils::parse_weekday(&word).is_some() && !word.ends_with("day")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, NaiveDate};
+
+    fn parse(input: &str) -> QuickAdd {
+        super::parse(input, Weekday::Mon, DateOrder::MonthDayYear)
+    }
+
+    fn today_at(days: i64, hour: u32, minute: u32) -> Option<DueDate> {
+        let at = (Local::now().date_naive() + Duration::days(days))
+            .and_hms_opt(hour, minute, 0)?
+            .and_local_timezone(Local)
+            .single()?;
+        Some(DueDate { at, has_time: true })
+    }
+
+    #[test]
+    fn parses_every_field() {
+        let parsed = parse("Fix rerun symlinks tomorrow 3pm every weekday #infra !high @Serial");
+        assert_eq!(parsed.title, "Fix rerun symlinks");
+        assert_eq!(parsed.due_date, today_at(1, 15, 0));
+        assert_eq!(parsed.frequency, Some(RecurringFrequency::Weekdays));
+        assert_eq!(parsed.tags, vec!["infra"]);
+        assert_eq!(parsed.priority, Some(Priority::High));
+        assert_eq!(parsed.list.as_deref(), Some("Serial"));
+    }
+
+    #[test]
+    fn plain_text_is_all_title() {
+        let parsed = parse("  Water the   plants ");
+        assert_eq!(parsed, QuickAdd { title: "Water the plants".to_string(), ..Default::default() });
+    }
+
+    #[test]
+    fn fields_can_come_in_any_order() {
+        let parsed = parse("@Home !2 #Garden Water plants");
+        assert_eq!(parsed.title, "Water plants");
+        assert_eq!(parsed.list.as_deref(), Some("Home"));
+        assert_eq!(parsed.priority, Some(Priority::Medium));
+        assert_eq!(parsed.tags, vec!["garden"]);
+    }
+
+    #[test]
+    fn tags_are_lowercase_and_unique() {
+        assert_eq!(parse("Call #Work #work #phone").tags, vec!["work", "phone"]);
+    }
+
+    #[test]
+    fn commas_are_dropped_from_tags() {
+        assert_eq!(parse("Call #home,work #,").tags, vec!["homework"]);
+        assert_eq!(parse("Call #,").title, "Call #,");
+    }
+
+    #[test]
+    fn lone_markers_stay_in_title() {
+        let parsed = parse("Ask # about @ and !");
+        assert_eq!(parsed.title, "Ask # about @ and !");
+        assert_eq!(parsed.tags, Vec::<String>::new());
+        assert_eq!(parsed.list, None);
+    }
+
+    #[test]
+    fn unknown_priority_stays_in_title() {
+        let parsed = parse("Read !important");
+        assert_eq!(parsed.title, "Read !important");
+        assert_eq!(parsed.priority, None);
+    }
+
+    #[test]
+    fn date_without_time_is_all_day() {
+        let parsed = parse("Pay rent 2030-01-31");
+        assert_eq!(parsed.title, "Pay rent");
+        let expected = NaiveDate::from_ymd_opt(2030, 1, 31)
+            .and_then(|date| date.and_hms_opt(23, 59, 59))
+            .and_then(|date| date.and_local_timezone(Local).single())
+            .map(|at| DueDate { at, has_time: false });
+        assert_eq!(parsed.due_date, expected);
+    }
+
+    #[test]
+    fn multi_word_dates() {
+        assert!(parse("Plan next week").due_date.is_some());
+        assert_eq!(parse("Plan next week").title, "Plan");
+
+        let parsed = parse("Standup 2030-01-31 09:15");
+        assert_eq!(parsed.title, "Standup");
+        assert_eq!(parsed.due_date.map(|d| d.at.format("%Y-%m-%d %H:%M").to_string()).as_deref(), Some("2030-01-31 09:15"));
+    }
+
+    #[test]
+    fn time_alone_is_today() {
+        assert_eq!(parse("Call mum at 6:30pm").due_date, today_at(0, 18, 30));
+        assert_eq!(parse("Call mum 18:30").due_date, today_at(0, 18, 30));
+        assert_eq!(parse("Lunch 12pm").due_date, today_at(0, 12, 0));
+        assert_eq!(parse("Breakfast 12am").due_date, today_at(0, 0, 0));
+    }
+
+    #[test]
+    fn time_before_date() {
+        assert_eq!(parse("Deploy at 9am tomorrow").due_date, today_at(1, 9, 0));
+    }
+
+    #[test]
+    fn at_without_time_stays_in_title() {
+        let parsed = parse("Look at tomorrow's plan");
+        assert_eq!(parsed.title, "Look at tomorrow's plan");
+        assert_eq!(parsed.due_date, None);
+    }
+
+    #[test]
+    fn only_first_date_is_used() {
+        let parsed = parse("Move today tomorrow");
+        assert_eq!(parsed.title, "Move tomorrow");
+        assert_eq!(parsed.due_date.map(|d| d.at.date_naive()), Some(Local::now().date_naive()));
+    }
+
+    #[test]
+    fn numbers_without_slash_are_not_dates() {
+        let parsed = parse("Buy 2.5 kg rice");
+        assert_eq!(parsed.title, "Buy 2.5 kg rice");
+        assert_eq!(parsed.due_date, None);
+
+        assert_eq!(parse("Seat 3-4 people").due_date, None);
+        assert_eq!(parse("Book 12.06 at 5pm").title, "Book 12.06");
+        assert_eq!(parse("Pay 5/31").title, "Pay");
+        assert_eq!(parse("Pay 5 may").title, "Pay");
+    }
+
+    #[test]
+    fn short_weekdays_alone_are_not_dates() {
+        let parsed = parse("Fix sun roof");
+        assert_eq!(parsed.title, "Fix sun roof");
+        assert_eq!(parsed.due_date, None);
+
+        assert_eq!(parse("Fix sun 3pm").title, "Fix sun");
+        assert_eq!(parse("Sat exam next sat").title, "Sat exam");
+        assert!(parse("Call sunday").due_date.is_some());
+    }
+
+    #[test]
+    fn frequencies() {
+        assert_eq!(parse("Stretch every day").frequency, Some(RecurringFrequency::Daily));
+        assert_eq!(parse("Review every Week").frequency, Some(RecurringFrequency::Weekly));
+        assert_eq!(parse("Invoice every month").frequency, Some(RecurringFrequency::Monthly));
+        assert_eq!(parse("Taxes every year").frequency, Some(RecurringFrequency::Yearly));
+
+        let parsed = parse("Try every option");
+        assert_eq!(parsed.title, "Try every option");
+        assert_eq!(parsed.frequency, None);
+    }
+}
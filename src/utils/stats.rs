@@ -0,0 +1,216 @@
+use crate::models::Task;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Weekday};
+use std::collections::BTreeMap;
+
+/// Days covered by the overdue trend, and at least by the daily counts
+pub const DAYS: usize = 30;
+/// Weeks covered by the weekly completions
+pub const WEEKS: usize = 8;
+
+/// Tasks completed on each of the `days` days up to and including `today`,
+/// oldest first
+pub fn completed_per_day(tasks: &[&Task], today: NaiveDate, days: usize) -> Vec<u64> {
+    per_day(tasks.iter().filter_map(|task| task.completed_at), today, days)
+}
+
+/// Tasks created on each of the `days` days up to and including `today`,
+/// oldest first
+pub fn created_per_day(tasks: &[&Task], today: NaiveDate, days: usize) -> Vec<u64> {
+    per_day(tasks.iter().map(|task| task.created_at), today, days)
+}
+
+fn per_day(times: impl Iterator<Item = DateTime<Local>>, today: NaiveDate, days: usize) -> Vec<u64> {
+    let mut counts = vec![0; days];
+    for time in times {
+        let days_ago = (today - time.date_naive()).num_days();
+        if (0..days as i64).contains(&days_ago) {
+            counts[days - 1 - days_ago as usize] += 1;
+        }
+    }
+    counts
+}
+
+/// First day of the week `date` is in
+pub fn start_of_week(date: NaiveDate, week_start: Weekday) -> NaiveDate {
+    let offset = (date.weekday().num_days_from_monday() + 7 - week_start.num_days_from_monday()) % 7;
+    date - Duration::days(offset as i64)
+}
+
+/// Tasks completed in each of the `weeks` weeks up to and including the
+/// current one, oldest first
+pub fn completed_per_week(tasks: &[&Task], today: NaiveDate, week_start: Weekday, weeks: usize) -> Vec<u64> {
+    let this_week = start_of_week(today, week_start);
+    let mut counts = vec![0; weeks];
+    for completed_at in tasks.iter().filter_map(|task| task.completed_at) {
+        let weeks_ago = (this_week - start_of_week(completed_at.date_naive(), week_start)).num_weeks();
+        if (0..weeks as i64).contains(&weeks_ago) {
+            counts[weeks - 1 - weeks_ago as usize] += 1;
+        }
+    }
+    counts
+}
+
+/// Completions on each day of the week, starting with `week_start`
+pub fn completed_per_weekday(tasks: &[&Task], week_start: Weekday) -> [u64; 7] {
+    let mut counts = [0; 7];
+    for completed_at in tasks.iter().filter_map(|task| task.completed_at) {
+        let weekday = completed_at.weekday().num_days_from_monday() + 7 - week_start.num_days_from_monday();
+        counts[weekday as usize % 7] += 1;
+    }
+    counts
+}
+
+/// Tasks that were overdue on each of the `days` days up to and including
+/// `today`, oldest first. A task is overdue on a day after the one it was
+/// due, until the day it's completed.
+pub fn overdue_per_day(tasks: &[&Task], today: NaiveDate, days: usize) -> Vec<u64> {
+    (0..days)
+        .rev()
+        .map(|days_ago| {
+            let day = today - Duration::days(days_ago as i64);
+            tasks
+                .iter()
+                .filter(|task| task.created_at.date_naive() <= day)
+                .filter(|task| task.due_date.is_some_and(|due| due.date_naive() < day))
+                .filter(|task| task.completed_at.is_none_or(|completed| completed.date_naive() >= day))
+                .count() as u64
+        })
+        .collect()
+}
+
+/// Average time from creation to completion of the completed tasks of each
+/// list, by list id
+pub fn average_completion_times(tasks: &[&Task]) -> BTreeMap<u32, Duration> {
+    let mut totals: BTreeMap<u32, (Duration, i32)> = BTreeMap::new();
+    for task in tasks {
+        let Some(completed_at) = task.completed_at else {
+            continue;
+        };
+        let (total, count) = totals.entry(task.list_id).or_insert((Duration::zero(), 0));
+        *total += (completed_at - task.created_at).max(Duration::zero());
+        *count += 1;
+    }
+    totals.into_iter().map(|(list_id, (total, count))| (list_id, total / count)).collect()
+}
+
+/// How many times in a row a repeating task has been done on time
+#[derive(Debug, Clone, PartialEq)]
+pub struct Streak {
+    pub title: String,
+    pub list_id: u32,
+    /// Instances done on time since the last one that was late or missed
+    pub current: u32,
+    pub best: u32,
+}
+
+/// Streaks of the repeating tasks, longest current streak first. The
+/// instances of a repeating task are told apart from other tasks by their
+/// title and list. Open instances only break a streak once they're overdue.
+pub fn recurring_streaks(tasks: &[&Task], now: DateTime<Local>) -> Vec<Streak> {
+    let mut series: BTreeMap<(u32, &str), Vec<&Task>> = BTreeMap::new();
+    for task in tasks.iter().filter(|task| task.recurring_frequency.is_some()) {
+        series.entry((task.list_id, task.title.as_str())).or_default().push(task);
+    }
+
+    let mut streaks: Vec<Streak> = series
+        .into_iter()
+        .filter(|(_, instances)| instances.iter().any(|task| task.is_completed))
+        .map(|((list_id, title), mut instances)| {
+            instances.sort_by_key(|task| (task.due_date, task.created_at));
+            let (mut current, mut best) = (0, 0);
+            for task in instances {
+                let on_time = match (task.completed_at, task.due_date) {
+                    (Some(completed_at), Some(due)) => completed_at <= due,
+                    (Some(_), None) => true,
+                    (None, Some(due)) if due < now => false,
+                    (None, _) => continue,
+                };
+                current = if on_time { current + 1 } else { 0 };
+                best = best.max(current);
+            }
+            Streak { title: title.to_string(), list_id, current, best }
+        })
+        .collect();
+    streaks.sort_by_key(|streak| std::cmp::Reverse((streak.current, streak.best)));
+    streaks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::RecurringFrequency;
+    use chrono::TimeZone;
+
+    /// Wednesday 15 May 2024
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 5, 15).unwrap()
+    }
+
+    fn at(day: u32, hour: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2024, 5, day, hour, 0, 0).unwrap()
+    }
+
+    fn task(id: u32, created: u32, due: Option<u32>, completed: Option<u32>) -> Task {
+        let mut task = Task::new(id, format!("task {}", id), 1);
+        task.created_at = at(created, 9);
+        task.due_date = due.map(|day| at(day, 23));
+        task.completed_at = completed.map(|day| at(day, 12));
+        task.is_completed = completed.is_some();
+        task
+    }
+
+    #[test]
+    fn daily_and_weekly_counts() {
+        let tasks = [task(1, 1, None, Some(15)), task(2, 2, None, Some(14)), task(3, 3, None, Some(14)), task(4, 13, None, None)];
+        let tasks: Vec<&Task> = tasks.iter().collect();
+
+        assert_eq!(completed_per_day(&tasks, today(), 3), vec![0, 2, 1]);
+        assert_eq!(created_per_day(&tasks, today(), 3), vec![1, 0, 0]);
+        // The week of Monday 13 May holds all three completions
+        assert_eq!(completed_per_week(&tasks, today(), Weekday::Mon, 2), vec![0, 3]);
+        // Tuesday twice and Wednesday once, from Monday or from Sunday
+        assert_eq!(completed_per_weekday(&tasks, Weekday::Mon), [0, 2, 1, 0, 0, 0, 0]);
+        assert_eq!(completed_per_weekday(&tasks, Weekday::Sun), [0, 0, 2, 1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn overdue_until_completed() {
+        let tasks = [task(1, 1, Some(12), Some(14)), task(2, 1, Some(13), None), task(3, 1, Some(20), None)];
+        let tasks: Vec<&Task> = tasks.iter().collect();
+
+        // Days 12 to 15: task 1 is overdue on the 13th and 14th, task 2 from the 14th
+        assert_eq!(overdue_per_day(&tasks, today(), 4), vec![0, 1, 2, 1]);
+    }
+
+    #[test]
+    fn average_time_to_complete() {
+        let tasks = [task(1, 1, None, Some(3)), task(2, 1, None, Some(5)), task(3, 1, None, None)];
+        let tasks: Vec<&Task> = tasks.iter().collect();
+
+        assert_eq!(average_completion_times(&tasks).get(&1), Some(&Duration::hours(75)));
+    }
+
+    #[test]
+    fn streaks_count_on_time_instances() {
+        let mut tasks = vec![
+            task(1, 1, Some(1), Some(1)),
+            task(2, 1, Some(2), Some(4)),
+            task(3, 1, Some(3), Some(3)),
+            task(4, 1, Some(4), Some(4)),
+            task(5, 1, Some(16), None),
+        ];
+        for task in &mut tasks {
+            task.title = "Water plants".to_string();
+            task.recurring_frequency = Some(RecurringFrequency::Daily);
+        }
+        let tasks: Vec<&Task> = tasks.iter().collect();
+
+        let streaks = recurring_streaks(&tasks, at(15, 10));
+        assert_eq!(streaks.len(), 1);
+        assert_eq!((streaks[0].current, streaks[0].best), (2, 2));
+
+        // Missing the next one ends the current streak
+        let streaks = recurring_streaks(&tasks, at(17, 10));
+        assert_eq!((streaks[0].current, streaks[0].best), (0, 2));
+    }
+}
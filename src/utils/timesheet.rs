@@ -0,0 +1,170 @@
+use crate::models::TimeEntry;
+use chrono::{DateTime, Duration, Local, NaiveDate};
+use std::collections::BTreeMap;
+
+/// Time tracked on one day, in total and per list
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimesheetDay {
+    pub date: NaiveDate,
+    pub total: Duration,
+    pub lists: Vec<TimesheetList>,
+}
+
+impl TimesheetDay {
+    /// Rows the day takes on the timesheet: its own, then each list's
+    /// followed by the list's tasks
+    pub fn row_count(&self) -> usize {
+        1 + self.lists.iter().map(|list| 1 + list.tasks.len()).sum::<usize>()
+    }
+}
+
+/// Time tracked in one list on one day, per task. Tasks that were purged
+/// from the trash no longer have a list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimesheetList {
+    pub list_id: Option<u32>,
+    pub total: Duration,
+    pub tasks: Vec<(u32, Duration)>,
+}
+
+/// The time of an entry on each day it covers. An entry running past
+/// midnight counts towards both days.
+pub fn split_by_day(entry: &TimeEntry, now: DateTime<Local>) -> Vec<(NaiveDate, Duration)> {
+    let end = entry.end(now);
+    let mut start = entry.started_at;
+    let mut days = Vec::new();
+    while start < end {
+        let next_midnight = start
+            .date_naive()
+            .succ_opt()
+            .and_then(|day| day.and_hms_opt(0, 0, 0))
+            .and_then(|midnight| midnight.and_local_timezone(Local).earliest())
+            .filter(|midnight| *midnight > start)
+            .unwrap_or(end);
+        let until = next_midnight.min(end);
+        days.push((start.date_naive(), until - start));
+        start = until;
+    }
+    days
+}
+
+/// Time tracked on a task, counting a running timer up to `now`
+pub fn total_time(entries: &[TimeEntry], task_id: u32, now: DateTime<Local>) -> Duration {
+    entries
+        .iter()
+        .filter(|entry| entry.task_id == task_id)
+        .map(|entry| entry.duration(now))
+        .fold(Duration::zero(), |total, duration| total + duration)
+}
+
+/// Tracked time by day, newest first, and on each day by list and task,
+/// each busiest first. `list_of` gives the list of a task.
+pub fn by_day(entries: &[TimeEntry], list_of: impl Fn(u32) -> Option<u32>, now: DateTime<Local>) -> Vec<TimesheetDay> {
+    let mut days: BTreeMap<NaiveDate, BTreeMap<Option<u32>, BTreeMap<u32, Duration>>> = BTreeMap::new();
+    for entry in entries {
+        for (date, duration) in split_by_day(entry, now) {
+            *days
+                .entry(date)
+                .or_default()
+                .entry(list_of(entry.task_id))
+                .or_default()
+                .entry(entry.task_id)
+                .or_insert_with(Duration::zero) += duration;
+        }
+    }
+
+    days.into_iter()
+        .rev()
+        .map(|(date, lists)| {
+            let mut lists: Vec<TimesheetList> = lists
+                .into_iter()
+                .map(|(list_id, tasks)| {
+                    let mut tasks: Vec<(u32, Duration)> = tasks.into_iter().collect();
+                    tasks.sort_by_key(|&(_, duration)| std::cmp::Reverse(duration));
+                    let total = tasks.iter().fold(Duration::zero(), |total, &(_, duration)| total + duration);
+                    TimesheetList { list_id, total, tasks }
+                })
+                .collect();
+            lists.sort_by_key(|list| std::cmp::Reverse(list.total));
+            let total = lists.iter().fold(Duration::zero(), |total, list| total + list.total);
+            TimesheetDay { date, total, lists }
+        })
+        .collect()
+}
+
+/// A duration in hours and minutes, e.g. "2h 05m" or "40m"
+pub fn duration_label(duration: Duration) -> String {
+    let minutes = duration.num_minutes().max(0);
+    if minutes >= 60 {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+/// A running timer's time with seconds, e.g. "1:02:09"
+pub fn clock_label(duration: Duration) -> String {
+    let seconds = duration.num_seconds().max(0);
+    format!("{}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2024, 5, day, hour, minute, 0).unwrap()
+    }
+
+    fn entry(task_id: u32, start: DateTime<Local>, stop: Option<DateTime<Local>>) -> TimeEntry {
+        TimeEntry { id: 0, task_id, started_at: start, stopped_at: stop }
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 5, day).unwrap()
+    }
+
+    #[test]
+    fn entries_are_split_at_midnight() {
+        let late = entry(1, at(14, 23, 30), Some(at(15, 1, 0)));
+        assert_eq!(
+            split_by_day(&late, at(20, 0, 0)),
+            vec![(date(14), Duration::minutes(30)), (date(15), Duration::minutes(60))]
+        );
+
+        // A running timer counts up to now
+        let running = entry(1, at(15, 9, 0), None);
+        assert_eq!(split_by_day(&running, at(15, 9, 45)), vec![(date(15), Duration::minutes(45))]);
+    }
+
+    #[test]
+    fn days_are_grouped_by_list_and_task() {
+        let entries = [
+            entry(1, at(14, 9, 0), Some(at(14, 10, 0))),
+            entry(2, at(14, 11, 0), Some(at(14, 11, 30))),
+            entry(3, at(14, 13, 0), Some(at(14, 15, 0))),
+            entry(1, at(15, 9, 0), Some(at(15, 9, 20))),
+            entry(1, at(15, 10, 0), Some(at(15, 10, 20))),
+        ];
+        // Tasks 1 and 2 are in list 10, task 3 in list 20
+        let list_of = |task_id| Some(if task_id == 3 { 20 } else { 10 });
+        let days = by_day(&entries, list_of, at(20, 0, 0));
+
+        assert_eq!(days.len(), 2);
+        assert_eq!((days[0].date, days[0].total), (date(15), Duration::minutes(40)));
+        assert_eq!(days[0].lists[0].tasks, vec![(1, Duration::minutes(40))]);
+
+        assert_eq!(days[1].total, Duration::minutes(210));
+        let lists: Vec<(Option<u32>, i64)> = days[1].lists.iter().map(|l| (l.list_id, l.total.num_minutes())).collect();
+        assert_eq!(lists, vec![(Some(20), 120), (Some(10), 90)]);
+        assert_eq!(total_time(&entries, 1, at(20, 0, 0)), Duration::minutes(100));
+    }
+
+    #[test]
+    fn labels() {
+        assert_eq!(duration_label(Duration::minutes(40)), "40m");
+        assert_eq!(duration_label(Duration::minutes(125)), "2h 05m");
+        assert_eq!(clock_label(Duration::seconds(3729)), "1:02:09");
+    }
+}
@@ -0,0 +1,28 @@
+use crate::config::Config;
+use crate::models::Storage;
+
+/// A workspace as listed in the workspace switcher
+#[derive(Debug, Clone)]
+pub struct WorkspaceSummary {
+    pub name: String,
+    /// Open and overdue task counts, or `None` if the tasks couldn't be read
+    pub counts: Option<(usize, usize)>,
+}
+
+/// Every workspace with its open and overdue task counts, read from its
+/// task file
+pub fn summaries(config: &Config) -> Vec<WorkspaceSummary> {
+    config
+        .workspace_names()
+        .into_iter()
+        .map(|name| {
+            let counts = config.workspace_dir(&name).ok().and_then(|dir| {
+                let tasks = Storage::new(&dir.to_string_lossy()).load_tasks().ok()?;
+                let open = tasks.iter().filter(|t| !t.is_completed).count();
+                let overdue = tasks.iter().filter(|t| t.is_overdue()).count();
+                Some((open, overdue))
+            });
+            WorkspaceSummary { name, counts }
+        })
+        .collect()
+}